@@ -2,7 +2,10 @@
 //!
 //! Defines the action enums used by various commands.
 
-use clap::Subcommand;
+use clap::{ArgGroup, Args, Subcommand};
+use mcp_execution_core::{RedactedItems, RedactedUrl, sanitize_path_for_error};
+use std::fmt;
+use std::path::Path;
 
 /// Server management actions.
 ///
@@ -33,4 +36,188 @@ pub enum ServerAction {
         /// Server command to validate
         command: String,
     },
+
+    /// Add a server to the MCP config file
+    ///
+    /// Writes to the file given with --config, or ~/.claude/mcp.json. The entry is
+    /// security-validated before anything is written, and every other key in the file is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// mcp-execution-cli server add github --env 'GITHUB_TOKEN=${GITHUB_TOKEN}' \
+    ///     -- npx -y @modelcontextprotocol/server-github
+    ///
+    /// mcp-execution-cli server add remote --http https://api.example.com/mcp \
+    ///     --header 'Authorization=Bearer ${API_KEY}'
+    /// ```
+    Add(AddServerArgs),
+
+    /// Remove a server from the MCP config file
+    ///
+    /// Edits the file given with --config, or ~/.claude/mcp.json.
+    Remove {
+        /// Server name
+        name: String,
+    },
+
+    /// Set a server's connection and discovery timeouts in the MCP config file
+    ///
+    /// Edits the file given with --config, or ~/.claude/mcp.json; other fields of the entry
+    /// are left as they are.
+    #[command(group(
+        ArgGroup::new("timeouts")
+            .required(true)
+            .multiple(true)
+            .args(["connect_timeout_secs", "discover_timeout_secs"])
+    ))]
+    SetTimeout {
+        /// Server name
+        name: String,
+
+        /// Connection (handshake) timeout in seconds (1-600); `connectTimeoutSecs`
+        #[arg(long = "connect-timeout-secs")]
+        connect_timeout_secs: Option<u64>,
+
+        /// Tool discovery timeout in seconds (1-600); `discoverTimeoutSecs`
+        #[arg(long = "discover-timeout-secs")]
+        discover_timeout_secs: Option<u64>,
+    },
+
+    /// Import servers from another MCP host's config into the MCP config file
+    ///
+    /// Reads Claude Desktop, Cursor, VS Code (.vscode/mcp.json), or Windsurf config, or any
+    /// config file given by path. Each server is previewed as new, unchanged, conflicting, or
+    /// invalid, with secret values redacted, and the selected ones are added to the file given
+    /// with --config, or ~/.claude/mcp.json. Conflicting entries are skipped unless named with
+    /// --replace.
+    ///
+    /// # Examples
+    ///
+    /// ```bash
+    /// mcp-execution-cli server import --from cursor --dry-run
+    /// mcp-execution-cli server import --from vscode --skip experimental --replace github
+    /// mcp-execution-cli server import --from ./team-servers.json --only github --only slack
+    /// ```
+    Import(ImportServersArgs),
+}
+
+/// Flags for `server add`.
+///
+/// Exactly one transport is selected: a stdio command after `--`, `--http`, or `--sse`.
+#[derive(Args)]
+#[command(group(
+    ArgGroup::new("transport")
+        .required(true)
+        .args(["command", "http", "sse"])
+))]
+pub struct AddServerArgs {
+    /// Server name (the key under "mcpServers")
+    pub name: String,
+
+    /// Command and arguments for a stdio server
+    #[arg(last = true, value_name = "COMMAND")]
+    pub command: Vec<String>,
+
+    /// Use HTTP transport with the given URL
+    #[arg(long)]
+    pub http: Option<String>,
+
+    /// Use SSE transport with the given URL
+    #[arg(long)]
+    pub sse: Option<String>,
+
+    /// Environment variable in KEY=VALUE format (stdio only; repeatable)
+    ///
+    /// Quote `${VAR}` references so the shell leaves them for the server entry.
+    #[arg(short, long = "env", num_args = 1, conflicts_with_all = ["http", "sse"])]
+    pub env: Vec<String>,
+
+    /// Working directory for the server process (stdio only)
+    #[arg(long, conflicts_with_all = ["http", "sse"])]
+    pub cwd: Option<String>,
+
+    /// HTTP header in KEY=VALUE format (HTTP/SSE only; repeatable)
+    #[arg(long = "header", num_args = 1, conflicts_with = "command")]
+    pub headers: Vec<String>,
+
+    /// Connection (handshake) timeout in seconds (1-600)
+    #[arg(long = "connect-timeout-secs")]
+    pub connect_timeout_secs: Option<u64>,
+
+    /// Tool discovery timeout in seconds (1-600)
+    #[arg(long = "discover-timeout-secs")]
+    pub discover_timeout_secs: Option<u64>,
+
+    /// Replace an existing entry with the same name
+    #[arg(long)]
+    pub force: bool,
+
+    /// Introspect the server and generate its TypeScript files once it is added
+    #[arg(long)]
+    pub generate: bool,
+}
+
+// Hand-written for the same reason as `ServerFlags`'s: `env`/`headers` are raw `KEY=VALUE`
+// strings and the URLs may embed credentials.
+impl fmt::Debug for AddServerArgs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            name,
+            command,
+            http,
+            sse,
+            env,
+            cwd,
+            headers,
+            connect_timeout_secs,
+            discover_timeout_secs,
+            force,
+            generate,
+        } = self;
+        f.debug_struct("AddServerArgs")
+            .field("name", name)
+            .field("command", &RedactedItems(command))
+            .field("http", &http.as_deref().map(RedactedUrl))
+            .field("sse", &sse.as_deref().map(RedactedUrl))
+            .field("env", &RedactedItems(env))
+            .field(
+                "cwd",
+                &cwd.as_deref()
+                    .map(|cwd| sanitize_path_for_error(Path::new(cwd))),
+            )
+            .field("headers", &RedactedItems(headers))
+            .field("connect_timeout_secs", connect_timeout_secs)
+            .field("discover_timeout_secs", discover_timeout_secs)
+            .field("force", force)
+            .field("generate", generate)
+            .finish()
+    }
+}
+
+/// Flags for `server import`.
+#[derive(Args, Debug)]
+pub struct ImportServersArgs {
+    /// Host to import from (claude-desktop, cursor, vscode, windsurf) or a config file path
+    ///
+    /// A path is read from "mcpServers", or from "servers" (VS Code's key) if it has no
+    /// "mcpServers". Write a file named like a host as ./NAME.
+    #[arg(long, value_name = "HOST|PATH")]
+    pub from: String,
+
+    /// Import only this server (repeatable)
+    #[arg(long, value_name = "NAME", num_args = 1, conflicts_with = "skip")]
+    pub only: Vec<String>,
+
+    /// Do not import this server (repeatable)
+    #[arg(long, value_name = "NAME", num_args = 1)]
+    pub skip: Vec<String>,
+
+    /// Overwrite this server's existing, different entry (repeatable)
+    #[arg(long, value_name = "NAME", num_args = 1)]
+    pub replace: Vec<String>,
+
+    /// Show the preview without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
 }
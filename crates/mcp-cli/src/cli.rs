@@ -15,6 +15,8 @@ use crate::actions::ServerAction;
 use crate::commands::common::{ServerSource, TransportArgs};
 use mcp_execution_core::cli::{LogFormat, OutputFormat};
 use mcp_execution_core::{Error as CoreError, RedactedItems, RedactedUrl, sanitize_path_for_error};
+use mcp_execution_skill::HostProfile;
+use mcp_execution_skill::host::{DEFAULT_HOST, HOST_NAMES, host_profile};
 
 /// MCP Code Execution - Secure WASM-based MCP tool execution.
 ///
@@ -68,6 +70,41 @@ pub struct Cli {
             .map(|s| LogFormat::from_str(&s).expect("possible values are LogFormat variants"))
     )]
     pub log_format: Option<LogFormat>,
+
+    /// Read MCP server definitions from this file only.
+    ///
+    /// By default servers are merged from Claude Code's per-project entries in
+    /// ~/.claude.json, the nearest .mcp.json, ~/.claude/mcp.json, the top level of
+    /// ~/.claude.json, and the Claude Desktop config, earlier ones winning. When unset, falls
+    /// back to the `MCP_EXECUTION_CONFIG` environment variable. `generate` bakes the file into
+    /// the generated code; setting `MCP_EXECUTION_CONFIG` at runtime still overrides it.
+    #[arg(long = "config", global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Home directory holding `.claude/` (servers, skills, and mcp.json)
+    ///
+    /// Replaces the user's home directory for every default path, and ignores
+    /// `CLAUDE_CONFIG_DIR`. When unset, falls back to the `MCP_EXECUTION_HOME` environment
+    /// variable, then the user's home directory, with `CLAUDE_CONFIG_DIR` relocating
+    /// `.claude/` itself. Useful for isolated sandboxes that each need their own tree.
+    #[arg(long = "home", global = true, value_name = "DIR")]
+    pub home: Option<PathBuf>,
+
+    /// Agent host to generate for: where bindings go, which instructions `skill` writes, and
+    /// which config file servers are read from
+    ///
+    /// `claude` (default) uses ~/.claude and every config file listed under --config. `codex`
+    /// uses ~/.codex (or `CODEX_HOME`) and writes a section of its AGENTS.md; `cursor` uses
+    /// ~/.cursor and writes .cursor/rules/<server>.mdc; `generic` uses ~/.mcp-execution and
+    /// writes a section of ./AGENTS.md. An explicit --config still wins over the host's file.
+    #[arg(
+        long = "host",
+        global = true,
+        default_value = DEFAULT_HOST,
+        value_parser = PossibleValuesParser::new(HOST_NAMES)
+            .map(|s| host_profile(&s).expect("possible values are host profile names"))
+    )]
+    pub host: &'static dyn HostProfile,
 }
 
 // Hand-written to redact `Commands::Introspect`'s `env`/`headers`/`http`/`sse`
@@ -88,12 +125,18 @@ impl fmt::Debug for Cli {
             verbose,
             format,
             log_format,
+            config,
+            home,
+            host,
         } = self;
         f.debug_struct("Cli")
             .field("command", command)
             .field("verbose", verbose)
             .field("format", format)
             .field("log_format", log_format)
+            .field("config", &config.as_deref().map(sanitize_path_for_error))
+            .field("home", &home.as_deref().map(sanitize_path_for_error))
+            .field("host", &host.name())
             .finish()
     }
 }
@@ -136,11 +179,12 @@ impl fmt::Debug for Cli {
         .args(["from_config", "server", "http", "sse"])
 ))]
 pub struct ServerFlags {
-    /// Load server configuration from ~/.claude/mcp.json by name
+    /// Load server configuration from the MCP config files by name
     ///
     /// When specified, all other server configuration options are rejected as
-    /// conflicting arguments. The server must be defined in ~/.claude/mcp.json
-    /// with matching name.
+    /// conflicting arguments. The server must be defined under `mcpServers` in
+    /// .mcp.json, ~/.claude/mcp.json, ~/.claude.json, the Claude Desktop config,
+    /// or the file given with --config.
     ///
     /// Example mcp.json (stdio and http entries can be mixed freely):
     /// ```json
@@ -357,7 +401,7 @@ pub enum Commands {
     ///
     /// # Configuration Modes
     ///
-    /// 1. Load from ~/.claude/mcp.json (recommended):
+    /// 1. Load from the MCP config files (recommended):
     ///    ```bash
     ///    mcp-execution-cli introspect --from-config github
     ///    ```
@@ -390,6 +434,9 @@ pub enum Commands {
     /// # HTTP transport
     /// mcp-execution-cli introspect --http https://api.githubcopilot.com/mcp/ \
     ///     --header "Authorization=Bearer ghp_xxx"
+    ///
+    /// # Keep a working manual invocation as an mcp.json entry
+    /// mcp-execution-cli introspect github-mcp-server --arg=stdio --save-as github
     /// ```
     Introspect {
         /// Server selection, transport, and timeout flags (shared with `generate`)
@@ -399,6 +446,11 @@ pub enum Commands {
         /// Show detailed tool schemas
         #[arg(short, long)]
         detailed: bool,
+
+        /// Once introspection succeeds, save the server flags as this entry in the MCP config
+        /// file (--config, or ~/.claude/mcp.json)
+        #[arg(long, value_name = "NAME", conflicts_with = "from_config")]
+        save_as: Option<String>,
     },
 
     /// Generate Claude Code skill file from progressive loading tools.
@@ -428,6 +480,9 @@ pub enum Commands {
     ///
     /// # Overwrite existing skill
     /// mcp-execution-cli skill --server github --overwrite
+    ///
+    /// # Skill for bindings committed with the current repository
+    /// mcp-execution-cli skill --server github --project
     /// ```
     Skill {
         /// Server identifier (e.g., "github")
@@ -465,6 +520,12 @@ pub enum Commands {
         /// Overwrite existing SKILL.md file
         #[arg(long)]
         overwrite: bool,
+
+        /// Read bindings from, and write the skill into, the current repository
+        /// (e.g. `.claude/servers` and `.claude/skills/{server}/SKILL.md`) instead of the home
+        /// directory. The skill refers to them by repo-relative paths.
+        #[arg(long, conflicts_with = "servers_dir")]
+        project: bool,
     },
 
     /// Generate progressive loading code from MCP server.
@@ -474,7 +535,7 @@ pub enum Commands {
     ///
     /// # Configuration Modes
     ///
-    /// 1. Load from ~/.claude/mcp.json (recommended):
+    /// 1. Load from the MCP config files (recommended):
     ///    ```bash
     ///    mcp-execution-cli generate --from-config github
     ///    ```
@@ -498,6 +559,21 @@ pub enum Commands {
     ///     --arg=-e --arg=GITHUB_PERSONAL_ACCESS_TOKEN \
     ///     --arg=ghcr.io/github/github-mcp-server \
     ///     --env=GITHUB_PERSONAL_ACCESS_TOKEN=ghp_xxx
+    ///
+    /// # Only issue tools, never admin ones (remembered for later regenerations)
+    /// mcp-execution-cli generate --from-config github --include 'issue_*' --exclude '*_admin_*'
+    ///
+    /// # Review what a regeneration would change, file by file
+    /// mcp-execution-cli generate --from-config github --dry-run --diff
+    ///
+    /// # CI: fail if the committed files are out of date
+    /// mcp-execution-cli generate --from-config github --progressive-output ./servers --check
+    ///
+    /// # Regenerate while developing a server
+    /// mcp-execution-cli generate --from-config my-server --watch --watch-path ./my-server/src
+    ///
+    /// # Commit bindings with the repository, next to its .mcp.json
+    /// mcp-execution-cli generate --from-config github --project
     /// ```
     Generate {
         /// Server selection, transport, and timeout flags (shared with `introspect`)
@@ -514,9 +590,77 @@ pub enum Commands {
         #[arg(long)]
         progressive_output: Option<PathBuf>,
 
+        /// Write into the current repository (e.g. `./.claude/servers/{name}`) instead of the
+        /// home directory
+        ///
+        /// The repository root is the nearest directory with `.git`, or else with `.mcp.json`.
+        /// The generated bridge reads the project's `.mcp.json` relative to itself before the
+        /// user's own config, and no generated file names an absolute path.
+        #[arg(long, conflicts_with = "progressive_output")]
+        project: bool,
+
         /// Preview files that would be generated without writing to disk
+        ///
+        /// Each file is reported as new, modified, unchanged, or to be deleted, compared with
+        /// the current output directory.
         #[arg(long)]
         dry_run: bool,
+
+        /// With `--dry-run`, also print a unified diff for every new or modified file
+        #[arg(long, requires = "dry_run")]
+        diff: bool,
+
+        /// Compare freshly generated files with those on disk without writing anything
+        ///
+        /// Exits non-zero and prints a unified diff of every differing file if the server
+        /// directory is out of date. `_meta.json`'s `generated_at` timestamp is ignored.
+        #[arg(long, conflicts_with = "dry_run")]
+        check: bool,
+
+        /// Keep regenerating until interrupted, whenever the server's tools change
+        ///
+        /// Watches the MCP config files (with `--from-config`), every `--watch-path`, and the
+        /// server's `notifications/tools/list_changed`. Each cycle re-introspects the server and
+        /// regenerates only if its tool digest changed, printing one log line.
+        #[arg(long, conflicts_with_all = ["dry_run", "check"])]
+        watch: bool,
+
+        /// With `--watch`, also watch this file or directory (e.g. the server's source or
+        /// binary); repeatable
+        #[arg(
+            long = "watch-path",
+            value_name = "PATH",
+            num_args = 1,
+            requires = "watch"
+        )]
+        watch_paths: Vec<PathBuf>,
+
+        /// Only generate tools whose name matches this glob (`*`, `?`); repeatable
+        ///
+        /// Replaces the filter recorded by a previous `generate` run for this server.
+        /// Without `--include`/`--exclude`, that recorded filter is reused.
+        #[arg(long = "include", value_name = "GLOB", num_args = 1)]
+        include: Vec<String>,
+
+        /// Skip tools whose name matches this glob (`*`, `?`); repeatable
+        ///
+        /// Takes precedence over `--include`. Replaces the filter recorded by a previous
+        /// `generate` run for this server.
+        #[arg(long = "exclude", value_name = "GLOB", num_args = 1)]
+        exclude: Vec<String>,
+
+        /// Generate every tool, discarding the filter recorded by a previous `generate` run
+        #[arg(long, conflicts_with_all = ["include", "exclude"])]
+        no_filter: bool,
+
+        /// Once generation succeeds, save the server flags as this entry in the MCP config
+        /// file (--config, or ~/.claude/mcp.json)
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["from_config", "dry_run", "check", "watch"]
+        )]
+        save_as: Option<String>,
     },
 
     /// Manage MCP server connections.
@@ -562,10 +706,15 @@ pub enum Commands {
 impl fmt::Debug for Commands {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Introspect { flags, detailed } => f
+            Self::Introspect {
+                flags,
+                detailed,
+                save_as,
+            } => f
                 .debug_struct("Introspect")
                 .field("flags", flags)
                 .field("detailed", detailed)
+                .field("save_as", save_as)
                 .finish(),
             Self::Skill {
                 server,
@@ -574,6 +723,7 @@ impl fmt::Debug for Commands {
                 skill_name,
                 hints,
                 overwrite,
+                project,
             } => f
                 .debug_struct("Skill")
                 .field("server", server)
@@ -582,18 +732,37 @@ impl fmt::Debug for Commands {
                 .field("skill_name", skill_name)
                 .field("hints", hints)
                 .field("overwrite", overwrite)
+                .field("project", project)
                 .finish(),
             Self::Generate {
                 flags,
                 name,
                 progressive_output,
+                project,
                 dry_run,
+                diff,
+                check,
+                watch,
+                watch_paths,
+                include,
+                exclude,
+                no_filter,
+                save_as,
             } => f
                 .debug_struct("Generate")
                 .field("flags", flags)
                 .field("name", name)
                 .field("progressive_output", progressive_output)
+                .field("project", project)
                 .field("dry_run", dry_run)
+                .field("diff", diff)
+                .field("check", check)
+                .field("watch", watch)
+                .field("watch_paths", watch_paths)
+                .field("include", include)
+                .field("exclude", exclude)
+                .field("no_filter", no_filter)
+                .field("save_as", save_as)
                 .finish(),
             Self::Server { action } => f.debug_struct("Server").field("action", action).finish(),
             Self::Setup => write!(f, "Setup"),
@@ -815,12 +984,293 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_generate_check() {
+        let cli = Cli::parse_from(["mcp-cli", "generate", "server", "--check"]);
+        if let Commands::Generate { check, dry_run, .. } = cli.command {
+            assert!(check);
+            assert!(!dry_run);
+        } else {
+            panic!("Expected Generate command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_diff_requires_dry_run() {
+        let cli = Cli::parse_from(["mcp-cli", "generate", "server", "--dry-run", "--diff"]);
+        if let Commands::Generate { dry_run, diff, .. } = cli.command {
+            assert!(dry_run);
+            assert!(diff);
+        } else {
+            panic!("Expected Generate command");
+        }
+
+        let result = Cli::try_parse_from(["mcp-cli", "generate", "server", "--diff"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_check_conflicts_with_dry_run() {
+        let result = Cli::try_parse_from(["mcp-cli", "generate", "server", "--check", "--dry-run"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_project() {
+        let cli = Cli::parse_from([
+            "mcp-cli",
+            "generate",
+            "--from-config",
+            "github",
+            "--project",
+        ]);
+        if let Commands::Generate { project, .. } = cli.command {
+            assert!(project);
+        } else {
+            panic!("Expected Generate command");
+        }
+
+        let result = Cli::try_parse_from([
+            "mcp-cli",
+            "generate",
+            "--from-config",
+            "github",
+            "--project",
+            "--progressive-output",
+            "/tmp/out",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_watch_with_paths() {
+        let cli = Cli::parse_from([
+            "mcp-cli",
+            "generate",
+            "--from-config",
+            "dev",
+            "--watch",
+            "--watch-path",
+            "./src",
+            "--watch-path",
+            "./target/debug/dev-server",
+        ]);
+        if let Commands::Generate {
+            watch, watch_paths, ..
+        } = cli.command
+        {
+            assert!(watch);
+            assert_eq!(
+                watch_paths,
+                vec![
+                    PathBuf::from("./src"),
+                    PathBuf::from("./target/debug/dev-server")
+                ]
+            );
+        } else {
+            panic!("Expected Generate command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_watch_conflicts_and_requirements() {
+        for conflicting in ["--dry-run", "--check"] {
+            let result =
+                Cli::try_parse_from(["mcp-cli", "generate", "server", "--watch", conflicting]);
+            assert!(
+                result.is_err(),
+                "--watch should conflict with {conflicting}"
+            );
+        }
+
+        let result = Cli::try_parse_from(["mcp-cli", "generate", "server", "--watch-path", "src"]);
+        assert!(result.is_err(), "--watch-path should require --watch");
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_tool_filter_flags_repeat() {
+        let cli = Cli::parse_from([
+            "mcp-cli",
+            "generate",
+            "server",
+            "--include",
+            "issue_*",
+            "--include",
+            "pr_*",
+            "--exclude",
+            "*_admin_*",
+        ]);
+        if let Commands::Generate {
+            include,
+            exclude,
+            no_filter,
+            ..
+        } = cli.command
+        {
+            assert_eq!(include, ["issue_*", "pr_*"]);
+            assert_eq!(exclude, ["*_admin_*"]);
+            assert!(!no_filter);
+        } else {
+            panic!("Expected Generate command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_generate_no_filter_conflicts_with_patterns() {
+        let result = Cli::try_parse_from([
+            "mcp-cli",
+            "generate",
+            "server",
+            "--no-filter",
+            "--exclude",
+            "*_admin_*",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_cli_parsing_server_list() {
         let cli = Cli::parse_from(["mcp-cli", "server", "list"]);
         assert!(matches!(cli.command, Commands::Server { .. }));
     }
 
+    #[test]
+    fn test_cli_parsing_server_add_stdio_takes_command_after_separator() {
+        let cli = Cli::parse_from([
+            "mcp-cli", "server", "add", "github", "--env", "TOKEN=x", "--", "npx", "-y", "pkg",
+        ]);
+        let Commands::Server {
+            action: ServerAction::Add(args),
+        } = cli.command
+        else {
+            panic!("expected server add");
+        };
+        assert_eq!(args.name, "github");
+        assert_eq!(args.command, ["npx", "-y", "pkg"]);
+        assert_eq!(args.env, ["TOKEN=x"]);
+    }
+
+    #[test]
+    fn test_cli_parsing_server_add_requires_one_transport() {
+        assert!(Cli::try_parse_from(["mcp-cli", "server", "add", "github"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "mcp-cli",
+                "server",
+                "add",
+                "remote",
+                "--http",
+                "https://a.example/mcp",
+                "--sse",
+                "https://b.example/mcp",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_server_add_rejects_env_with_http() {
+        let result = Cli::try_parse_from([
+            "mcp-cli",
+            "server",
+            "add",
+            "remote",
+            "--http",
+            "https://a.example/mcp",
+            "--env",
+            "A=b",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_server_set_timeout_requires_a_timeout() {
+        assert!(Cli::try_parse_from(["mcp-cli", "server", "set-timeout", "github"]).is_err());
+        let cli = Cli::parse_from([
+            "mcp-cli",
+            "server",
+            "set-timeout",
+            "github",
+            "--discover-timeout-secs",
+            "90",
+        ]);
+        assert!(matches!(
+            cli.command,
+            Commands::Server {
+                action: ServerAction::SetTimeout {
+                    connect_timeout_secs: None,
+                    discover_timeout_secs: Some(90),
+                    ..
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cli_parsing_server_import() {
+        let cli = Cli::parse_from([
+            "mcp-cli",
+            "server",
+            "import",
+            "--from",
+            "cursor",
+            "--skip",
+            "a",
+            "--replace",
+            "b",
+            "--dry-run",
+        ]);
+        let Commands::Server {
+            action: ServerAction::Import(args),
+        } = cli.command
+        else {
+            panic!("expected server import");
+        };
+        assert_eq!(args.from, "cursor");
+        assert_eq!(args.skip, ["a"]);
+        assert_eq!(args.replace, ["b"]);
+        assert!(args.dry_run);
+
+        assert!(Cli::try_parse_from(["mcp-cli", "server", "import"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "mcp-cli", "server", "import", "--from", "cursor", "--only", "a", "--skip", "b",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_save_as() {
+        let cli = Cli::parse_from(["mcp-cli", "introspect", "server", "--save-as", "mine"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Introspect { save_as: Some(ref name), .. } if name == "mine"
+        ));
+
+        for argv in [
+            &[
+                "mcp-cli",
+                "introspect",
+                "--from-config",
+                "x",
+                "--save-as",
+                "y",
+            ][..],
+            &[
+                "mcp-cli",
+                "generate",
+                "server",
+                "--save-as",
+                "y",
+                "--dry-run",
+            ],
+            &["mcp-cli", "generate", "server", "--save-as", "y", "--watch"],
+        ] {
+            assert!(Cli::try_parse_from(argv).is_err(), "{argv:?}");
+        }
+    }
+
     #[test]
     fn test_cli_verbose_flag() {
         let cli = Cli::parse_from(["mcp-cli", "--verbose", "introspect", "github"]);
@@ -929,6 +1379,26 @@ mod tests {
         assert_eq!(cli.log_format, Some(LogFormat::Json));
     }
 
+    #[test]
+    fn test_cli_host_global_flag() {
+        let cli = Cli::parse_from(["mcp-cli", "skill", "--server", "github", "--host", "cursor"]);
+        assert_eq!(cli.host.name(), "cursor");
+
+        let cli = Cli::parse_from(["mcp-cli", "setup"]);
+        assert_eq!(cli.host.name(), "claude");
+
+        assert!(Cli::try_parse_from(["mcp-cli", "setup", "--host", "emacs"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_home_global_flag() {
+        let cli = Cli::parse_from(["mcp-cli", "setup", "--home", "/sandbox/agent-1"]);
+        assert_eq!(cli.home, Some(PathBuf::from("/sandbox/agent-1")));
+
+        let cli = Cli::parse_from(["mcp-cli", "setup"]);
+        assert_eq!(cli.home, None);
+    }
+
     #[test]
     fn test_cli_log_format_possible_values_parse_via_from_str() {
         let cmd = Cli::command();
@@ -986,6 +1456,7 @@ mod tests {
             skill_name,
             hints,
             overwrite,
+            project,
         } = cli.command
         {
             assert_eq!(server, "github");
@@ -994,6 +1465,7 @@ mod tests {
             assert!(skill_name.is_none());
             assert!(hints.is_empty());
             assert!(!overwrite);
+            assert!(!project);
         } else {
             panic!("Expected Skill command");
         }
@@ -1025,6 +1497,7 @@ mod tests {
             skill_name,
             hints,
             overwrite,
+            project,
         } = cli.command
         {
             assert_eq!(server, "github");
@@ -1036,6 +1509,7 @@ mod tests {
                 vec!["pull requests".to_string(), "code review".to_string()]
             );
             assert!(overwrite);
+            assert!(!project);
         } else {
             panic!("Expected Skill command");
         }
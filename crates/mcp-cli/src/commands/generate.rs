@@ -5,18 +5,44 @@
 //! 1. Introspects the server to discover tools and schemas
 //! 2. Generates TypeScript files for progressive loading (one file per tool)
 //! 3. Saves files to `~/.claude/servers/{server-id}/` directory
+//!
+//! An optional include/exclude tool filter narrows which tools are generated. It is recorded in
+//! the generated `_meta.json`, so a later run without `--include`/`--exclude` keeps applying it.
+//!
+//! With `--dry-run` or `--check`, nothing is written: the freshly generated files are compared
+//! with the server directory instead. A dry run reports each file as new, modified, unchanged, or
+//! to be deleted (with per-file diffs under `--diff`); a check exits non-zero with a diff if
+//! anything differs.
+//!
+//! With `--watch`, generation is repeated whenever the server's tools may have changed; see
+//! [`run_watch`].
 
-use super::common::{ServerSource, derive_server_id_from_path_or_name, resolve_server_config};
+mod compare;
+mod watch;
+
+pub use self::watch::run_watch;
+
+use self::compare::{FileComparison, FileStatus, compare_with_disk};
+use super::common::{
+    ServerSource, derive_server_id_from_path_or_name, resolve_paths, resolve_server_config,
+};
 use crate::formatters::escape_display;
 use anyhow::{Context, Result};
 use mcp_execution_codegen::GeneratedCode;
-use mcp_execution_codegen::progressive::ProgressiveGenerator;
+use mcp_execution_codegen::progressive::{
+    BridgeConfigPaths, ProgressiveGenerator, ToolCategorization, reusable_categorizations,
+};
 use mcp_execution_core::cli::{ExitCode, OutputFormat};
-use mcp_execution_core::{ServerConfig, ServerId};
+use mcp_execution_core::mcp_config::McpConfigLocations;
+use mcp_execution_core::metadata::read_persisted_tool_metadata;
+use mcp_execution_core::tool_filter::{ToolFilter, read_persisted_tool_filter};
+use mcp_execution_core::untrusted::sanitize_untrusted_text;
+use mcp_execution_core::{Error as CoreError, ServerConfig, ServerId};
 use mcp_execution_files::{ExportOptions, FilesBuilder};
 use mcp_execution_introspector::{Introspector, ServerInfo};
 use mcp_execution_skill::validate_server_id;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 
@@ -29,6 +55,8 @@ struct GenerationResult {
     server_name: String,
     /// Number of tools generated
     tool_count: usize,
+    /// Number of discovered tools left out by the tool filter
+    excluded_count: usize,
     /// Path where files were saved
     output_path: String,
     /// Hint describing the required post-export step (issue #257).
@@ -46,6 +74,24 @@ struct FilePreview {
     path: String,
     /// File size in bytes
     size: usize,
+    /// How the file compares with the one currently on disk
+    status: FileStatus,
+    /// Unified diff from the on-disk file to the generated one, with `--diff`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+}
+
+/// Per-status file counts of a dry run.
+#[derive(Debug, Default, Serialize)]
+struct ChangeCounts {
+    /// Files that do not exist yet
+    new: usize,
+    /// Files whose content would change
+    modified: usize,
+    /// Files that would be rewritten unchanged
+    unchanged: usize,
+    /// Files on disk the regeneration would remove
+    deleted: usize,
 }
 
 /// Result of a dry-run preview.
@@ -57,14 +103,186 @@ struct DryRunResult {
     server_name: String,
     /// Output path that would be used
     output_path: String,
-    /// Files that would be generated
+    /// Files that would be generated, sorted by path
     files: Vec<FilePreview>,
+    /// Files currently on disk that the regeneration would remove, sorted
+    deleted: Vec<String>,
+    /// How many files fall under each status
+    changes: ChangeCounts,
     /// Total number of files
     total_files: usize,
     /// Total estimated size in bytes
     total_size: usize,
 }
 
+/// Result of a `--check` comparison.
+#[derive(Debug, Serialize)]
+struct CheckResult {
+    /// Server ID
+    server_id: String,
+    /// Server name
+    server_name: String,
+    /// Server directory that was compared
+    output_path: String,
+    /// Whether every generated file matches the server directory
+    up_to_date: bool,
+    /// Number of generated files compared
+    files_checked: usize,
+    /// Files that differ, sorted by path
+    differences: Vec<FileDifference>,
+}
+
+/// One differing file in a [`CheckResult`].
+#[derive(Debug, Serialize)]
+struct FileDifference {
+    /// Path relative to the server directory
+    path: String,
+    /// How the file differs
+    status: FileStatus,
+    /// Unified diff from the on-disk file to the generated one, for modified files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diff: Option<String>,
+}
+
+impl From<&FileComparison> for FileDifference {
+    fn from(comparison: &FileComparison) -> Self {
+        Self {
+            path: comparison.path.clone(),
+            status: comparison.status,
+            diff: (comparison.status == FileStatus::Modified)
+                .then(|| comparison.unified_diff())
+                .flatten(),
+        }
+    }
+}
+
+/// Cap, in `char`s, on one diff line echoed to the terminal by `--check` or `--dry-run --diff`.
+const MAX_DIFF_LINE_LEN: usize = 1000;
+
+/// What `generate` does with the files it renders.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_cli::commands::generate::GenerateMode;
+///
+/// assert_eq!(GenerateMode::from_flags(false, false, false), GenerateMode::Write);
+/// assert_eq!(
+///     GenerateMode::from_flags(true, true, false),
+///     GenerateMode::DryRun { diff: true }
+/// );
+/// assert_eq!(GenerateMode::from_flags(false, false, true), GenerateMode::Check);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GenerateMode {
+    /// Export the files to the server directory.
+    #[default]
+    Write,
+    /// List the files that would be written and how each compares with the one on disk,
+    /// without writing them.
+    DryRun {
+        /// Include a unified diff for every new or modified file.
+        diff: bool,
+    },
+    /// Compare the files with the server directory, without writing them.
+    Check,
+}
+
+impl GenerateMode {
+    /// Builds a mode from `generate`'s `--dry-run`/`--diff`/`--check` flags. clap already keeps
+    /// `--dry-run` and `--check` mutually exclusive and makes `--diff` require `--dry-run`.
+    #[must_use]
+    pub const fn from_flags(dry_run: bool, diff: bool, check: bool) -> Self {
+        if check {
+            Self::Check
+        } else if dry_run {
+            Self::DryRun { diff }
+        } else {
+            Self::Write
+        }
+    }
+}
+
+/// Where `generate` writes server directories.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_cli::commands::generate::OutputDir;
+/// use std::path::PathBuf;
+///
+/// assert_eq!(OutputDir::from(None), OutputDir::Default);
+/// assert_eq!(
+///     OutputDir::from(Some(PathBuf::from("/tmp/out"))),
+///     OutputDir::Custom(PathBuf::from("/tmp/out"))
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum OutputDir {
+    /// The servers directory the config locations resolve to (`~/.claude/servers` by default).
+    #[default]
+    Default,
+    /// A directory given with `--progressive-output`.
+    Custom(PathBuf),
+    /// `--project`: a bindings directory inside the project at `root`. The runtime bridge
+    /// bakes only the relative way back to `root`, so the generated files name no path on this
+    /// machine and work wherever the project is checked out.
+    Project {
+        /// The project root, where the project's `.mcp.json` lives.
+        root: PathBuf,
+        /// The bindings directory under `root`, e.g. `<root>/.claude/servers`.
+        bindings_dir: PathBuf,
+    },
+}
+
+impl From<Option<PathBuf>> for OutputDir {
+    fn from(dir: Option<PathBuf>) -> Self {
+        dir.map_or(Self::Default, Self::Custom)
+    }
+}
+
+/// How `generate` picks the tool filter for a run.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_cli::commands::generate::FilterSelection;
+///
+/// let selection = FilterSelection::from_flags(vec!["issue_*".to_string()], vec![], false).unwrap();
+/// assert!(matches!(selection, FilterSelection::Explicit(_)));
+///
+/// let selection = FilterSelection::from_flags(vec![], vec![], false).unwrap();
+/// assert_eq!(selection, FilterSelection::Persisted);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FilterSelection {
+    /// Reuse the filter recorded in the server directory's existing `_meta.json`, if any.
+    #[default]
+    Persisted,
+    /// Use this filter, replacing any recorded one.
+    Explicit(ToolFilter),
+    /// Generate every tool, discarding any recorded filter.
+    AllTools,
+}
+
+impl FilterSelection {
+    /// Builds a selection from `generate`'s `--include`/`--exclude`/`--no-filter` flags.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CoreError::ValidationError`] if any pattern fails [`ToolFilter::new`]'s
+    /// validation.
+    pub fn from_flags(include: Vec<String>, exclude: Vec<String>, no_filter: bool) -> Result<Self> {
+        if no_filter {
+            return Ok(Self::AllTools);
+        }
+        if include.is_empty() && exclude.is_empty() {
+            return Ok(Self::Persisted);
+        }
+        Ok(Self::Explicit(ToolFilter::new(include, exclude)?))
+    }
+}
+
 #[expect(
     clippy::cast_precision_loss,
     reason = "Converting byte counts to f64 for human-readable KB/MB formatting; precision loss \
@@ -85,42 +303,53 @@ fn format_size(bytes: usize) -> String {
 /// Generates progressive loading TypeScript files from an MCP server.
 ///
 /// This command performs the following steps:
-/// 1. Builds `ServerConfig` from CLI arguments or loads from ~/.claude/mcp.json
+/// 1. Builds `ServerConfig` from CLI arguments or loads it from the MCP config files
 /// 2. Introspects the MCP server to discover tools
 /// 3. Generates TypeScript files (one per tool) using progressive loading pattern
-/// 4. Exports VFS to `~/.claude/servers/{server-id}/` directory
+/// 4. Exports VFS to `~/.claude/servers/{server-id}/` directory, or previews or checks it
+///    without writing, depending on `mode`
 ///
 /// # Arguments
 ///
-/// * `source` - Resolved server-selection source: either a `~/.claude/mcp.json`
-///   name or CLI transport flags with timeout overrides. Timeout overrides
+/// * `source` - Resolved server-selection source: either an MCP config
+///   server name or CLI transport flags with timeout overrides. Timeout overrides
 ///   only exist on the `Flags` arm — a `Config` source always uses the
 ///   `mcp.json` entry's own `connectTimeoutSecs`/`discoverTimeoutSecs`, so
 ///   there is no "ignored override" state to document.
 /// * `name` - Custom server name for directory (default: `server_id`)
-/// * `output_dir` - Custom output directory (default: ~/.claude/servers/)
-/// * `dry_run` - When true, preview files without writing to disk
+/// * `output_dir` - Where server directories go; see [`OutputDir`]
+/// * `mode` - Whether to write, preview, or check the files; see [`GenerateMode`]
+/// * `filter` - Which tools to generate; see [`FilterSelection`]
 /// * `output_format` - Output format (json, text, pretty)
+/// * `locations` - Where to look for MCP config files when `source` is `Config`
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - Server configuration is invalid
-/// - Server not found in mcp.json (when using --from-config)
+/// - Server not found in any MCP config file (when using --from-config)
 /// - Server connection fails
 /// - Tool introspection fails
+/// - The tool filter is invalid, cannot be read back from `_meta.json`, or excludes every tool
 /// - Code generation fails
-/// - File export fails (skipped in dry-run mode)
+/// - File export fails (skipped in dry-run and check mode)
+/// - The server directory cannot be read (check mode)
+///
+/// In check mode, an out-of-date server directory is not an error: it returns
+/// [`ExitCode::ERROR`] after printing the differences.
 ///
 /// # Examples
 ///
 /// ```no_run
 /// use mcp_execution_cli::commands::common::{ServerSource, TransportArgs};
-/// use mcp_execution_cli::commands::generate;
+/// use mcp_execution_cli::commands::generate::{self, FilterSelection, GenerateMode, OutputDir};
 /// use mcp_execution_core::cli::OutputFormat;
+/// use mcp_execution_core::mcp_config::McpConfigLocations;
 /// use std::path::PathBuf;
 ///
 /// # async fn example() -> anyhow::Result<()> {
+/// let locations = McpConfigLocations::discover(None);
+///
 /// // Generate from a stdio transport
 /// let exit_code = generate::run(
 ///     ServerSource::Flags {
@@ -134,9 +363,11 @@ fn format_size(bytes: usize) -> String {
 ///         discover_timeout_secs: None,
 ///     },
 ///     None,
-///     None,
-///     false,
-///     OutputFormat::Pretty
+///     OutputDir::Default,
+///     GenerateMode::Write,
+///     FilterSelection::Persisted,
+///     OutputFormat::Pretty,
+///     &locations,
 /// ).await?;
 ///
 /// // Generate with custom output directory and name override
@@ -150,9 +381,11 @@ fn format_size(bytes: usize) -> String {
 ///         discover_timeout_secs: Some(30),
 ///     },
 ///     Some("my-custom-name".to_string()),
-///     Some(PathBuf::from("/tmp/generated")),
-///     false,
-///     OutputFormat::Json
+///     OutputDir::Custom(PathBuf::from("/tmp/generated")),
+///     GenerateMode::Check,
+///     FilterSelection::from_flags(vec!["issue_*".to_string()], vec![], false)?,
+///     OutputFormat::Json,
+///     &locations,
 /// ).await?;
 /// # Ok(())
 /// # }
@@ -160,9 +393,11 @@ fn format_size(bytes: usize) -> String {
 pub async fn run(
     source: ServerSource,
     name: Option<String>,
-    output_dir: Option<PathBuf>,
-    dry_run: bool,
+    output_dir: OutputDir,
+    mode: GenerateMode,
+    filter: FilterSelection,
     output_format: OutputFormat,
+    locations: &McpConfigLocations,
 ) -> Result<ExitCode> {
     // Captured before `source` is consumed below: an id resolved from
     // `--from-config` without a `--name` override is the one case
@@ -171,7 +406,7 @@ pub async fn run(
     // given the id came from its own, already-validated value instead.
     let id_from_unvalidated_config_key =
         matches!(source, ServerSource::Config { .. }) && name.is_none();
-    let (server_id, server_config) = resolve_server_config(source)?;
+    let (server_id, server_config) = resolve_server_config(source, locations)?;
 
     let server_info = discover_server_info(server_id, &server_config, name.as_deref()).await?;
 
@@ -180,19 +415,180 @@ pub async fn run(
         return Ok(ExitCode::SUCCESS);
     }
 
-    let server_dir_name = resolve_server_dir_name(&server_info, id_from_unvalidated_config_key)?;
-    let generated_code = generate_code(&server_info, &server_config)?;
+    let PreparedGeneration {
+        base_dir,
+        output_path,
+        tool_count,
+        generated_code,
+    } = prepare_generation(
+        &server_info,
+        &server_config,
+        id_from_unvalidated_config_key,
+        &output_dir,
+        filter,
+        locations,
+    )?;
+
+    match mode {
+        GenerateMode::Write => {}
+        GenerateMode::DryRun { diff } => {
+            return render_dry_run(
+                &server_info,
+                generated_code,
+                &output_path,
+                diff,
+                output_format,
+            );
+        }
+        GenerateMode::Check => {
+            return render_check(&server_info, generated_code, &output_path, output_format);
+        }
+    }
+
+    export_generated_code(generated_code, &base_dir, &output_path)?;
 
-    let base_dir = resolve_base_dir(output_dir)?;
+    render_success(&server_info, tool_count, &output_path, output_format)
+}
+
+/// Generated files for one server, ready to be written, previewed, or checked.
+struct PreparedGeneration {
+    /// Base directory server directories are exported under
+    base_dir: PathBuf,
+    /// The server's own directory under `base_dir`
+    output_path: PathBuf,
+    /// Number of discovered tools the tool filter keeps
+    tool_count: usize,
+    /// The generated files
+    generated_code: GeneratedCode,
+}
+
+/// Resolves where `server_info`'s files go and which tools to keep, then generates them.
+///
+/// Shared by a one-off [`run`] and each `--watch` cycle.
+///
+/// # Errors
+///
+/// Returns an error if the server id is not a valid directory name, the home directory cannot
+/// be determined, the tool filter cannot be resolved or keeps no tools, or generation fails.
+fn prepare_generation(
+    server_info: &ServerInfo,
+    server_config: &ServerConfig,
+    id_from_unvalidated_config_key: bool,
+    output_dir: &OutputDir,
+    filter: FilterSelection,
+    locations: &McpConfigLocations,
+) -> Result<PreparedGeneration> {
+    let server_dir_name = resolve_server_dir_name(server_info, id_from_unvalidated_config_key)?;
+    let base_dir = resolve_base_dir(output_dir, locations)?;
     let output_path = base_dir.join(&server_dir_name);
+    let config_paths = match output_dir {
+        OutputDir::Project { root, .. } => {
+            BridgeConfigPaths::project(path_back_to(root, &output_path)?)
+        }
+        OutputDir::Default | OutputDir::Custom(_) => BridgeConfigPaths::from_locations(locations),
+    };
 
-    if dry_run {
-        return render_dry_run(&server_info, &generated_code, &output_path, output_format);
+    let tool_filter = resolve_tool_filter(filter, &output_path)?;
+    let tool_count = count_kept_tools(server_info, &tool_filter)?;
+    let categorization = previous_categorizations(server_info, &output_path);
+    let generated_code = generate_code(
+        server_info,
+        server_config,
+        &categorization,
+        &tool_filter,
+        config_paths,
+    )?;
+
+    Ok(PreparedGeneration {
+        base_dir,
+        output_path,
+        tool_count,
+        generated_code,
+    })
+}
+
+/// Resolves `selection` to the concrete filter for this run, reading the one recorded in
+/// `output_path`'s existing `_meta.json` for [`FilterSelection::Persisted`].
+///
+/// # Errors
+///
+/// Returns an error if the existing sidecar cannot be read or records an invalid filter.
+fn resolve_tool_filter(selection: FilterSelection, output_path: &Path) -> Result<ToolFilter> {
+    match selection {
+        FilterSelection::Explicit(filter) => Ok(filter),
+        FilterSelection::AllTools => Ok(ToolFilter::default()),
+        FilterSelection::Persisted => {
+            let persisted = read_persisted_tool_filter(output_path).with_context(|| {
+                "failed to read the previous tool filter; pass --include/--exclude to replace \
+                 it or --no-filter to discard it"
+            })?;
+            if let Some(filter) = &persisted {
+                info!(
+                    "Reusing tool filter from previous generation (include: {:?}, exclude: {:?})",
+                    filter.include(),
+                    filter.exclude()
+                );
+            }
+            Ok(persisted.unwrap_or_default())
+        }
     }
+}
 
-    export_generated_code(generated_code, &base_dir, &output_path)?;
+/// Returns the categorizations recorded in `output_path`'s existing `_meta.json` for the tools
+/// of `server_info` that are unchanged since then, so a regeneration keeps them.
+///
+/// A sidecar that cannot be read is logged and carries nothing over, rather than failing a run
+/// that may be meant to replace it.
+fn previous_categorizations(
+    server_info: &ServerInfo,
+    output_path: &Path,
+) -> HashMap<String, ToolCategorization> {
+    let previous = match read_persisted_tool_metadata(output_path) {
+        Ok(previous) => previous,
+        Err(err) => {
+            warn!("Not reusing previous categorizations: {err}");
+            return HashMap::new();
+        }
+    };
+    let categorization = reusable_categorizations(server_info, &previous);
+    if !categorization.is_empty() {
+        info!(
+            "Reusing categorizations of {} unchanged tools from previous generation",
+            categorization.len()
+        );
+    }
+    categorization
+}
 
-    render_success(&server_info, &output_path, output_format)
+/// Counts the discovered tools `filter` keeps.
+///
+/// # Errors
+///
+/// Returns a [`CoreError::InvalidArgument`] if `filter` keeps none of them: generating a server
+/// directory with no tools is never what a caller who wrote the filter meant.
+fn count_kept_tools(server_info: &ServerInfo, filter: &ToolFilter) -> Result<usize> {
+    let kept = server_info
+        .tools
+        .iter()
+        .filter(|tool| filter.matches(tool.name.as_str()))
+        .count();
+    if kept == 0 {
+        return Err(CoreError::InvalidArgument(format!(
+            "tool filter (include: {:?}, exclude: {:?}) matches none of the {} discovered tools",
+            filter.include(),
+            filter.exclude(),
+            server_info.tools.len()
+        ))
+        .into());
+    }
+    if kept < server_info.tools.len() {
+        info!(
+            "Tool filter excludes {} of {} tools",
+            server_info.tools.len() - kept,
+            server_info.tools.len()
+        );
+    }
+    Ok(kept)
 }
 
 /// Connects to the target server, discovers its tools, and applies the
@@ -246,15 +642,25 @@ async fn discover_server_info(
     Ok(server_info)
 }
 
-/// Generates progressive-loading TypeScript code for `server_info`.
+/// Generates progressive-loading TypeScript code for the tools of `server_info` that
+/// `tool_filter` keeps, categorized by `categorization`, with `locations`' config paths baked
+/// into the runtime bridge.
 ///
 /// # Errors
 ///
 /// Returns an error if the code generator fails to initialize or generate code.
-fn generate_code(server_info: &ServerInfo, server_config: &ServerConfig) -> Result<GeneratedCode> {
-    let generator = ProgressiveGenerator::new().context("failed to create code generator")?;
+fn generate_code(
+    server_info: &ServerInfo,
+    server_config: &ServerConfig,
+    categorization: &HashMap<String, ToolCategorization>,
+    tool_filter: &ToolFilter,
+    config_paths: BridgeConfigPaths,
+) -> Result<GeneratedCode> {
+    let generator = ProgressiveGenerator::new()
+        .context("failed to create code generator")?
+        .with_config_paths(config_paths);
     let generated_code = generator
-        .generate(server_info, server_config)
+        .generate_filtered(server_info, server_config, categorization, tool_filter)
         .context("failed to generate TypeScript code")?;
 
     info!(
@@ -294,7 +700,7 @@ fn generate_code(server_info: &ServerInfo, server_config: &ServerConfig) -> Resu
 /// Returns an error if `server_info.id` fails
 /// [`validate_server_id`](mcp_execution_skill::validate_server_id). When
 /// `is_from_unvalidated_config_key` is `true`, the error names
-/// `~/.claude/mcp.json` and suggests the `--name` override (with a
+/// the MCP config and suggests the `--name` override (with a
 /// filesystem-safe slug derived from the offending id) as a fix, since the
 /// fault is the user's config, not this tool. Otherwise it is framed as an
 /// internal error, since reaching it would mean one of the other arms' own
@@ -308,7 +714,7 @@ fn resolve_server_dir_name(
         if is_from_unvalidated_config_key {
             let suggested_name = derive_server_id_from_path_or_name(&server_dir_name);
             anyhow::anyhow!(
-                "server '{server_dir_name}' in ~/.claude/mcp.json is not a valid directory name \
+                "server '{server_dir_name}' in the MCP config is not a valid directory name \
                  ({source}); use --name {suggested_name} to override it"
             )
         } else {
@@ -322,65 +728,134 @@ fn resolve_server_dir_name(
 }
 
 /// Resolves the base directory generated servers are exported under, defaulting to
-/// `~/.claude/servers` when `output_dir` is not set.
+/// `locations`' servers directory (`~/.claude/servers`) for [`OutputDir::Default`].
 ///
 /// # Errors
 ///
-/// Returns an error if `output_dir` is `None` and the home directory cannot be determined.
-fn resolve_base_dir(output_dir: Option<PathBuf>) -> Result<PathBuf> {
-    if let Some(custom_dir) = output_dir {
-        Ok(custom_dir)
-    } else {
-        Ok(dirs::home_dir()
-            .context("failed to get home directory")?
-            .join(".claude")
-            .join("servers"))
+/// Returns an error if `output_dir` is [`OutputDir::Default`] and the home directory cannot be
+/// determined.
+fn resolve_base_dir(output_dir: &OutputDir, locations: &McpConfigLocations) -> Result<PathBuf> {
+    match output_dir {
+        OutputDir::Default => Ok(resolve_paths(locations)?.servers_dir()),
+        OutputDir::Custom(dir) => Ok(dir.clone()),
+        OutputDir::Project { bindings_dir, .. } => Ok(bindings_dir.clone()),
     }
 }
 
+/// The relative path from `server_dir` back up to `root`, e.g. `../../..` for
+/// `<root>/.claude/servers/<id>`.
+///
+/// # Errors
+///
+/// Returns an error if `server_dir` is not inside `root`.
+fn path_back_to(root: &Path, server_dir: &Path) -> Result<PathBuf> {
+    let inner = server_dir.strip_prefix(root).map_err(|_| {
+        anyhow::anyhow!(
+            "server directory {} is outside the project at {}",
+            server_dir.display(),
+            root.display()
+        )
+    })?;
+    Ok(inner.components().map(|_| Path::new("..")).collect())
+}
+
 /// Renders a dry-run preview of the files that would be generated, without writing anything.
+///
+/// # Errors
+///
+/// Returns an error if VFS construction fails or `output_path` cannot be read.
 fn render_dry_run(
     server_info: &ServerInfo,
-    generated_code: &GeneratedCode,
+    generated_code: GeneratedCode,
     output_path: &Path,
+    show_diff: bool,
     output_format: OutputFormat,
 ) -> Result<ExitCode> {
+    let result = build_dry_run(server_info, generated_code, output_path, show_diff)?;
+
+    println!("{}", format_dry_run(&result, output_format)?);
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Compares `generated_code` with the files under `output_path` and collects the dry-run
+/// preview, with per-file diffs if `show_diff` is set.
+///
+/// # Errors
+///
+/// Returns an error if VFS construction fails or `output_path` cannot be read.
+fn build_dry_run(
+    server_info: &ServerInfo,
+    generated_code: GeneratedCode,
+    output_path: &Path,
+    show_diff: bool,
+) -> Result<DryRunResult> {
     let server_dir_name = server_info.id.to_string();
-    let files: Vec<FilePreview> = generated_code
-        .files
-        .iter()
-        .map(|f| FilePreview {
-            path: format!("{}/{}", server_dir_name, f.path),
-            size: f.content.len(),
-        })
-        .collect();
+    let vfs = FilesBuilder::from_generated_code(generated_code, "/")
+        .build()
+        .context("failed to build VFS")?;
+    let comparisons = compare_with_disk(&vfs, output_path)
+        .context("failed to compare generated files with the server directory")?;
+
+    let mut files = Vec::with_capacity(vfs.file_count());
+    let mut deleted = Vec::new();
+    let mut changes = ChangeCounts::default();
+    for comparison in &comparisons {
+        let path = format!("{server_dir_name}/{}", comparison.path);
+        match comparison.status {
+            FileStatus::New => changes.new += 1,
+            FileStatus::Modified => changes.modified += 1,
+            FileStatus::Unchanged => changes.unchanged += 1,
+            FileStatus::Deleted => {
+                changes.deleted += 1;
+                deleted.push(path);
+                continue;
+            }
+        }
+        files.push(FilePreview {
+            path,
+            size: vfs.read_file(format!("/{}", comparison.path))?.len(),
+            status: comparison.status,
+            diff: if show_diff {
+                comparison.unified_diff()
+            } else {
+                None
+            },
+        });
+    }
     let total_size: usize = files.iter().map(|f| f.size).sum();
     let total_files = files.len();
 
-    let result = DryRunResult {
+    Ok(DryRunResult {
         server_id: server_info.id.to_string(),
         server_name: server_info.name.clone(),
         output_path: output_path.display().to_string(),
         files,
+        deleted,
+        changes,
         total_files,
         total_size,
-    };
-
-    println!("{}", format_dry_run(&result, output_format)?);
-
-    Ok(ExitCode::SUCCESS)
+    })
 }
 
 /// Renders a [`DryRunResult`] for the given `output_format`.
 ///
 /// `server_name` is server-supplied (untrusted), so `Text`/`Pretty` output escapes it via
-/// [`escape_display`] to neutralize embedded control characters; `Json` output is unaffected
-/// since `serde_json` already escapes string values.
+/// [`escape_display`] to neutralize embedded control characters, and diffs go through
+/// [`write_sanitized_diffs`]; `Json` output is unaffected since `serde_json` already escapes
+/// string values.
 fn format_dry_run(result: &DryRunResult, output_format: OutputFormat) -> Result<String> {
-    Ok(match output_format {
-        OutputFormat::Json => serde_json::to_string_pretty(result)?,
+    use std::fmt::Write as _;
+
+    let changes = &result.changes;
+    let change_summary = format!(
+        "{} new, {} modified, {} unchanged, {} deleted",
+        changes.new, changes.modified, changes.unchanged, changes.deleted
+    );
+    let mut out = match output_format {
+        OutputFormat::Json => return Ok(serde_json::to_string_pretty(result)?),
         OutputFormat::Text => format!(
-            "Server: {} ({})\nWould generate {} files ({}) to {}/",
+            "Server: {} ({})\nWould generate {} files ({}) to {}/\nChanges: {change_summary}",
             escape_display(&result.server_name),
             result.server_id,
             result.total_files,
@@ -388,26 +863,155 @@ fn format_dry_run(result: &DryRunResult, output_format: OutputFormat) -> Result<
             result.output_path
         ),
         OutputFormat::Pretty => {
-            use std::fmt::Write as _;
-
             let mut out = format!(
                 "Would generate {} files to {}/:\n\n",
                 result.total_files, result.output_path
             );
             for f in &result.files {
-                let _ = writeln!(out, "  - {} ({})", f.path, format_size(f.size));
+                let _ = writeln!(
+                    out,
+                    "  - {} ({}) [{}]",
+                    f.path,
+                    format_size(f.size),
+                    f.status.label()
+                );
+            }
+            for path in &result.deleted {
+                let _ = writeln!(
+                    out,
+                    "  - {} [{}]",
+                    sanitize_untrusted_text(path, MAX_DIFF_LINE_LEN),
+                    FileStatus::Deleted.label()
+                );
             }
             let _ = write!(
                 out,
-                "\nTotal: {} files, ~{}",
+                "\nTotal: {} files, ~{}\nChanges: {change_summary}",
                 result.total_files,
                 format_size(result.total_size)
             );
             out
         }
+    };
+    write_sanitized_diffs(
+        &mut out,
+        result.files.iter().filter_map(|f| f.diff.as_deref()),
+    );
+    Ok(out)
+}
+
+/// Appends each diff to `out` after a blank line, sanitizing every line via
+/// [`sanitize_untrusted_text`]: a diff carries server-supplied text and whatever was on disk.
+fn write_sanitized_diffs<'a>(out: &mut String, diffs: impl Iterator<Item = &'a str>) {
+    for diff in diffs {
+        out.push_str("\n\n");
+        let lines: Vec<String> = diff
+            .lines()
+            .map(|line| sanitize_untrusted_text(line, MAX_DIFF_LINE_LEN))
+            .collect();
+        out.push_str(&lines.join("\n"));
+    }
+}
+
+/// Compares `generated_code` with the files under `output_path` and renders the result.
+///
+/// Returns [`ExitCode::ERROR`] if any file differs.
+///
+/// # Errors
+///
+/// Returns an error if VFS construction fails or `output_path` cannot be read.
+fn render_check(
+    server_info: &ServerInfo,
+    generated_code: GeneratedCode,
+    output_path: &Path,
+    output_format: OutputFormat,
+) -> Result<ExitCode> {
+    let vfs = FilesBuilder::from_generated_code(generated_code, "/")
+        .build()
+        .context("failed to build VFS")?;
+    let comparisons = compare_with_disk(&vfs, output_path)
+        .context("failed to compare generated files with the server directory")?;
+
+    let differences: Vec<FileDifference> = comparisons
+        .iter()
+        .filter(|c| c.status != FileStatus::Unchanged)
+        .map(FileDifference::from)
+        .collect();
+    let result = CheckResult {
+        server_id: server_info.id.to_string(),
+        server_name: server_info.name.clone(),
+        output_path: output_path.display().to_string(),
+        up_to_date: differences.is_empty(),
+        files_checked: vfs.file_count(),
+        differences,
+    };
+
+    println!("{}", format_check(&result, output_format)?);
+
+    Ok(if result.up_to_date {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::ERROR
     })
 }
 
+/// Renders a [`CheckResult`] for the given `output_format`.
+///
+/// Diffs can carry server-supplied text (and whatever is on disk), so `Text`/`Pretty` output
+/// sanitizes them via [`write_sanitized_diffs`] and escapes `server_name` via
+/// [`escape_display`]; `Json` output is unaffected since `serde_json` already escapes string
+/// values.
+fn format_check(result: &CheckResult, output_format: OutputFormat) -> Result<String> {
+    use std::fmt::Write as _;
+
+    if output_format == OutputFormat::Json {
+        return Ok(serde_json::to_string_pretty(result)?);
+    }
+
+    let server_name = escape_display(&result.server_name);
+    let mut out = match (output_format, result.up_to_date) {
+        (OutputFormat::Pretty, true) => format!(
+            "✓ Generated files are up to date\n  Server: {server_name} ({})\n  Files: {}\n  Location: {}",
+            result.server_id, result.files_checked, result.output_path
+        ),
+        (OutputFormat::Pretty, false) => format!(
+            "✗ Generated files are out of date\n  Server: {server_name} ({})\n  Differing files: {} of {}\n  Location: {}\n",
+            result.server_id,
+            result.differences.len(),
+            result.files_checked,
+            result.output_path
+        ),
+        (_, true) => format!(
+            "Server: {server_name} ({})\nUp to date: {} files in {}/",
+            result.server_id, result.files_checked, result.output_path
+        ),
+        (_, false) => format!(
+            "Server: {server_name} ({})\nOut of date: {} files differ in {}/\n",
+            result.server_id,
+            result.differences.len(),
+            result.output_path
+        ),
+    };
+    if result.up_to_date {
+        return Ok(out);
+    }
+
+    for difference in &result.differences {
+        let _ = write!(
+            out,
+            "\n  {:<9} {}",
+            difference.status.label(),
+            sanitize_untrusted_text(&difference.path, MAX_DIFF_LINE_LEN)
+        );
+    }
+    write_sanitized_diffs(
+        &mut out,
+        result.differences.iter().filter_map(|d| d.diff.as_deref()),
+    );
+    out.push_str("\n\nRun generate without --check to update them.");
+    Ok(out)
+}
+
 /// Builds the VFS from `generated_code` and exports it to `output_path` under `base_dir`.
 ///
 /// # Errors
@@ -436,8 +1040,11 @@ fn export_generated_code(
     // directory name onto `base_dir` (see `derive_server_id_from_path_or_name`,
     // which is the primary guard); confining the export here means a future
     // caller that skips that sanitization fails loudly instead of writing
-    // outside `base_dir`.
-    let options = ExportOptions::new().with_confine_to(base_dir);
+    // outside `base_dir`. Incremental so a regeneration only rewrites the
+    // files whose content actually changed.
+    let options = ExportOptions::new()
+        .with_confine_to(base_dir)
+        .with_incremental(true);
     vfs.export_to_filesystem_with_options(output_path, &options)
         .context("failed to export files to filesystem")?;
 
@@ -447,13 +1054,15 @@ fn export_generated_code(
 /// Renders the success output for a completed export, including the #257 npm-install hint.
 fn render_success(
     server_info: &ServerInfo,
+    tool_count: usize,
     output_path: &Path,
     output_format: OutputFormat,
 ) -> Result<ExitCode> {
     let result = GenerationResult {
         server_id: server_info.id.to_string(),
         server_name: server_info.name.clone(),
-        tool_count: server_info.tools.len(),
+        tool_count,
+        excluded_count: server_info.tools.len() - tool_count,
         output_path: output_path.display().to_string(),
         next_step: NPM_INSTALL_HINT.to_string(),
     };
@@ -469,17 +1078,22 @@ fn render_success(
 /// [`escape_display`] to neutralize embedded control characters; `Json` output is unaffected
 /// since `serde_json` already escapes string values.
 fn format_success(result: &GenerationResult, output_format: OutputFormat) -> Result<String> {
+    let excluded_note = if result.excluded_count == 0 {
+        String::new()
+    } else {
+        format!(" ({} excluded by tool filter)", result.excluded_count)
+    };
     Ok(match output_format {
         OutputFormat::Json => serde_json::to_string_pretty(result)?,
         OutputFormat::Text => format!(
-            "Server: {} ({})\nGenerated {} tool files\nOutput: {}\nNext step: {NPM_INSTALL_HINT}",
+            "Server: {} ({})\nGenerated {} tool files{excluded_note}\nOutput: {}\nNext step: {NPM_INSTALL_HINT}",
             escape_display(&result.server_name),
             result.server_id,
             result.tool_count,
             result.output_path
         ),
         OutputFormat::Pretty => format!(
-            "✓ Successfully generated progressive loading files\n  Server: {} ({})\n  Tools: {}\n  Location: {}\n  Next step: {NPM_INSTALL_HINT}",
+            "✓ Successfully generated progressive loading files\n  Server: {} ({})\n  Tools: {}{excluded_note}\n  Location: {}\n  Next step: {NPM_INSTALL_HINT}",
             escape_display(&result.server_name),
             result.server_id,
             result.tool_count,
@@ -535,6 +1149,7 @@ mod tests {
             server_id: "test".to_string(),
             server_name: "Test Server".to_string(),
             tool_count: 5,
+            excluded_count: 0,
             output_path: "/path/to/output".to_string(),
             next_step: NPM_INSTALL_HINT.to_string(),
         };
@@ -553,6 +1168,7 @@ mod tests {
             server_id: "test".to_string(),
             server_name: "evil\u{1b}[2J\u{1b}]0;pwned\u{7}".to_string(),
             tool_count: 1,
+            excluded_count: 0,
             output_path: "/path/to/output".to_string(),
             next_step: NPM_INSTALL_HINT.to_string(),
         };
@@ -568,6 +1184,7 @@ mod tests {
             server_id: "test".to_string(),
             server_name: "evil\u{1b}[2Jname".to_string(),
             tool_count: 1,
+            excluded_count: 0,
             output_path: "/path/to/output".to_string(),
             next_step: NPM_INSTALL_HINT.to_string(),
         };
@@ -584,6 +1201,8 @@ mod tests {
             server_name: "evil\u{1b}[2Jname".to_string(),
             output_path: "/path/to/output".to_string(),
             files: vec![],
+            deleted: vec![],
+            changes: ChangeCounts::default(),
             total_files: 0,
             total_size: 0,
         };
@@ -603,6 +1222,8 @@ mod tests {
             server_name: "evil\u{1b}[2Jname".to_string(),
             output_path: "/path/to/output".to_string(),
             files: vec![],
+            deleted: vec![],
+            changes: ChangeCounts::default(),
             total_files: 0,
             total_size: 0,
         };
@@ -618,6 +1239,7 @@ mod tests {
             server_id: "test".to_string(),
             server_name: "evil\u{1b}[2Jname".to_string(),
             tool_count: 1,
+            excluded_count: 0,
             output_path: "/path/to/output".to_string(),
             next_step: NPM_INSTALL_HINT.to_string(),
         };
@@ -636,6 +1258,7 @@ mod tests {
             server_id: "test".to_string(),
             server_name: "Test Server".to_string(),
             tool_count: 1,
+            excluded_count: 0,
             output_path: "/path/to/output".to_string(),
             next_step: NPM_INSTALL_HINT.to_string(),
         };
@@ -651,6 +1274,8 @@ mod tests {
             server_name: "Test Server".to_string(),
             output_path: "/path/to/output".to_string(),
             files: vec![],
+            deleted: vec![],
+            changes: ChangeCounts::default(),
             total_files: 0,
             total_size: 0,
         };
@@ -677,6 +1302,334 @@ mod tests {
         assert!(code.file_count() > 0);
     }
 
+    #[test]
+    fn test_filter_selection_from_flags() {
+        assert_eq!(
+            FilterSelection::from_flags(vec![], vec![], false).unwrap(),
+            FilterSelection::Persisted
+        );
+        assert_eq!(
+            FilterSelection::from_flags(vec![], vec![], true).unwrap(),
+            FilterSelection::AllTools
+        );
+        let FilterSelection::Explicit(filter) =
+            FilterSelection::from_flags(vec![], vec!["*_admin".to_string()], false).unwrap()
+        else {
+            panic!("expected an explicit filter");
+        };
+        assert!(!filter.matches("repo_admin"));
+    }
+
+    #[test]
+    fn test_filter_selection_invalid_pattern_is_invalid_input() {
+        let err = FilterSelection::from_flags(vec![String::new()], vec![], false).unwrap_err();
+        assert!(
+            err.downcast_ref::<CoreError>()
+                .is_some_and(CoreError::is_validation_error)
+        );
+    }
+
+    /// A regeneration without `--include`/`--exclude` keeps honoring the filter recorded by the
+    /// previous run, while `--no-filter` drops it.
+    #[test]
+    fn test_resolve_tool_filter_reuses_filter_recorded_in_meta_json() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let filter = ToolFilter::new(vec!["test_*".to_string()], vec![]).unwrap();
+        let generated = ProgressiveGenerator::new()
+            .unwrap()
+            .generate_filtered(
+                &create_mock_server_info(),
+                &create_mock_server_config(),
+                &HashMap::new(),
+                &filter,
+            )
+            .unwrap();
+        let output_path = temp_dir.path().join("test-server");
+        export_generated_code(generated, temp_dir.path(), &output_path).unwrap();
+
+        assert_eq!(
+            resolve_tool_filter(FilterSelection::Persisted, &output_path).unwrap(),
+            filter
+        );
+        assert!(
+            resolve_tool_filter(FilterSelection::AllTools, &output_path)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            resolve_tool_filter(FilterSelection::Persisted, &temp_dir.path().join("absent"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    /// A regeneration keeps a tool's categorization only while the tool is unchanged since the
+    /// previous run.
+    #[test]
+    fn test_previous_categorizations_reuses_unchanged_tools() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let server_info = create_mock_server_info();
+        let categorization = HashMap::from([(
+            "test_tool".to_string(),
+            ToolCategorization {
+                category: "testing".to_string(),
+                keywords: vec!["test".to_string()],
+                short_description: "Test".to_string(),
+            },
+        )]);
+        let generated = ProgressiveGenerator::new()
+            .unwrap()
+            .generate_filtered(
+                &server_info,
+                &create_mock_server_config(),
+                &categorization,
+                &ToolFilter::default(),
+            )
+            .unwrap();
+        let output_path = temp_dir.path().join("test-server");
+        export_generated_code(generated, temp_dir.path(), &output_path).unwrap();
+
+        let reused = previous_categorizations(&server_info, &output_path);
+        assert_eq!(reused["test_tool"].category, "testing");
+        assert_eq!(reused["test_tool"].short_description, "Test");
+
+        let mut changed = server_info.clone();
+        changed.tools[0].description.push_str(" (changed)");
+        assert!(previous_categorizations(&changed, &output_path).is_empty());
+        assert!(previous_categorizations(&server_info, &temp_dir.path().join("absent")).is_empty());
+    }
+
+    fn generate_mock_code() -> GeneratedCode {
+        ProgressiveGenerator::new()
+            .unwrap()
+            .generate(&create_mock_server_info(), &create_mock_server_config())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_generate_mode_from_flags() {
+        assert_eq!(
+            GenerateMode::from_flags(false, false, false),
+            GenerateMode::Write
+        );
+        assert_eq!(
+            GenerateMode::from_flags(true, false, false),
+            GenerateMode::DryRun { diff: false }
+        );
+        assert_eq!(
+            GenerateMode::from_flags(true, true, false),
+            GenerateMode::DryRun { diff: true }
+        );
+        assert_eq!(
+            GenerateMode::from_flags(false, false, true),
+            GenerateMode::Check
+        );
+    }
+
+    /// A freshly exported directory checks clean against a second, separately timestamped
+    /// generation; editing or removing a file, or adding a stray one, makes it fail.
+    #[test]
+    fn test_render_check_detects_out_of_date_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let server_info = create_mock_server_info();
+        let output_path = temp_dir.path().join("test-server");
+        export_generated_code(generate_mock_code(), temp_dir.path(), &output_path).unwrap();
+
+        let exit_code = render_check(
+            &server_info,
+            generate_mock_code(),
+            &output_path,
+            OutputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(exit_code, ExitCode::SUCCESS);
+
+        std::fs::write(output_path.join("index.ts"), "// edited\n").unwrap();
+        std::fs::write(output_path.join("stray.ts"), "").unwrap();
+        let exit_code = render_check(
+            &server_info,
+            generate_mock_code(),
+            &output_path,
+            OutputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(exit_code, ExitCode::ERROR);
+
+        // Nothing was written back.
+        assert_eq!(
+            std::fs::read_to_string(output_path.join("index.ts")).unwrap(),
+            "// edited\n"
+        );
+    }
+
+    /// What the documented `npm install` leaves in the output directory is not a difference.
+    #[test]
+    fn test_render_check_ignores_npm_install_artifacts() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output_path = temp_dir.path().join("test-server");
+        export_generated_code(generate_mock_code(), temp_dir.path(), &output_path).unwrap();
+        std::fs::create_dir_all(output_path.join("node_modules/@types/node")).unwrap();
+        std::fs::write(output_path.join("node_modules/@types/node/index.d.ts"), "").unwrap();
+        std::fs::write(output_path.join("package-lock.json"), "{}").unwrap();
+
+        let exit_code = render_check(
+            &create_mock_server_info(),
+            generate_mock_code(),
+            &output_path,
+            OutputFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(exit_code, ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn test_build_dry_run_classifies_files_against_disk() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let server_info = create_mock_server_info();
+        let output_path = temp_dir.path().join("test-server");
+        export_generated_code(generate_mock_code(), temp_dir.path(), &output_path).unwrap();
+        std::fs::write(output_path.join("index.ts"), "// edited\n").unwrap();
+        std::fs::remove_file(output_path.join("testTool.ts")).unwrap();
+        std::fs::write(output_path.join("removedTool.ts"), "").unwrap();
+
+        let result =
+            build_dry_run(&server_info, generate_mock_code(), &output_path, false).unwrap();
+
+        let status_of = |path: &str| {
+            result
+                .files
+                .iter()
+                .find(|f| f.path == format!("test-server/{path}"))
+                .map(|f| f.status)
+        };
+        assert_eq!(status_of("index.ts"), Some(FileStatus::Modified));
+        assert_eq!(status_of("testTool.ts"), Some(FileStatus::New));
+        assert_eq!(status_of("_meta.json"), Some(FileStatus::Unchanged));
+        assert_eq!(result.deleted, ["test-server/removedTool.ts"]);
+        assert_eq!(result.changes.new, 1);
+        assert_eq!(result.changes.modified, 1);
+        assert_eq!(result.changes.deleted, 1);
+        assert_eq!(result.changes.unchanged, result.total_files - 2);
+        assert!(result.files.iter().all(|f| f.diff.is_none()));
+        assert!(
+            output_path.join("removedTool.ts").exists(),
+            "dry run must not write"
+        );
+    }
+
+    #[test]
+    fn test_build_dry_run_with_diff_covers_new_and_modified_files_only() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let server_info = create_mock_server_info();
+        let output_path = temp_dir.path().join("test-server");
+        export_generated_code(generate_mock_code(), temp_dir.path(), &output_path).unwrap();
+        std::fs::write(output_path.join("index.ts"), "// edited\n").unwrap();
+
+        let result = build_dry_run(&server_info, generate_mock_code(), &output_path, true).unwrap();
+
+        for file in &result.files {
+            assert_eq!(
+                file.diff.is_some(),
+                file.status != FileStatus::Unchanged,
+                "{}",
+                file.path
+            );
+        }
+        let index = result
+            .files
+            .iter()
+            .find(|f| f.path == "test-server/index.ts")
+            .unwrap();
+        assert!(index.diff.as_deref().unwrap().contains("-// edited"));
+
+        let pretty = format_dry_run(&result, OutputFormat::Pretty).unwrap();
+        assert!(pretty.contains("index.ts"), "{pretty}");
+        assert!(pretty.contains("[modified]"), "{pretty}");
+        assert!(pretty.contains("-// edited"), "{pretty}");
+        assert!(pretty.contains("Changes: 0 new, 1 modified"), "{pretty}");
+    }
+
+    #[test]
+    fn test_format_check_lists_differences_with_sanitized_diff() {
+        let result = CheckResult {
+            server_id: "test".to_string(),
+            server_name: "Test".to_string(),
+            output_path: "/tmp/servers/test".to_string(),
+            up_to_date: false,
+            files_checked: 3,
+            differences: vec![
+                FileDifference {
+                    path: "index.ts".to_string(),
+                    status: FileStatus::Modified,
+                    diff: Some("--- a/index.ts\n+++ b/index.ts\n-old\u{1b}[2J\n+new\n".to_string()),
+                },
+                FileDifference {
+                    path: "stray.ts".to_string(),
+                    status: FileStatus::Deleted,
+                    diff: None,
+                },
+            ],
+        };
+
+        for format in [OutputFormat::Text, OutputFormat::Pretty] {
+            let output = format_check(&result, format).unwrap();
+            assert!(output.contains("modified  index.ts"), "{output}");
+            assert!(output.contains("deleted   stray.ts"), "{output}");
+            assert!(output.contains("+new"), "{output}");
+            assert!(!output.contains('\u{1b}'), "{output}");
+        }
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_check(&result, OutputFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["up_to_date"], false);
+        assert_eq!(json["differences"][0]["status"], "modified");
+        assert!(json["differences"][1].get("diff").is_none());
+    }
+
+    #[test]
+    fn test_format_check_up_to_date() {
+        let result = CheckResult {
+            server_id: "test".to_string(),
+            server_name: "Test".to_string(),
+            output_path: "/tmp/servers/test".to_string(),
+            up_to_date: true,
+            files_checked: 3,
+            differences: vec![],
+        };
+
+        let output = format_check(&result, OutputFormat::Text).unwrap();
+        assert!(output.contains("Up to date: 3 files"), "{output}");
+    }
+
+    #[test]
+    fn test_count_kept_tools_rejects_filter_matching_nothing() {
+        let server_info = create_mock_server_info();
+        let keep_all = ToolFilter::default();
+        assert_eq!(count_kept_tools(&server_info, &keep_all).unwrap(), 1);
+
+        let exclude_all = ToolFilter::new(vec![], vec!["*".to_string()]).unwrap();
+        let err = count_kept_tools(&server_info, &exclude_all).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<CoreError>(),
+            Some(CoreError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_success_mentions_filtered_out_tools() {
+        let result = GenerationResult {
+            server_id: "test".to_string(),
+            server_name: "Test Server".to_string(),
+            tool_count: 2,
+            excluded_count: 3,
+            output_path: "/path/to/output".to_string(),
+            next_step: NPM_INSTALL_HINT.to_string(),
+        };
+
+        let output = format_success(&result, OutputFormat::Text).unwrap();
+        assert!(output.contains("Generated 2 tool files (3 excluded by tool filter)"));
+    }
+
     #[test]
     fn test_format_size_bytes() {
         assert_eq!(format_size(0), "0 B");
@@ -707,12 +1660,22 @@ mod tests {
                 FilePreview {
                     path: "github/createIssue.ts".to_string(),
                     size: 2450,
+                    status: FileStatus::New,
+                    diff: None,
                 },
                 FilePreview {
                     path: "github/listRepos.ts".to_string(),
                     size: 1200,
+                    status: FileStatus::Unchanged,
+                    diff: None,
                 },
             ],
+            deleted: vec![],
+            changes: ChangeCounts {
+                new: 1,
+                unchanged: 1,
+                ..ChangeCounts::default()
+            },
             total_files: 2,
             total_size: 3650,
         };
@@ -740,6 +1703,8 @@ mod tests {
             .map(|f| FilePreview {
                 path: format!("{}/{}", server_dir_name, f.path),
                 size: f.content.len(),
+                status: FileStatus::New,
+                diff: None,
             })
             .collect();
 
@@ -781,6 +1746,8 @@ mod tests {
             .map(|f| FilePreview {
                 path: format!("{}/{}", server_dir_name, f.path),
                 size: f.content.len(),
+                status: FileStatus::New,
+                diff: None,
             })
             .collect();
 
@@ -808,7 +1775,16 @@ mod tests {
             connect_timeout_secs: Some(0),
             discover_timeout_secs: None,
         };
-        let result = run(source, None, None, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            None,
+            OutputDir::Default,
+            GenerateMode::Write,
+            FilterSelection::Persisted,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -836,7 +1812,16 @@ mod tests {
             connect_timeout_secs: Some(5),
             discover_timeout_secs: Some(90),
         };
-        let result = run(source, None, None, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            None,
+            OutputDir::Default,
+            GenerateMode::Write,
+            FilterSelection::Persisted,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -942,11 +1927,12 @@ mod tests {
         let err = resolve_server_dir_name(&server_info, false).unwrap_err();
         let err_msg = err.to_string();
         assert!(err_msg.contains("internal error"), "got: {err_msg}");
-        assert!(!err_msg.contains("mcp.json"), "got: {err_msg}");
+        assert!(!err_msg.contains("MCP config"), "got: {err_msg}");
     }
 
     #[test]
-    fn test_resolve_server_dir_name_from_config_error_names_mcp_json_and_suggests_name_override() {
+    fn test_resolve_server_dir_name_from_config_error_names_mcp_config_and_suggests_name_override()
+    {
         // Regression test for #311 review M10: a `claude_ai_Gmail`-style
         // legitimate mcp.json key fails `validate_server_id`'s stricter
         // filesystem-safety charset. The error must name the actual fault
@@ -957,11 +1943,72 @@ mod tests {
 
         let err = resolve_server_dir_name(&server_info, true).unwrap_err();
         let err_msg = err.to_string();
-        assert!(err_msg.contains("mcp.json"), "got: {err_msg}");
+        assert!(err_msg.contains("MCP config"), "got: {err_msg}");
         assert!(err_msg.contains("--name claude-ai-gmail"), "got: {err_msg}");
         assert!(!err_msg.contains("internal error"), "got: {err_msg}");
     }
 
+    #[test]
+    fn test_path_back_to_counts_levels() {
+        let root = Path::new("/work/app");
+        assert_eq!(
+            path_back_to(root, Path::new("/work/app/.claude/servers/github")).unwrap(),
+            Path::new("../../..")
+        );
+        assert!(path_back_to(root, Path::new("/elsewhere/github")).is_err());
+    }
+
+    /// Project bindings are meant to be committed: nothing generated may name a path on the
+    /// generating machine, home directory included.
+    #[test]
+    fn test_prepare_generation_project_embeds_no_absolute_paths() {
+        let home = tempfile::TempDir::new().unwrap();
+        let project = tempfile::TempDir::new().unwrap();
+        let locations = McpConfigLocations::new()
+            .with_home_dir(home.path())
+            .with_project_dir(project.path());
+        let output_dir = OutputDir::Project {
+            root: project.path().to_path_buf(),
+            bindings_dir: project.path().join(".claude").join("servers"),
+        };
+
+        let prepared = prepare_generation(
+            &create_mock_server_info(),
+            &create_mock_server_config(),
+            false,
+            &output_dir,
+            FilterSelection::default(),
+            &locations,
+        )
+        .unwrap();
+
+        assert_eq!(
+            prepared.output_path,
+            project.path().join(".claude/servers/test-server")
+        );
+        for file in &prepared.generated_code.files {
+            for dir in [home.path(), project.path()] {
+                assert!(
+                    !file.content.contains(&*dir.to_string_lossy()),
+                    "{} embeds {}",
+                    file.path,
+                    dir.display()
+                );
+            }
+        }
+        let bridge = prepared
+            .generated_code
+            .files
+            .iter()
+            .find(|f| f.path == "_runtime/mcp-bridge.ts")
+            .unwrap();
+        assert!(
+            bridge
+                .content
+                .contains(r#"import.meta.url)), "../../../..");"#)
+        );
+    }
+
     #[test]
     fn test_export_generated_code_confines_output_to_base_dir() {
         // End-to-end reproduction of the vulnerable call site
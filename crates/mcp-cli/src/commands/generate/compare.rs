@@ -0,0 +1,357 @@
+//! Comparison of freshly generated files against an existing server directory.
+//!
+//! Used by `generate --check` to decide whether the files on disk are what a regeneration would
+//! write, and by `generate --dry-run` to show what a regeneration would change. Both sides are
+//! normalized the same way before comparing: `_meta.json`'s `provenance.generated_at` line is
+//! masked, since it changes on every run even when nothing else does.
+
+use anyhow::{Context, Result};
+use mcp_execution_codegen::progressive::generator::NPM_INSTALL_ARTIFACTS;
+use mcp_execution_core::metadata::METADATA_FILE_NAME;
+use mcp_execution_files::FileSystem;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::io;
+use std::path::Path;
+
+/// Upper bound on entries walked under the server directory (CWE-400).
+const MAX_SCANNED_ENTRIES: usize = 10_000;
+
+/// Upper bound on the size of a single on-disk file read for comparison.
+///
+/// Generated files are far smaller; anything larger is reported as modified without being
+/// read.
+const MAX_COMPARED_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// JSON key whose line is masked in `_meta.json` before comparing.
+const GENERATED_AT_KEY: &str = "\"generated_at\":";
+
+/// How a generated file relates to what is on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    /// Generated, but absent from disk.
+    New,
+    /// Present on both sides with different content.
+    Modified,
+    /// Present on both sides with identical (normalized) content.
+    Unchanged,
+    /// Present on disk only; a regeneration would remove it.
+    Deleted,
+}
+
+impl FileStatus {
+    /// Short lower-case label for text output.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Modified => "modified",
+            Self::Unchanged => "unchanged",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+
+/// One file's comparison result.
+///
+/// `generated` and `on_disk` hold normalized content where it was read: `on_disk` is `None` for
+/// a [`FileStatus::New`] or [`FileStatus::Deleted`] file, and for an on-disk entry that is not a
+/// regular file or exceeds [`MAX_COMPARED_FILE_SIZE`].
+#[derive(Debug)]
+pub struct FileComparison {
+    /// Path relative to the server directory, `/`-separated.
+    pub path: String,
+    /// Classification of this file.
+    pub status: FileStatus,
+    generated: Option<String>,
+    on_disk: Option<String>,
+}
+
+impl FileComparison {
+    /// Renders a unified diff from the on-disk content to the generated content, or `None` if
+    /// the file is unchanged or was only present on disk.
+    pub fn unified_diff(&self) -> Option<String> {
+        if matches!(self.status, FileStatus::Unchanged | FileStatus::Deleted) {
+            return None;
+        }
+        let old = self.on_disk.as_deref().unwrap_or_default();
+        let new = self.generated.as_deref().unwrap_or_default();
+        Some(
+            similar::TextDiff::from_lines(old, new)
+                .unified_diff()
+                .context_radius(3)
+                .header(&format!("a/{}", self.path), &format!("b/{}", self.path))
+                .to_string(),
+        )
+    }
+}
+
+/// Compares every file in `vfs` with its counterpart under `server_dir`, and reports files
+/// present only on disk as [`FileStatus::Deleted`].
+///
+/// `vfs` paths are expected to be rooted at `/` (as built by
+/// [`FilesBuilder::from_generated_code`](mcp_execution_files::FilesBuilder::from_generated_code)
+/// with a `/` base). A missing `server_dir` makes every file [`FileStatus::New`]. The result is
+/// sorted by path.
+///
+/// # Errors
+///
+/// Returns an error if `server_dir` or an entry under it cannot be read, or if the directory
+/// holds more than [`MAX_SCANNED_ENTRIES`] entries.
+pub fn compare_with_disk(vfs: &FileSystem, server_dir: &Path) -> Result<Vec<FileComparison>> {
+    let mut on_disk_paths = scan_server_dir(server_dir)?;
+    let mut comparisons = Vec::with_capacity(vfs.file_count());
+
+    for vfs_path in vfs.all_paths() {
+        let relative = vfs_path.as_str().trim_start_matches('/').to_string();
+        let generated = vfs
+            .read_file(vfs_path)
+            .with_context(|| format!("failed to read generated file {relative}"))?;
+        let generated = normalize_for_comparison(&relative, generated).into_owned();
+
+        let (status, on_disk) = if on_disk_paths.remove(&relative) {
+            read_for_comparison(&server_dir.join(&relative))?.map_or(
+                (FileStatus::Modified, None),
+                |content| {
+                    let content = normalize_for_comparison(&relative, &content).into_owned();
+                    let status = if content == generated {
+                        FileStatus::Unchanged
+                    } else {
+                        FileStatus::Modified
+                    };
+                    (status, Some(content))
+                },
+            )
+        } else {
+            (FileStatus::New, None)
+        };
+
+        comparisons.push(FileComparison {
+            path: relative,
+            status,
+            generated: Some(generated),
+            on_disk,
+        });
+    }
+
+    comparisons.extend(on_disk_paths.into_iter().map(|path| FileComparison {
+        path,
+        status: FileStatus::Deleted,
+        generated: None,
+        on_disk: None,
+    }));
+    comparisons.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(comparisons)
+}
+
+/// Lists every non-directory entry under `server_dir` as a `/`-separated relative path,
+/// skipping the top-level [`NPM_INSTALL_ARTIFACTS`]. Symlinks are listed, never followed.
+fn scan_server_dir(server_dir: &Path) -> Result<BTreeSet<String>> {
+    let mut found = BTreeSet::new();
+    let mut pending = vec![(server_dir.to_path_buf(), String::new())];
+    let mut scanned = 0usize;
+
+    while let Some((dir, prefix)) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && prefix.is_empty() => {
+                return Ok(found);
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to read {}", dir.display()));
+            }
+        };
+
+        for entry in entries {
+            let entry = entry.with_context(|| format!("failed to read {}", dir.display()))?;
+            scanned += 1;
+            if scanned > MAX_SCANNED_ENTRIES {
+                anyhow::bail!(
+                    "{} holds more than {MAX_SCANNED_ENTRIES} entries; refusing to compare it",
+                    server_dir.display()
+                );
+            }
+
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if prefix.is_empty() && NPM_INSTALL_ARTIFACTS.contains(&name.as_str()) {
+                continue;
+            }
+            let relative = if prefix.is_empty() {
+                name
+            } else {
+                format!("{prefix}/{name}")
+            };
+            let file_type = entry
+                .file_type()
+                .with_context(|| format!("failed to read {}", entry.path().display()))?;
+            if file_type.is_dir() {
+                pending.push((entry.path(), relative));
+            } else {
+                found.insert(relative);
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+/// Reads `path` for comparison, returning `None` if it is not a regular file or is larger than
+/// [`MAX_COMPARED_FILE_SIZE`]. Invalid UTF-8 is replaced rather than rejected, since it can only
+/// ever compare as modified.
+fn read_for_comparison(path: &Path) -> Result<Option<String>> {
+    let metadata = std::fs::symlink_metadata(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    if !metadata.is_file() || metadata.len() > MAX_COMPARED_FILE_SIZE {
+        return Ok(None);
+    }
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(Some(String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// Masks the `generated_at` line of the top-level `_meta.json`; every other file is compared
+/// as-is.
+fn normalize_for_comparison<'a>(relative_path: &str, content: &'a str) -> Cow<'a, str> {
+    if relative_path != METADATA_FILE_NAME || !content.contains(GENERATED_AT_KEY) {
+        return Cow::Borrowed(content);
+    }
+
+    let mut normalized = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with(GENERATED_AT_KEY) {
+            let indent = &line[..line.len() - trimmed.len()];
+            let body = trimmed.trim_end();
+            let comma = if body.ends_with(',') { "," } else { "" };
+            let newline = &trimmed[body.len()..];
+            normalized.push_str(indent);
+            normalized.push_str(GENERATED_AT_KEY);
+            normalized.push_str(" \"<ignored>\"");
+            normalized.push_str(comma);
+            normalized.push_str(newline);
+        } else {
+            normalized.push_str(line);
+        }
+    }
+    Cow::Owned(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_execution_files::FilesBuilder;
+    use tempfile::TempDir;
+
+    fn vfs(files: &[(&str, &str)]) -> FileSystem {
+        let mut builder = FilesBuilder::new();
+        for (path, content) in files {
+            builder = builder.add_file(format!("/{path}"), *content);
+        }
+        builder.build().unwrap()
+    }
+
+    fn statuses(comparisons: &[FileComparison]) -> Vec<(&str, FileStatus)> {
+        comparisons
+            .iter()
+            .map(|c| (c.path.as_str(), c.status))
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_with_missing_dir_reports_every_file_new() {
+        let temp = TempDir::new().unwrap();
+        let comparisons =
+            compare_with_disk(&vfs(&[("index.ts", "x")]), &temp.path().join("absent")).unwrap();
+        assert_eq!(statuses(&comparisons), [("index.ts", FileStatus::New)]);
+    }
+
+    #[test]
+    fn test_compare_classifies_each_file() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("_runtime")).unwrap();
+        std::fs::write(temp.path().join("same.ts"), "same\n").unwrap();
+        std::fs::write(temp.path().join("changed.ts"), "old\n").unwrap();
+        std::fs::write(temp.path().join("_runtime/stale.ts"), "stale\n").unwrap();
+        std::fs::create_dir_all(temp.path().join("node_modules/pkg")).unwrap();
+        std::fs::write(temp.path().join("node_modules/pkg/index.js"), "").unwrap();
+
+        let generated = vfs(&[
+            ("same.ts", "same\n"),
+            ("changed.ts", "new\n"),
+            ("added.ts", "added\n"),
+        ]);
+        let comparisons = compare_with_disk(&generated, temp.path()).unwrap();
+
+        assert_eq!(
+            statuses(&comparisons),
+            [
+                ("_runtime/stale.ts", FileStatus::Deleted),
+                ("added.ts", FileStatus::New),
+                ("changed.ts", FileStatus::Modified),
+                ("same.ts", FileStatus::Unchanged),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_shows_changed_lines() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("a.ts"), "one\ntwo\n").unwrap();
+
+        let comparisons =
+            compare_with_disk(&vfs(&[("a.ts", "one\nthree\n")]), temp.path()).unwrap();
+        let diff = comparisons[0].unified_diff().unwrap();
+
+        assert!(diff.contains("--- a/a.ts"), "{diff}");
+        assert!(diff.contains("+++ b/a.ts"), "{diff}");
+        assert!(diff.contains("-two"), "{diff}");
+        assert!(diff.contains("+three"), "{diff}");
+    }
+
+    #[test]
+    fn test_meta_json_generated_at_is_ignored() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join(METADATA_FILE_NAME),
+            "{\n  \"provenance\": {\n    \"generated_at\": \"2026-01-01T00:00:00Z\",\n    \"x\": 1\n  }\n}",
+        )
+        .unwrap();
+
+        let generated = vfs(&[(
+            METADATA_FILE_NAME,
+            "{\n  \"provenance\": {\n    \"generated_at\": \"2026-02-02T00:00:00Z\",\n    \"x\": 1\n  }\n}",
+        )]);
+        let comparisons = compare_with_disk(&generated, temp.path()).unwrap();
+
+        assert_eq!(
+            statuses(&comparisons),
+            [(METADATA_FILE_NAME, FileStatus::Unchanged)]
+        );
+    }
+
+    #[test]
+    fn test_generated_at_is_only_masked_in_meta_json() {
+        let content = "  \"generated_at\": \"2026-01-01T00:00:00Z\",\n";
+        assert_eq!(normalize_for_comparison("other.json", content), content);
+        assert_eq!(
+            normalize_for_comparison(METADATA_FILE_NAME, content),
+            "  \"generated_at\": \"<ignored>\",\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_in_place_of_generated_file_is_modified_not_followed() {
+        let temp = TempDir::new().unwrap();
+        let outside = temp.path().join("outside.ts");
+        std::fs::write(&outside, "x").unwrap();
+        let server_dir = temp.path().join("server");
+        std::fs::create_dir(&server_dir).unwrap();
+        std::os::unix::fs::symlink(&outside, server_dir.join("a.ts")).unwrap();
+
+        let comparisons = compare_with_disk(&vfs(&[("a.ts", "x")]), &server_dir).unwrap();
+        assert_eq!(statuses(&comparisons), [("a.ts", FileStatus::Modified)]);
+    }
+}
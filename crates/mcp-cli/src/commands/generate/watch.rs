@@ -0,0 +1,706 @@
+//! `generate --watch`: regenerates a server directory whenever the server's tools may have
+//! changed.
+//!
+//! A cycle is triggered by:
+//! - a change to any existing MCP config file (only with `--from-config`, the one source it
+//!   affects),
+//! - a change under any `--watch-path` (e.g. the server's source tree or binary),
+//! - a `notifications/tools/list_changed` sent by the server over a live session.
+//!
+//! File events are debounced until [`DEBOUNCE`] passes without a new one. Each cycle re-resolves
+//! the server config, re-introspects the server, and regenerates only if the [`ToolDigest`] of
+//! the discovered tools differs from the previous cycle's. The first cycle always generates.
+//! Every cycle prints exactly one line to stdout; failed cycles are reported the same way and
+//! watching continues, so a broken edit can be fixed without restarting.
+
+use super::{FilterSelection, PreparedGeneration, discover_server_info, export_generated_code};
+use super::{OutputDir, prepare_generation, resolve_base_dir};
+use crate::commands::common::{ServerSource, resolve_server_config};
+use crate::formatters::{escape_display, escape_error_text, json};
+use anyhow::{Context, Result};
+use mcp_execution_core::cli::{ExitCode, OutputFormat};
+use mcp_execution_core::mcp_config::McpConfigLocations;
+use mcp_execution_core::provenance::{ToolDigest, ToolDigestEntry};
+use mcp_execution_core::{ServerConfig, ServerId};
+use mcp_execution_introspector::{ServerInfo, ToolListSubscription};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// Quiet period after the last file event before a cycle starts.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Capacity of the channel carrying file events from the watcher thread.
+///
+/// Events that arrive while it is full are dropped: one pending event is enough to trigger the
+/// next cycle, and the dropped paths only shorten that cycle's log line.
+const FILE_EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Number of hex characters of a [`ToolDigest`] shown in a log line.
+const SHORT_DIGEST_LEN: usize = 12;
+
+/// Runs `generate` in watch mode until interrupted with Ctrl-C.
+///
+/// `watch_paths` are watched in addition to the MCP config files `locations` finds when watching
+/// starts: a directory recursively, a file on its own. Events under the output base directory
+/// are ignored, so a watched path that contains it does not retrigger on every export.
+///
+/// # Errors
+///
+/// Returns an error if a watch path does not exist or the file watcher cannot be set up. Errors
+/// inside a cycle are logged and do not end watching.
+pub async fn run_watch(
+    source: ServerSource,
+    name: Option<String>,
+    output_dir: OutputDir,
+    watch_paths: Vec<PathBuf>,
+    filter: FilterSelection,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+) -> Result<ExitCode> {
+    let mut targets = Vec::with_capacity(watch_paths.len() + 1);
+    if matches!(source, ServerSource::Config { .. }) {
+        for config_path in locations.existing_files() {
+            targets.push(WatchTarget::resolve(&config_path)?);
+        }
+    }
+    for path in &watch_paths {
+        targets.push(WatchTarget::resolve(path)?);
+    }
+
+    // Created up front so it can be canonicalized like the targets: events are reported under
+    // the canonical watch roots.
+    let base_dir = resolve_base_dir(&output_dir, locations)?;
+    std::fs::create_dir_all(&base_dir).context("failed to create output directory")?;
+    let ignored = std::fs::canonicalize(&base_dir).unwrap_or(base_dir);
+
+    let (mut file_changes, _watcher) = FileChanges::watch(targets, ignored)?;
+
+    let cycle = CycleInputs {
+        source,
+        locations: locations.clone(),
+        name,
+        output_dir,
+        filter,
+    };
+    let mut last_digest = None;
+    let mut subscription = None;
+    let mut trigger = Trigger::Startup;
+
+    loop {
+        let outcome = run_cycle(&cycle, &mut last_digest).await;
+        if let Ok(outcome) = &outcome
+            && (subscription.is_none() || !matches!(trigger, Trigger::ToolListChanged))
+        {
+            // Reconnected after every file-triggered cycle, so the live session runs the
+            // (possibly rebuilt) server and the (possibly edited) config the cycle just used.
+            drop(subscription.take());
+            subscription = subscribe(&outcome.server_id, &outcome.server_config).await;
+        }
+        println!(
+            "{}",
+            format_cycle_line(&CycleLine::new(&trigger, &outcome), output_format)?
+        );
+
+        trigger = tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            paths = file_changes.settled() => Trigger::Files(paths),
+            () = tool_list_changed(&mut subscription) => Trigger::ToolListChanged,
+        };
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// What started a cycle.
+#[derive(Debug)]
+enum Trigger {
+    /// The first cycle, run as soon as watching starts.
+    Startup,
+    /// Changes to these watched paths.
+    Files(Vec<PathBuf>),
+    /// A `notifications/tools/list_changed` from the live session.
+    ToolListChanged,
+}
+
+impl Trigger {
+    /// Short description used in the cycle's log line.
+    fn describe(&self) -> String {
+        match self {
+            Self::Startup => "startup".to_string(),
+            Self::ToolListChanged => "tools/list_changed".to_string(),
+            Self::Files(paths) => {
+                let first = paths.first().map_or_else(String::new, |path| {
+                    path.file_name()
+                        .unwrap_or(path.as_os_str())
+                        .to_string_lossy()
+                        .into_owned()
+                });
+                match paths.len() {
+                    0 | 1 => format!("{first} changed"),
+                    count => format!("{first} and {} more changed", count - 1),
+                }
+            }
+        }
+    }
+}
+
+/// Inputs every cycle resolves afresh, mirroring a one-off `generate` run.
+#[derive(Debug)]
+struct CycleInputs {
+    source: ServerSource,
+    locations: McpConfigLocations,
+    name: Option<String>,
+    output_dir: OutputDir,
+    filter: FilterSelection,
+}
+
+/// Result of a cycle that reached the server.
+#[derive(Debug)]
+struct CycleOutcome {
+    /// Id the server was resolved under, for (re)subscribing.
+    server_id: ServerId,
+    /// Config the server was reached with, for (re)subscribing.
+    server_config: ServerConfig,
+    /// Display name reported by the server.
+    server_name: String,
+    /// Digest of every discovered tool, before the tool filter.
+    digest: ToolDigest,
+    /// Where files were written, or `None` if the digest was unchanged and nothing was.
+    generated: Option<Generated>,
+}
+
+/// Files written by a cycle.
+#[derive(Debug)]
+struct Generated {
+    tool_count: usize,
+    output_path: PathBuf,
+}
+
+/// Runs one cycle: resolve config, introspect, and regenerate if the tools changed since
+/// `last_digest`, which is updated on success.
+///
+/// # Errors
+///
+/// Returns an error if any step of a one-off `generate` would fail, or if the server reports no
+/// tools.
+async fn run_cycle(
+    inputs: &CycleInputs,
+    last_digest: &mut Option<ToolDigest>,
+) -> Result<CycleOutcome> {
+    let id_from_unvalidated_config_key =
+        matches!(inputs.source, ServerSource::Config { .. }) && inputs.name.is_none();
+    let (server_id, server_config) =
+        resolve_server_config(inputs.source.clone(), &inputs.locations)?;
+    let server_info =
+        discover_server_info(server_id.clone(), &server_config, inputs.name.as_deref()).await?;
+    anyhow::ensure!(
+        !server_info.tools.is_empty(),
+        "server has no tools to generate code for"
+    );
+
+    let digest = digest_tools(&server_info);
+    let generated = if last_digest.as_ref() == Some(&digest) {
+        info!("Tool digest unchanged, skipping regeneration");
+        None
+    } else {
+        let PreparedGeneration {
+            base_dir,
+            output_path,
+            tool_count,
+            generated_code,
+        } = prepare_generation(
+            &server_info,
+            &server_config,
+            id_from_unvalidated_config_key,
+            &inputs.output_dir,
+            inputs.filter.clone(),
+            &inputs.locations,
+        )?;
+        export_generated_code(generated_code, &base_dir, &output_path)?;
+        *last_digest = Some(digest.clone());
+        Some(Generated {
+            tool_count,
+            output_path,
+        })
+    };
+
+    Ok(CycleOutcome {
+        server_id,
+        server_config,
+        server_name: server_info.name,
+        digest,
+        generated,
+    })
+}
+
+/// Computes the [`ToolDigest`] of every tool `server_info` reports, the same way the generated
+/// `_meta.json` provenance does.
+fn digest_tools(server_info: &ServerInfo) -> ToolDigest {
+    let entries: Vec<ToolDigestEntry<'_>> = server_info
+        .tools
+        .iter()
+        .map(|tool| ToolDigestEntry {
+            name: tool.name.as_str(),
+            description: &tool.description,
+            input_schema: &tool.input_schema,
+            output_schema: tool.output_schema.as_ref(),
+        })
+        .collect();
+    ToolDigest::compute(&entries)
+}
+
+/// Opens a live session listening for tool list changes, or logs why it could not.
+///
+/// Watching carries on without one: file changes still trigger cycles.
+async fn subscribe(
+    server_id: &ServerId,
+    server_config: &ServerConfig,
+) -> Option<ToolListSubscription> {
+    match ToolListSubscription::connect(server_id, server_config).await {
+        Ok(subscription) => {
+            if !subscription.server_advertises_list_changed() {
+                info!("Server does not advertise tools.listChanged; relying on file changes");
+            }
+            Some(subscription)
+        }
+        Err(err) => {
+            warn!("Could not open a live session for tools/list_changed: {err}");
+            None
+        }
+    }
+}
+
+/// Resolves when the live session reports a tool list change; never resolves without one.
+///
+/// Drops the session once it closes, so the next file-triggered cycle reconnects.
+async fn tool_list_changed(subscription: &mut Option<ToolListSubscription>) {
+    if let Some(live) = subscription {
+        if live.changed().await {
+            return;
+        }
+        warn!("Live session closed; tools/list_changed is unavailable until the next cycle");
+        *subscription = None;
+    }
+    std::future::pending::<()>().await;
+}
+
+/// A watched path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WatchTarget {
+    /// Canonical path of the watched file or directory.
+    path: PathBuf,
+    /// Whether `path` is a directory, watched recursively.
+    is_dir: bool,
+}
+
+impl WatchTarget {
+    /// Canonicalizes `path`, which must exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` does not exist or cannot be resolved.
+    fn resolve(path: &Path) -> Result<Self> {
+        let canonical = std::fs::canonicalize(path)
+            .with_context(|| format!("cannot watch '{}'", path.display()))?;
+        let is_dir = canonical.is_dir();
+        Ok(Self {
+            path: canonical,
+            is_dir,
+        })
+    }
+
+    /// The path handed to the watcher, and how.
+    ///
+    /// A file is watched through its parent directory: editors commonly save by writing a new
+    /// file and renaming it over the old one, which a watch on the old inode would miss.
+    fn watch_root(&self) -> (&Path, RecursiveMode) {
+        if self.is_dir {
+            (&self.path, RecursiveMode::Recursive)
+        } else {
+            (
+                self.path.parent().unwrap_or(&self.path),
+                RecursiveMode::NonRecursive,
+            )
+        }
+    }
+
+    /// Whether an event on `path` concerns this target.
+    fn covers(&self, path: &Path) -> bool {
+        if self.is_dir {
+            path.starts_with(&self.path)
+        } else {
+            path == self.path
+        }
+    }
+}
+
+/// Whether an event on `path` should trigger a cycle: it concerns one of `targets` and is not
+/// under `ignored` (the output base directory).
+fn is_relevant(targets: &[WatchTarget], ignored: &Path, path: &Path) -> bool {
+    !path.starts_with(ignored) && targets.iter().any(|target| target.covers(path))
+}
+
+/// Debounced stream of relevant file changes.
+#[derive(Debug)]
+struct FileChanges {
+    events: mpsc::Receiver<PathBuf>,
+    /// Paths received since the last settled batch; kept here so [`settled`](Self::settled)
+    /// stays cancel-safe across `select!` iterations.
+    pending: BTreeSet<PathBuf>,
+}
+
+impl FileChanges {
+    /// Starts watching `targets`. The returned watcher must be kept alive for as long as events
+    /// are wanted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform watcher cannot be created or a target cannot be
+    /// watched.
+    fn watch(targets: Vec<WatchTarget>, ignored: PathBuf) -> Result<(Self, RecommendedWatcher)> {
+        let (sender, events) = mpsc::channel(FILE_EVENT_CHANNEL_CAPACITY);
+        let roots: BTreeSet<(PathBuf, bool)> = targets
+            .iter()
+            .map(|target| {
+                let (root, mode) = target.watch_root();
+                (root.to_path_buf(), mode == RecursiveMode::Recursive)
+            })
+            .collect();
+
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                    for path in event.paths {
+                        if is_relevant(&targets, &ignored, &path) {
+                            // Full: enough events are already pending to trigger a cycle.
+                            let _ = sender.try_send(path);
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => warn!("File watcher error: {err}"),
+            })
+            .context("failed to create file watcher")?;
+
+        for (root, recursive) in &roots {
+            let mode = if *recursive {
+                RecursiveMode::Recursive
+            } else {
+                RecursiveMode::NonRecursive
+            };
+            watcher
+                .watch(root, mode)
+                .with_context(|| format!("failed to watch '{}'", root.display()))?;
+        }
+
+        Ok((
+            Self {
+                events,
+                pending: BTreeSet::new(),
+            },
+            watcher,
+        ))
+    }
+
+    /// Waits for a change, then until [`DEBOUNCE`] passes without another, and returns every
+    /// changed path in between.
+    async fn settled(&mut self) -> Vec<PathBuf> {
+        if self.pending.is_empty() {
+            match self.events.recv().await {
+                Some(path) => {
+                    self.pending.insert(path);
+                }
+                None => std::future::pending().await,
+            }
+        }
+        while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, self.events.recv()).await {
+            self.pending.insert(path);
+        }
+        std::mem::take(&mut self.pending).into_iter().collect()
+    }
+}
+
+/// One cycle's log line.
+#[derive(Debug, Serialize)]
+struct CycleLine {
+    /// Local wall-clock time the cycle finished
+    time: String,
+    /// What started the cycle
+    trigger: String,
+    /// `generated`, `unchanged`, or `failed`
+    outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    server_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    server_name: Option<String>,
+    /// Abbreviated tool digest
+    #[serde(skip_serializing_if = "Option::is_none")]
+    digest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl CycleLine {
+    fn new(trigger: &Trigger, outcome: &Result<CycleOutcome>) -> Self {
+        let mut line = Self {
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            trigger: trigger.describe(),
+            outcome: "failed",
+            server_id: None,
+            server_name: None,
+            digest: None,
+            tool_count: None,
+            output_path: None,
+            error: None,
+        };
+        match outcome {
+            Ok(outcome) => {
+                line.outcome = if outcome.generated.is_some() {
+                    "generated"
+                } else {
+                    "unchanged"
+                };
+                line.server_id = Some(outcome.server_id.to_string());
+                line.server_name = Some(outcome.server_name.clone());
+                line.digest = Some(
+                    outcome
+                        .digest
+                        .as_str()
+                        .chars()
+                        .take(SHORT_DIGEST_LEN)
+                        .collect(),
+                );
+                if let Some(generated) = &outcome.generated {
+                    line.tool_count = Some(generated.tool_count);
+                    line.output_path = Some(generated.output_path.display().to_string());
+                }
+            }
+            Err(err) => line.error = Some(escape_error_text(&format!("{err:#}"))),
+        }
+        line
+    }
+}
+
+/// Formats a cycle line: one compact JSON object per line for `--format json`, a single
+/// human-readable line otherwise.
+///
+/// # Errors
+///
+/// Returns an error if JSON serialization fails.
+fn format_cycle_line(line: &CycleLine, output_format: OutputFormat) -> Result<String> {
+    if output_format == OutputFormat::Json {
+        return json::format_compact(line);
+    }
+    let detail = match (line.outcome, &line.error) {
+        (_, Some(error)) => format!("failed: {error}"),
+        ("generated", None) => format!(
+            "generated {} tools for {} (digest {}) -> {}",
+            line.tool_count.unwrap_or_default(),
+            escape_display(line.server_name.as_deref().unwrap_or_default()),
+            line.digest.as_deref().unwrap_or_default(),
+            line.output_path.as_deref().unwrap_or_default()
+        ),
+        _ => format!(
+            "tools unchanged (digest {}), skipped",
+            line.digest.as_deref().unwrap_or_default()
+        ),
+    };
+    Ok(format!("[{}] {}: {detail}", line.time, line.trigger))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_execution_core::ToolName;
+    use mcp_execution_introspector::{ServerCapabilities, ToolInfo};
+    use serde_json::json;
+
+    fn server_info(tools: &[(&str, &str)]) -> ServerInfo {
+        ServerInfo {
+            id: ServerId::new("watched").unwrap(),
+            name: "Watched Server".to_string(),
+            version: "1.0.0".to_string(),
+            tools: tools
+                .iter()
+                .map(|(name, description)| ToolInfo {
+                    name: ToolName::new(*name).unwrap(),
+                    description: (*description).to_string(),
+                    input_schema: json!({"type": "object"}),
+                    output_schema: None,
+                })
+                .collect(),
+            capabilities: ServerCapabilities {
+                supports_tools: true,
+                supports_resources: false,
+                supports_prompts: false,
+            },
+        }
+    }
+
+    fn outcome(generated: Option<Generated>) -> CycleOutcome {
+        CycleOutcome {
+            server_id: ServerId::new("watched").unwrap(),
+            server_config: ServerConfig::builder()
+                .command("watched-server".to_string())
+                .build()
+                .unwrap(),
+            server_name: "Watched Server".to_string(),
+            digest: digest_tools(&server_info(&[("a", "")])),
+            generated,
+        }
+    }
+
+    #[test]
+    fn test_digest_tools_tracks_tool_changes_not_order() {
+        let base = digest_tools(&server_info(&[("a", "first"), ("b", "second")]));
+        let reordered = digest_tools(&server_info(&[("b", "second"), ("a", "first")]));
+        let edited = digest_tools(&server_info(&[("a", "first"), ("b", "changed")]));
+
+        assert_eq!(base, reordered);
+        assert_ne!(base, edited);
+    }
+
+    #[test]
+    fn test_is_relevant_matches_files_exactly_and_directories_recursively() {
+        let targets = vec![
+            WatchTarget {
+                path: PathBuf::from("/home/u/.claude/mcp.json"),
+                is_dir: false,
+            },
+            WatchTarget {
+                path: PathBuf::from("/src/server"),
+                is_dir: true,
+            },
+        ];
+        let ignored = Path::new("/src/server/out");
+
+        assert!(is_relevant(
+            &targets,
+            ignored,
+            Path::new("/home/u/.claude/mcp.json")
+        ));
+        assert!(!is_relevant(
+            &targets,
+            ignored,
+            Path::new("/home/u/.claude/other.json")
+        ));
+        assert!(is_relevant(
+            &targets,
+            ignored,
+            Path::new("/src/server/src/main.rs")
+        ));
+        assert!(!is_relevant(
+            &targets,
+            ignored,
+            Path::new("/src/server/out/x/index.ts")
+        ));
+        assert!(!is_relevant(
+            &targets,
+            ignored,
+            Path::new("/src/other/main.rs")
+        ));
+    }
+
+    #[test]
+    fn test_watch_target_watches_files_through_their_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("server.bin");
+        std::fs::write(&file, "").unwrap();
+
+        let file_target = WatchTarget::resolve(&file).unwrap();
+        let (root, mode) = file_target.watch_root();
+        assert_eq!(root, std::fs::canonicalize(dir.path()).unwrap());
+        assert_eq!(mode, RecursiveMode::NonRecursive);
+
+        let dir_target = WatchTarget::resolve(dir.path()).unwrap();
+        assert_eq!(dir_target.watch_root().1, RecursiveMode::Recursive);
+
+        assert!(WatchTarget::resolve(&dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_trigger_describe() {
+        assert_eq!(Trigger::Startup.describe(), "startup");
+        assert_eq!(Trigger::ToolListChanged.describe(), "tools/list_changed");
+        assert_eq!(
+            Trigger::Files(vec![PathBuf::from("/a/mcp.json")]).describe(),
+            "mcp.json changed"
+        );
+        assert_eq!(
+            Trigger::Files(vec![
+                PathBuf::from("/a/lib.rs"),
+                PathBuf::from("/a/main.rs")
+            ])
+            .describe(),
+            "lib.rs and 1 more changed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_file_changes_settled_debounces_a_burst() {
+        let (sender, events) = mpsc::channel(8);
+        let mut changes = FileChanges {
+            events,
+            pending: BTreeSet::new(),
+        };
+        sender.send(PathBuf::from("/a")).await.unwrap();
+        sender.send(PathBuf::from("/b")).await.unwrap();
+        sender.send(PathBuf::from("/a")).await.unwrap();
+
+        assert_eq!(
+            changes.settled().await,
+            vec![PathBuf::from("/a"), PathBuf::from("/b")]
+        );
+        assert!(changes.pending.is_empty());
+    }
+
+    #[test]
+    fn test_format_cycle_line_text() {
+        let generated = CycleLine::new(
+            &Trigger::Startup,
+            &Ok(outcome(Some(Generated {
+                tool_count: 3,
+                output_path: PathBuf::from("/out/watched"),
+            }))),
+        );
+        let text = format_cycle_line(&generated, OutputFormat::Text).unwrap();
+        assert!(text.contains("startup: generated 3 tools for \"Watched Server\""));
+        assert!(text.ends_with("-> /out/watched"));
+        assert!(!text.contains('\n'));
+
+        let unchanged = CycleLine::new(&Trigger::ToolListChanged, &Ok(outcome(None)));
+        let text = format_cycle_line(&unchanged, OutputFormat::Pretty).unwrap();
+        assert!(text.contains("tools/list_changed: tools unchanged (digest "));
+        assert!(text.ends_with("), skipped"));
+
+        let failed = CycleLine::new(
+            &Trigger::Files(vec![PathBuf::from("/a/mcp.json")]),
+            &Err(anyhow::anyhow!("bad\nconfig")),
+        );
+        let text = format_cycle_line(&failed, OutputFormat::Text).unwrap();
+        assert!(text.contains("mcp.json changed: failed: bad"));
+        assert!(!text.contains('\n'));
+    }
+
+    #[test]
+    fn test_format_cycle_line_json_is_one_line() {
+        let line = CycleLine::new(&Trigger::Startup, &Ok(outcome(None)));
+        let formatted = format_cycle_line(&line, OutputFormat::Json).unwrap();
+        assert!(!formatted.contains('\n'));
+
+        let value: serde_json::Value = serde_json::from_str(&formatted).unwrap();
+        assert_eq!(value["outcome"], "unchanged");
+        assert_eq!(value["server_id"], "watched");
+        assert_eq!(value["digest"].as_str().unwrap().len(), SHORT_DIGEST_LEN);
+        assert!(value.get("error").is_none());
+    }
+}
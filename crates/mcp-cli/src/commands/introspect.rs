@@ -5,6 +5,7 @@
 use super::common::{ServerSource, resolve_server_config};
 use anyhow::{Context, Result};
 use mcp_execution_core::cli::{ExitCode, OutputFormat};
+use mcp_execution_core::mcp_config::McpConfigLocations;
 use mcp_execution_introspector::{Introspector, ServerInfo, ToolInfo};
 use serde::Serialize;
 use tracing::info;
@@ -118,7 +119,7 @@ pub struct ToolDisplay {
 ///
 /// # Process
 ///
-/// 1. Builds `ServerConfig` from CLI arguments or loads from ~/.claude/mcp.json
+/// 1. Builds `ServerConfig` from CLI arguments or loads it from the MCP config files
 /// 2. Creates an introspector and connects to the server
 /// 3. Discovers server capabilities and tools
 /// 4. Formats the output according to the specified format
@@ -126,13 +127,14 @@ pub struct ToolDisplay {
 ///
 /// # Arguments
 ///
-/// * `source` - Resolved server-selection source: either a `~/.claude/mcp.json`
-///   name or CLI transport flags with timeout overrides. Timeout overrides
+/// * `source` - Resolved server-selection source: either an MCP config
+///   server name or CLI transport flags with timeout overrides. Timeout overrides
 ///   only exist on the `Flags` arm — a `Config` source always uses the
 ///   `mcp.json` entry's own `connectTimeoutSecs`/`discoverTimeoutSecs`, so
 ///   there is no "ignored override" state to document.
 /// * `detailed` - Whether to show detailed tool schemas
 /// * `output_format` - Output format (json, text, pretty)
+/// * `locations` - Where to look for MCP config files when `source` is `Config`
 ///
 /// # Errors
 ///
@@ -148,8 +150,11 @@ pub struct ToolDisplay {
 /// use mcp_execution_cli::commands::common::{ServerSource, TransportArgs};
 /// use mcp_execution_cli::commands::introspect;
 /// use mcp_execution_core::cli::OutputFormat;
+/// use mcp_execution_core::mcp_config::McpConfigLocations;
 ///
 /// # async fn example() -> anyhow::Result<()> {
+/// let locations = McpConfigLocations::discover(None);
+///
 /// // Simple server
 /// let exit_code = introspect::run(
 ///     ServerSource::Flags {
@@ -163,7 +168,8 @@ pub struct ToolDisplay {
 ///         discover_timeout_secs: None,
 ///     },
 ///     false,
-///     OutputFormat::Json
+///     OutputFormat::Json,
+///     &locations,
 /// ).await?;
 ///
 /// // HTTP transport with a shorter connect timeout
@@ -177,7 +183,8 @@ pub struct ToolDisplay {
 ///         discover_timeout_secs: None,
 ///     },
 ///     false,
-///     OutputFormat::Json
+///     OutputFormat::Json,
+///     &locations,
 /// ).await?;
 /// # Ok(())
 /// # }
@@ -186,9 +193,10 @@ pub async fn run(
     source: ServerSource,
     detailed: bool,
     output_format: OutputFormat,
+    locations: &McpConfigLocations,
 ) -> Result<ExitCode> {
-    // Build server config: either from mcp.json or from CLI arguments
-    let (server_id, config) = resolve_server_config(source)?;
+    // Build server config: either from the MCP config files or from CLI arguments
+    let (server_id, config) = resolve_server_config(source, locations)?;
 
     info!("Introspecting server: {}", server_id);
     info!("Server config: {config:?}");
@@ -566,7 +574,13 @@ mod tests {
     #[tokio::test]
     async fn test_run_server_connection_failure() {
         let source = stdio_source("nonexistent-server-xyz");
-        let result = run(source, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -664,7 +678,13 @@ mod tests {
     async fn test_run_with_text_format() {
         // Test that Text format output works correctly (compact JSON)
         let source = stdio_source("nonexistent-server");
-        let result = run(source, false, OutputFormat::Text).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Text,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         // Connection should fail but format handling should not panic
         assert!(result.is_err());
@@ -674,7 +694,13 @@ mod tests {
     async fn test_run_with_pretty_format() {
         // Test that Pretty format output works correctly (colorized)
         let source = stdio_source("nonexistent-server");
-        let result = run(source, false, OutputFormat::Pretty).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Pretty,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         // Connection should fail but format handling should not panic
         assert!(result.is_err());
@@ -684,7 +710,7 @@ mod tests {
     async fn test_run_with_detailed_mode() {
         // Test that detailed mode doesn't cause crashes even with connection failure
         let source = stdio_source("nonexistent-server");
-        let result = run(source, true, OutputFormat::Json).await; // detailed mode
+        let result = run(source, true, OutputFormat::Json, &McpConfigLocations::new()).await; // detailed mode
 
         assert!(result.is_err());
     }
@@ -698,7 +724,13 @@ mod tests {
             "https://localhost:99999/invalid",
             vec!["Authorization=Bearer test"],
         );
-        let result = run(source, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -726,7 +758,13 @@ mod tests {
     #[tokio::test]
     async fn test_run_sse_transport() {
         let source = sse_source("https://localhost:99999/sse", vec!["X-API-Key=test-key"]);
-        let result = run(source, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -751,7 +789,7 @@ mod tests {
         // Test all output formats don't cause panics
         for format in [OutputFormat::Json, OutputFormat::Text, OutputFormat::Pretty] {
             let source = stdio_source("nonexistent");
-            let result = run(source, false, format).await;
+            let result = run(source, false, format, &McpConfigLocations::new()).await;
 
             assert!(result.is_err());
         }
@@ -762,7 +800,7 @@ mod tests {
         // Test detailed mode with all output formats
         for format in [OutputFormat::Json, OutputFormat::Text, OutputFormat::Pretty] {
             let source = stdio_source("nonexistent");
-            let result = run(source, true, format).await; // detailed
+            let result = run(source, true, format, &McpConfigLocations::new()).await; // detailed
 
             assert!(result.is_err());
         }
@@ -965,7 +1003,13 @@ mod tests {
     #[tokio::test]
     async fn test_run_from_config_not_found() {
         let source = config_source("nonexistent-server-xyz");
-        let result = run(source, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -983,7 +1027,13 @@ mod tests {
         // (server arg is unrepresentable alongside it — enforced by
         // `ServerSource` being a closed enum rather than a runtime check).
         let source = config_source("test-server");
-        let result = run(source, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         // Should fail because config doesn't exist, not because of server
         assert!(result.is_err());
@@ -999,7 +1049,13 @@ mod tests {
     async fn test_run_manual_mode_backward_compatible() {
         // Existing behavior: from_config = None, use server arg
         let source = stdio_source("test-server-direct");
-        let result = run(source, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
@@ -1024,7 +1080,13 @@ mod tests {
             connect_timeout_secs: Some(0),
             discover_timeout_secs: None,
         };
-        let result = run(source, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err = result.unwrap_err();
@@ -1108,7 +1170,13 @@ mod tests {
         let _guard = tracing::subscriber::set_default(capture.clone());
 
         let source = http_source("https://localhost:99999/invalid", vec![&header]);
-        let _ = run(source, false, OutputFormat::Json).await;
+        let _ = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         let logged = capture.joined();
         assert!(logged.contains("Authorization"));
@@ -1135,7 +1203,13 @@ mod tests {
             connect_timeout_secs: None,
             discover_timeout_secs: None,
         };
-        let _ = run(source, false, OutputFormat::Json).await;
+        let _ = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         let logged = capture.joined();
         assert!(logged.contains("GITHUB_TOKEN"));
@@ -1156,7 +1230,13 @@ mod tests {
             connect_timeout_secs: Some(5),
             discover_timeout_secs: Some(90),
         };
-        let result = run(source, false, OutputFormat::Json).await;
+        let result = run(
+            source,
+            false,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+        )
+        .await;
 
         assert!(result.is_err());
         let err_msg = result.unwrap_err().to_string();
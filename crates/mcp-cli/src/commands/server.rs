@@ -1,7 +1,7 @@
 //! Server command implementation.
 //!
-//! Manages MCP server listing, inspection, and validation using
-//! `~/.claude/mcp.json` as the single source of truth for server definitions.
+//! Manages MCP server listing, inspection, and validation of the servers defined across the
+//! MCP config files (see [`mcp_execution_core::mcp_config`]).
 
 use crate::actions::ServerAction;
 use crate::commands::common::{
@@ -13,7 +13,10 @@ use anyhow::{Context, Result};
 use mcp_execution_core::ServerConfig;
 use mcp_execution_core::ServerId;
 use mcp_execution_core::cli::{ExitCode, OutputFormat};
-use mcp_execution_core::{REDACTED_PLACEHOLDER, RedactedUrl, sanitize_path_for_error};
+use mcp_execution_core::mcp_config::{ConfigSource, McpConfigLocations};
+use mcp_execution_core::{
+    REDACTED_PLACEHOLDER, RedactedUrl, interpolation, sanitize_path_for_error,
+};
 use mcp_execution_introspector::Introspector;
 use serde::Serialize;
 use std::path::Path;
@@ -21,6 +24,11 @@ use std::time::Duration;
 use tracing::{info, warn};
 use url::Url;
 
+mod edit;
+mod import;
+
+pub use self::edit::SaveAs;
+
 /// Maximum time `server list` waits for a single http/sse availability
 /// check, independent of (and shorter than) the entry's own configured
 /// `connect_timeout_secs`/`discover_timeout_secs`.
@@ -92,11 +100,16 @@ pub enum ServerStatus {
 ///
 /// ```
 /// use mcp_execution_cli::commands::server::{ServerEntry, ServerStatus};
+/// use mcp_execution_core::mcp_config::{ConfigScope, ConfigSource};
 ///
 /// let entry = ServerEntry {
 ///     id: "github".to_string(),
 ///     command: "github-mcp-server".to_string(),
 ///     status: ServerStatus::Available,
+///     source: ConfigSource {
+///         scope: ConfigScope::User,
+///         path: "/home/user/.claude/mcp.json".into(),
+///     },
 /// };
 ///
 /// assert_eq!(entry.id, "github");
@@ -114,6 +127,8 @@ pub struct ServerEntry {
     /// timeout, so this is a time-bounded, best-effort signal. Run `server
     /// validate <name>` for an authoritative answer on one specific server.
     pub status: ServerStatus,
+    /// Config file the server's definition was read from.
+    pub source: ConfigSource,
 }
 
 /// List of configured servers.
@@ -122,6 +137,7 @@ pub struct ServerEntry {
 ///
 /// ```
 /// use mcp_execution_cli::commands::server::{ServerEntry, ServerList, ServerStatus};
+/// use mcp_execution_core::mcp_config::{ConfigScope, ConfigSource};
 ///
 /// let list = ServerList {
 ///     servers: vec![
@@ -129,6 +145,10 @@ pub struct ServerEntry {
 ///             id: "github".to_string(),
 ///             command: "github-mcp-server".to_string(),
 ///             status: ServerStatus::Available,
+///             source: ConfigSource {
+///                 scope: ConfigScope::Project,
+///                 path: "/work/repo/.mcp.json".into(),
+///             },
 ///         }
 ///     ],
 /// };
@@ -156,6 +176,7 @@ pub struct ServerList {
 ///     status: ServerStatus::Available,
 ///     tools: vec![],
 ///     capabilities: vec!["tools".to_string()],
+///     stderr: None,
 /// };
 ///
 /// assert_eq!(info.id, "github");
@@ -176,6 +197,9 @@ pub struct ServerInfo {
     pub tools: Vec<ToolSummary>,
     /// Server capabilities.
     pub capabilities: Vec<String>,
+    /// Last lines a stdio server wrote to stderr before introspection failed, redacted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
 }
 
 /// Tool summary for output.
@@ -225,21 +249,213 @@ pub struct ValidationResult {
     pub message: String,
 }
 
+/// Kind of change a `server add`/`remove`/`set-timeout` made to an MCP config file.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_cli::commands::server::ConfigChangeKind;
+///
+/// assert_eq!(
+///     serde_json::to_string(&ConfigChangeKind::Replaced).unwrap(),
+///     "\"replaced\""
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigChangeKind {
+    /// A new entry was written.
+    Added,
+    /// An existing entry was overwritten (`server add --force`).
+    Replaced,
+    /// An entry was deleted.
+    Removed,
+    /// Fields of an existing entry were changed (`server set-timeout`).
+    Updated,
+}
+
+impl ConfigChangeKind {
+    /// Lowercase name, matching the serialized form.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Replaced => "replaced",
+            Self::Removed => "removed",
+            Self::Updated => "updated",
+        }
+    }
+}
+
+/// Result of editing one server entry in an MCP config file.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_cli::commands::server::{ConfigChange, ConfigChangeKind};
+/// use mcp_execution_core::mcp_config::{ConfigScope, ConfigSource};
+///
+/// let change = ConfigChange {
+///     server: "github".to_string(),
+///     change: ConfigChangeKind::Added,
+///     config: ConfigSource {
+///         scope: ConfigScope::User,
+///         path: "/home/user/.claude/mcp.json".into(),
+///     },
+/// };
+///
+/// assert_eq!(change.change, ConfigChangeKind::Added);
+/// ```
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// Server name.
+    pub server: String,
+    /// What happened to its entry.
+    pub change: ConfigChangeKind,
+    /// The file that was edited.
+    pub config: ConfigSource,
+}
+
+/// How `server import` classifies a server from the source config against the target file.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_cli::commands::server::ImportStatus;
+///
+/// assert_eq!(
+///     serde_json::to_string(&ImportStatus::Conflict).unwrap(),
+///     "\"conflict\""
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStatus {
+    /// The target file has no server of that name.
+    New,
+    /// The target file already has an identical entry.
+    Unchanged,
+    /// The target file has a different entry of that name.
+    Conflict,
+    /// The entry cannot be used: a malformed entry, an invalid name, or a failed security check.
+    Invalid,
+}
+
+/// What `server import` does with a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportAction {
+    /// Write it as a new entry.
+    Add,
+    /// Overwrite the conflicting entry (`--replace <name>`).
+    Replace,
+    /// Leave the target file's entry, if any, as it is.
+    Skip,
+}
+
+/// One server in a `server import` preview.
+///
+/// Argument lists and `env`/`headers` values routinely hold secrets, so only the command (or
+/// redacted URL) and the variable names are shown.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_cli::commands::server::{ImportAction, ImportStatus, ImportedServer};
+///
+/// let server = ImportedServer {
+///     name: "github".to_string(),
+///     status: ImportStatus::New,
+///     action: ImportAction::Add,
+///     command: Some("npx <redacted> <redacted>".to_string()),
+///     env: Some(r#"{"GITHUB_TOKEN": "<redacted>"}"#.to_string()),
+///     headers: None,
+///     reason: None,
+/// };
+///
+/// assert_eq!(server.action, ImportAction::Add);
+/// ```
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ImportedServer {
+    /// Server name.
+    pub name: String,
+    /// Comparison with the target file.
+    pub status: ImportStatus,
+    /// What the import does with it.
+    pub action: ImportAction,
+    /// Command used to start the server, or its URL, redacted as in `server list`. Absent for
+    /// an entry that could not be parsed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Environment variables, values redacted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    /// HTTP headers, values redacted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<String>,
+    /// Why the server is invalid or skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Result of `server import`.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_cli::commands::server::ImportReport;
+/// use mcp_execution_core::mcp_config::{ConfigScope, ConfigSource};
+///
+/// let report = ImportReport {
+///     source: "~/.cursor/mcp.json".to_string(),
+///     target: ConfigSource {
+///         scope: ConfigScope::User,
+///         path: "/home/user/.claude/mcp.json".into(),
+///     },
+///     servers: vec![],
+///     imported: 0,
+///     dry_run: true,
+/// };
+///
+/// assert!(report.servers.is_empty());
+/// ```
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Sanitized path of the file servers were read from.
+    pub source: String,
+    /// The file servers are written to.
+    pub target: ConfigSource,
+    /// Every server in the source file, in file order.
+    pub servers: Vec<ImportedServer>,
+    /// Number of entries written (or that would be, with `--dry-run`).
+    pub imported: usize,
+    /// Whether this was only a preview.
+    pub dry_run: bool,
+}
+
 /// Runs the server command.
 ///
-/// Manages server listing, detailed info, and validation.
-/// All server definitions are loaded from `~/.claude/mcp.json`.
+/// Manages server listing, detailed info, validation, and edits to the MCP config file.
+/// All server definitions are loaded from the MCP config files `locations` finds; `Add`,
+/// `Remove`, `SetTimeout`, and `Import` write only to
+/// [`McpConfigLocations::writable_config`].
 ///
 /// # Arguments
 ///
-/// * `action` - Server management action (List, Info, or Validate)
+/// * `action` - Server management action (List, Info, Validate, Add, Remove, `SetTimeout`, or
+///   Import)
 /// * `output_format` - Output format (json, text, pretty)
+/// * `locations` - Where to look for MCP config files
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The configuration file cannot be read or is malformed
 /// - For the `Info` action, the named server is not found in the configuration
+/// - For the `Add`, `Remove`, and `SetTimeout` actions, the entry is invalid, already exists
+///   (`Add` without `--force`), or is missing, or the config file cannot be written
+/// - For the `Import` action, the source config cannot be found or read, or a name given to
+///   `--only`/`--skip`/`--replace` is not in it
 /// - Output formatting fails (serialization error)
 ///
 /// Note: For the `Validate` action, an unknown server name is reported via
@@ -253,42 +469,70 @@ pub struct ValidationResult {
 /// ```no_run
 /// use mcp_execution_cli::commands::server;
 /// use mcp_execution_core::cli::{ExitCode, OutputFormat};
+/// use mcp_execution_core::mcp_config::McpConfigLocations;
 ///
 /// # #[tokio::main]
 /// # async fn main() {
 /// let result = server::run(
 ///     mcp_execution_cli::ServerAction::List,
-///     OutputFormat::Json
+///     OutputFormat::Json,
+///     &McpConfigLocations::discover(None),
 /// ).await;
 /// assert!(result.is_ok());
 /// # }
 /// ```
-pub async fn run(action: ServerAction, output_format: OutputFormat) -> Result<ExitCode> {
+pub async fn run(
+    action: ServerAction,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+) -> Result<ExitCode> {
     info!("Server action: {:?}", action);
     info!("Output format: {}", output_format);
 
     match action {
-        ServerAction::List => list_servers(output_format).await,
-        ServerAction::Info { server } => show_server_info(server, output_format).await,
-        ServerAction::Validate { command } => validate_command(command, output_format).await,
+        ServerAction::List => list_servers(output_format, locations).await,
+        ServerAction::Info { server } => show_server_info(server, output_format, locations).await,
+        ServerAction::Validate { command } => {
+            validate_command(command, output_format, locations).await
+        }
+        ServerAction::Add(args) => edit::add_server(args, output_format, locations).await,
+        ServerAction::Remove { name } => edit::remove_server(name, output_format, locations).await,
+        ServerAction::SetTimeout {
+            name,
+            connect_timeout_secs,
+            discover_timeout_secs,
+        } => {
+            edit::set_timeout(
+                name,
+                connect_timeout_secs,
+                discover_timeout_secs,
+                output_format,
+                locations,
+            )
+            .await
+        }
+        ServerAction::Import(args) => import::import_servers(args, output_format, locations).await,
     }
 }
 
-/// Lists all servers configured in `~/.claude/mcp.json`.
+/// Lists all servers configured across the MCP config files, each with the file its
+/// definition came from.
 ///
-/// Returns an empty list (not an error) when the config file does not exist.
+/// Returns an empty list (not an error) when no config file exists.
 ///
 /// For every http/sse entry, this performs a real, bounded MCP handshake
 /// against the remote server (see `LIST_AVAILABILITY_TIMEOUT`), not a purely
 /// local check — this has real network cost and, per known
 /// `mcp-execution-introspector` limitations, can leave an orphaned session
 /// on the remote server per invocation.
-async fn list_servers(output_format: OutputFormat) -> Result<ExitCode> {
-    let servers = list_mcp_servers()
-        .context("failed to read server configuration from ~/.claude/mcp.json")?;
+async fn list_servers(
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+) -> Result<ExitCode> {
+    let servers = list_mcp_servers(locations).context("failed to read server configuration")?;
 
     if servers.is_empty() {
-        info!("No MCP servers configured in ~/.claude/mcp.json");
+        info!("No MCP servers configured");
         let server_list = ServerList {
             servers: Vec::new(),
         };
@@ -298,7 +542,7 @@ async fn list_servers(output_format: OutputFormat) -> Result<ExitCode> {
     // Each server's status check may include a full MCP introspection
     // attempt (see `transport_available`); run them concurrently so `list`'s
     // total latency is bounded by the slowest single check, not their sum.
-    let checks = servers.into_iter().map(|(name, entry)| async move {
+    let checks = servers.into_iter().map(|(name, entry, source)| async move {
         let command = build_command_string(&entry);
         let status = if transport_available(&name, &entry).await {
             ServerStatus::Available
@@ -310,6 +554,7 @@ async fn list_servers(output_format: OutputFormat) -> Result<ExitCode> {
             id: name,
             command,
             status,
+            source,
         }
     });
     let entries = futures_util::future::join_all(checks).await;
@@ -325,12 +570,16 @@ async fn list_servers(output_format: OutputFormat) -> Result<ExitCode> {
 /// An entry whose `url` (or other field) fails [`build_core_config`]'s security validation is
 /// reported the same way as an entry that is well-formed but unreachable — a structured
 /// `"status": "unavailable"` [`ServerInfo`] through `output_format`, not a raw, unformatted error
-/// (#305). [`get_mcp_server_entry`]'s own errors — a missing/malformed `~/.claude/mcp.json` as
+/// (#305). [`get_mcp_server_entry`]'s own errors — a missing/malformed MCP config file as
 /// well as a genuinely absent server name — are not caught here and still propagate as `Err`
 /// (unlike `server validate`, which distinguishes these cases into its own structured
 /// `ValidationResult`, #479).
-async fn show_server_info(server: String, output_format: OutputFormat) -> Result<ExitCode> {
-    let (server_id, entry) = get_mcp_server_entry(&server)?;
+async fn show_server_info(
+    server: String,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+) -> Result<ExitCode> {
+    let (server_id, entry) = get_mcp_server_entry(&server, locations)?;
     let command = build_command_string(&entry);
 
     let server_config = match build_core_config(&entry) {
@@ -385,6 +634,7 @@ async fn show_server_info(server: String, output_format: OutputFormat) -> Result
                 status: ServerStatus::Available,
                 tools,
                 capabilities,
+                stderr: None,
             };
 
             crate::formatters::emit(&server_info, output_format, ExitCode::SUCCESS)
@@ -396,11 +646,11 @@ async fn show_server_info(server: String, output_format: OutputFormat) -> Result
                 escape_error_text(&e.to_string())
             );
 
-            crate::formatters::emit(
-                &unavailable_server_info(server, command),
-                output_format,
-                ExitCode::ERROR,
-            )
+            let server_info = ServerInfo {
+                stderr: e.server_stderr().map(str::to_string),
+                ..unavailable_server_info(server, command)
+            };
+            crate::formatters::emit(&server_info, output_format, ExitCode::ERROR)
         }
     }
 }
@@ -417,23 +667,28 @@ fn unavailable_server_info(server: String, command: String) -> ServerInfo {
         status: ServerStatus::Unavailable,
         tools: Vec::new(),
         capabilities: Vec::new(),
+        stderr: None,
     }
 }
 
 /// Validates a server by checking its command and attempting introspection.
 ///
-/// The server must be configured in `~/.claude/mcp.json`. An entry that is present but fails
+/// The server must be configured in one of the MCP config files. An entry that is present but fails
 /// [`build_core_config`]'s security validation (e.g. an invalid URL scheme) is reported with a
 /// message describing that specific problem, not the "not found" message reserved for a
 /// genuinely absent entry (#304).
 ///
 /// Config loading and name lookup are performed as two separate steps (via [`load_mcp_config`]
 /// and [`lookup_server_entry`], rather than the combined [`get_mcp_server_entry`]) so a missing
-/// or malformed `~/.claude/mcp.json` produces its own message instead of being collapsed into
+/// or malformed MCP config file produces its own message instead of being collapsed into
 /// "server not found" — mirroring how `generate --from-config`/`server info`/`server list`
 /// report the same underlying conditions (#479).
-async fn validate_command(server_name: String, output_format: OutputFormat) -> Result<ExitCode> {
-    let config = match load_mcp_config() {
+async fn validate_command(
+    server_name: String,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+) -> Result<ExitCode> {
+    let config = match load_mcp_config(locations) {
         Ok(config) => config,
         Err(e) => {
             let result = ValidationResult {
@@ -464,15 +719,24 @@ async fn validate_command(server_name: String, output_format: OutputFormat) -> R
     // variant must fail to compile here rather than silently skip the
     // precheck (that asymmetry with the exhaustive match below is what let
     // #280 slip through).
+    //
+    // The check runs against the `${VAR}`-expanded value but the message quotes
+    // the value as written, so an expanded secret never reaches the output. A
+    // value that fails to expand skips the precheck: `build_core_config` below
+    // reports the unset variable by name.
+    let expanded = |field: &str, value: &str| {
+        interpolation::expand_vars(value, field, interpolation::process_env).ok()
+    };
     let precheck_failure = match &entry.transport {
         McpTransport::Stdio {
             command: bin_command,
             ..
-        } => (!check_command_exists(bin_command))
-            .then(|| format!("Command '{bin_command}' not found in PATH")),
-        McpTransport::Http { url, .. } | McpTransport::Sse { url, .. } => {
-            (!url_well_formed(url)).then(|| url_precheck_message(url))
-        }
+        } => expanded("command", bin_command)
+            .filter(|resolved| !check_command_exists(resolved))
+            .map(|_| format!("Command '{bin_command}' not found in PATH")),
+        McpTransport::Http { url, .. } | McpTransport::Sse { url, .. } => expanded("url", url)
+            .filter(|resolved| !url_well_formed(resolved))
+            .map(|_| url_precheck_message(url)),
     };
 
     if let Some(message) = precheck_failure {
@@ -672,7 +936,10 @@ async fn discover_within(name: &str, config: &ServerConfig, timeout: Duration) -
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mcp_execution_core::EnvPolicy;
+    use mcp_execution_core::mcp_config::ConfigScope;
     use std::collections::HashMap;
+    use std::path::PathBuf;
 
     #[test]
     fn test_server_status_serializes_lowercase() {
@@ -694,6 +961,7 @@ mod tests {
                 args: Vec::new(),
                 env: HashMap::default(),
                 cwd: None,
+                env_policy: EnvPolicy::Inherit,
             },
             connect_timeout_secs: None,
             discover_timeout_secs: None,
@@ -713,6 +981,7 @@ mod tests {
                 args: vec!["/path/to/server.js".to_string(), "--verbose".to_string()],
                 env: HashMap::default(),
                 cwd: None,
+                env_policy: EnvPolicy::Inherit,
             },
             connect_timeout_secs: None,
             discover_timeout_secs: None,
@@ -739,6 +1008,7 @@ mod tests {
                 args: vec!["--api-key".to_string(), secret.to_string()],
                 env: HashMap::default(),
                 cwd: None,
+                env_policy: EnvPolicy::Inherit,
             },
             connect_timeout_secs: None,
             discover_timeout_secs: None,
@@ -761,6 +1031,7 @@ mod tests {
                 args: Vec::new(),
                 env: HashMap::default(),
                 cwd: None,
+                env_policy: EnvPolicy::Inherit,
             },
             connect_timeout_secs: None,
             discover_timeout_secs: None,
@@ -988,12 +1259,17 @@ mod tests {
             id: "test".to_string(),
             command: "test-cmd".to_string(),
             status: ServerStatus::Available,
+            source: ConfigSource {
+                scope: ConfigScope::Project,
+                path: PathBuf::from("/work/.mcp.json"),
+            },
         };
 
         let json = serde_json::to_string(&entry).unwrap();
         assert!(json.contains("test"));
         assert!(json.contains("test-cmd"));
         assert!(json.contains("available"));
+        assert!(json.contains(r#""scope":"project""#));
     }
 
     #[test]
@@ -1003,6 +1279,10 @@ mod tests {
                 id: "test".to_string(),
                 command: "test-cmd".to_string(),
                 status: ServerStatus::Available,
+                source: ConfigSource {
+                    scope: ConfigScope::User,
+                    path: PathBuf::from("/home/u/.claude/mcp.json"),
+                },
             }],
         };
 
@@ -1024,6 +1304,7 @@ mod tests {
                 description: "A test tool".to_string(),
             }],
             capabilities: vec!["tools".to_string()],
+            stderr: None,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -1095,6 +1376,7 @@ mod tests {
                 server: "nonexistent-server".to_string(),
             },
             OutputFormat::Json,
+            &McpConfigLocations::discover(None),
         )
         .await;
 
@@ -1109,7 +1391,7 @@ mod tests {
         assert!(result.is_err());
         let message = format!("{:#}", result.unwrap_err());
         assert_eq!(
-            message.matches("not found in ~/.claude/mcp.json").count(),
+            message.matches("not found in any MCP config").count(),
             1,
             "expected exactly one not-found message in the error chain, got: {message}"
         );
@@ -1174,12 +1456,14 @@ mod tests {
             r#"{"mcpServers": {"badhttp": {"type": "http", "url": "not-a-url"}}}"#,
         );
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Validate {
                     command: "badhttp".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
@@ -1194,12 +1478,14 @@ mod tests {
             r#"{"mcpServers": {"badsse": {"type": "sse", "url": "ftp://example.com"}}}"#,
         );
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Validate {
                     command: "badsse".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
@@ -1214,12 +1500,14 @@ mod tests {
             r#"{"mcpServers": {"badstdio": {"command": "this_command_definitely_does_not_exist_12345"}}}"#,
         );
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Validate {
                     command: "badstdio".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
@@ -1239,12 +1527,14 @@ mod tests {
             r#"{"mcpServers": {"unreachable": {"type": "http", "url": "http://127.0.0.1:1/mcp"}}}"#,
         );
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Validate {
                     command: "unreachable".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
@@ -1264,12 +1554,14 @@ mod tests {
             r#"{"mcpServers": {"badtimeout": {"type": "http", "url": "https://example.com/mcp", "connectTimeoutSecs": 0}}}"#,
         );
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Validate {
                     command: "badtimeout".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
@@ -1289,12 +1581,14 @@ mod tests {
             r#"{"mcpServers": {"http-malformed": {"type": "http", "url": "not-a-url"}}}"#,
         );
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Info {
                     server: "http-malformed".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
@@ -1322,6 +1616,7 @@ mod tests {
 
         let json = serde_json::to_string(&info).unwrap();
         assert!(json.contains("\"status\":\"unavailable\""));
+        assert!(!json.contains("stderr"));
     }
 
     // ── validate_command config-load vs. name-lookup error paths (#479) ──
@@ -1340,12 +1635,14 @@ mod tests {
         // HOME points at a fresh temp dir with no `.claude/mcp.json` at all.
         let temp = tempfile::TempDir::new().unwrap();
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Validate {
                     command: "anything".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
@@ -1358,12 +1655,14 @@ mod tests {
     async fn test_validate_command_malformed_config_reports_error() {
         let temp = write_test_mcp_config("not valid json");
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Validate {
                     command: "anything".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
@@ -1376,12 +1675,14 @@ mod tests {
     async fn test_validate_command_unknown_server_name_reports_error() {
         let temp = write_test_mcp_config(r#"{"mcpServers": {"unrelated": {"command": "node"}}}"#);
 
+        let locations = McpConfigLocations::new().with_home_dir(temp.path());
         let result = with_home_pointed_at(temp.path(), || {
             run(
                 ServerAction::Validate {
                     command: "nonexistent-server".to_string(),
                 },
                 OutputFormat::Json,
+                &locations,
             )
         })
         .await;
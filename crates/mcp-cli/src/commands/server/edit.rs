@@ -0,0 +1,690 @@
+//! `server add`, `server remove`, `server set-timeout`, and `--save-as`: edits to the MCP
+//! config file.
+//!
+//! Every edit goes to [`McpConfigLocations::writable_config`] — the `--config` file, or
+//! `~/.claude/mcp.json` — through [`McpConfigDocument`], so keys this project does not model
+//! survive and the file is replaced atomically. An entry is security-validated before anything
+//! is written; files owned by other MCP hosts (`.mcp.json`, `~/.claude.json`, Claude Desktop's
+//! config) are never touched.
+
+use super::{ConfigChange, ConfigChangeKind};
+use crate::actions::AddServerArgs;
+use crate::commands::common::{
+    McpServerEntry, McpTransport, ServerSource, TransportArgs, validate_entry_for_write,
+};
+use crate::commands::generate::{self, FilterSelection, GenerateMode, OutputDir};
+use anyhow::{Context, Result, anyhow, bail};
+use mcp_execution_core::ServerId;
+use mcp_execution_core::cli::{ExitCode, OutputFormat};
+use mcp_execution_core::mcp_config::{ConfigSource, McpConfigDocument, McpConfigLocations};
+use tracing::{info, warn};
+
+/// Adds (or, with `--force`, replaces) a server entry.
+///
+/// With `--generate`, the change is logged rather than printed and the command's output is that
+/// of `generate --from-config <name>`.
+///
+/// # Errors
+///
+/// Returns an error if the name is not a valid server id, the flags do not form a valid entry,
+/// the entry fails security validation, the name already exists without `--force`, or the
+/// config file cannot be read or written.
+pub(super) async fn add_server(
+    args: AddServerArgs,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+) -> Result<ExitCode> {
+    let AddServerArgs {
+        name,
+        command,
+        http,
+        sse,
+        env,
+        cwd,
+        headers,
+        connect_timeout_secs,
+        discover_timeout_secs,
+        force,
+        generate,
+    } = args;
+
+    ServerId::new(&name).with_context(|| format!("'{name}' is not a valid server name"))?;
+
+    let transport = match (http, sse) {
+        (Some(url), None) => TransportArgs::Http { url, headers },
+        (None, Some(url)) => TransportArgs::Sse { url, headers },
+        (None, None) => {
+            let mut command = command.into_iter();
+            let program = command
+                .next()
+                .context("a server command is required after `--` (or use --http/--sse)")?;
+            TransportArgs::Stdio {
+                command: program,
+                args: command.collect(),
+                env,
+                cwd,
+            }
+        }
+        (Some(_), Some(_)) => bail!("--http and --sse cannot be used together"),
+    };
+    let entry = McpServerEntry {
+        transport: McpTransport::try_from(transport)?,
+        connect_timeout_secs,
+        discover_timeout_secs,
+    };
+    validate_entry_for_write(&entry).with_context(|| format!("server '{name}' was not added"))?;
+
+    let target = writable_config(locations)?;
+    let mut document = McpConfigDocument::open(&target.path)?;
+    if document.server(&name).is_some() && !force {
+        bail!(
+            "server '{name}' already exists in {target}\n\
+             Hint: pass --force to replace it"
+        );
+    }
+    let previous = document.insert_server(name.clone(), entry.to_json());
+    document.save().await?;
+    warn_if_shadowed(&name, &target, locations);
+
+    let change = ConfigChange {
+        server: name.clone(),
+        change: if previous.is_some() {
+            ConfigChangeKind::Replaced
+        } else {
+            ConfigChangeKind::Added
+        },
+        config: target,
+    };
+
+    if !generate {
+        return crate::formatters::emit(&change, output_format, ExitCode::SUCCESS);
+    }
+    info!(
+        "Server '{}' {} in {}",
+        change.server,
+        change.change.as_str(),
+        change.config
+    );
+    generate::run(
+        ServerSource::Config { name },
+        None,
+        OutputDir::Default,
+        GenerateMode::default(),
+        FilterSelection::default(),
+        output_format,
+        locations,
+    )
+    .await
+}
+
+/// Removes a server entry.
+///
+/// # Errors
+///
+/// Returns an error if the entry is not in the writable config file (naming the file that does
+/// define it, if any), or the file cannot be read or written.
+pub(super) async fn remove_server(
+    name: String,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+) -> Result<ExitCode> {
+    let target = writable_config(locations)?;
+    let mut document = McpConfigDocument::open(&target.path)?;
+    if document.remove_server(&name).is_none() {
+        return Err(not_in_target(&name, &target, locations));
+    }
+    document.save().await?;
+
+    let change = ConfigChange {
+        server: name,
+        change: ConfigChangeKind::Removed,
+        config: target,
+    };
+    crate::formatters::emit(&change, output_format, ExitCode::SUCCESS)
+}
+
+/// Sets `connectTimeoutSecs` and/or `discoverTimeoutSecs` on an existing entry, leaving its
+/// other fields as written.
+///
+/// # Errors
+///
+/// Returns an error if the entry is not in the writable config file, the edited entry is
+/// malformed or fails security validation (e.g. a timeout outside 1-600 seconds), or the file
+/// cannot be read or written.
+pub(super) async fn set_timeout(
+    name: String,
+    connect_timeout_secs: Option<u64>,
+    discover_timeout_secs: Option<u64>,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+) -> Result<ExitCode> {
+    let target = writable_config(locations)?;
+    let mut document = McpConfigDocument::open(&target.path)?;
+    let Some(entry) = document.server_mut(&name) else {
+        return Err(not_in_target(&name, &target, locations));
+    };
+    let Some(fields) = entry.as_object_mut() else {
+        bail!("server '{name}' in {target} is not a JSON object");
+    };
+    if let Some(secs) = connect_timeout_secs {
+        fields.insert("connectTimeoutSecs".to_string(), secs.into());
+    }
+    if let Some(secs) = discover_timeout_secs {
+        fields.insert("discoverTimeoutSecs".to_string(), secs.into());
+    }
+
+    let edited: McpServerEntry = serde_json::from_value(entry.clone())
+        .map_err(|err| anyhow!("server '{name}' in {target} is malformed: {err}"))?;
+    validate_entry_for_write(&edited)
+        .with_context(|| format!("server '{name}' in {target} was not updated"))?;
+    document.save().await?;
+
+    let change = ConfigChange {
+        server: name,
+        change: ConfigChangeKind::Updated,
+        config: target,
+    };
+    crate::formatters::emit(&change, output_format, ExitCode::SUCCESS)
+}
+
+/// A `--save-as <name>` request from `introspect` or `generate`: the manual transport flags,
+/// checked before the server is contacted and written as an `mcp.json` entry once the command
+/// has succeeded.
+///
+/// The entry is written as given on the command line, so `${VAR}` references stay unexpanded.
+#[derive(Debug)]
+pub struct SaveAs {
+    name: String,
+    entry: McpServerEntry,
+    target: ConfigSource,
+}
+
+impl SaveAs {
+    /// Checks that `source` can be saved as `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `source` is a config entry rather than manual flags, `name` is not a
+    /// valid server name, the entry fails security validation, or the writable config file
+    /// already defines `name` or cannot be read.
+    pub fn prepare(
+        name: String,
+        source: &ServerSource,
+        locations: &McpConfigLocations,
+    ) -> Result<Self> {
+        let ServerSource::Flags {
+            transport,
+            connect_timeout_secs,
+            discover_timeout_secs,
+        } = source
+        else {
+            bail!("--save-as needs a server command, --http, or --sse, not --from-config");
+        };
+        ServerId::new(&name).with_context(|| format!("'{name}' is not a valid server name"))?;
+        let entry = McpServerEntry {
+            transport: McpTransport::try_from(transport.clone())?,
+            connect_timeout_secs: *connect_timeout_secs,
+            discover_timeout_secs: *discover_timeout_secs,
+        };
+        validate_entry_for_write(&entry)
+            .with_context(|| format!("server '{name}' cannot be saved"))?;
+
+        let target = writable_config(locations)?;
+        ensure_unused(&McpConfigDocument::open(&target.path)?, &name, &target)?;
+        Ok(Self {
+            name,
+            entry,
+            target,
+        })
+    }
+
+    /// Writes the entry, then prints it with secrets redacted to stderr, leaving stdout to the
+    /// command's own output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `name` was defined in the meantime, or the config file cannot be
+    /// read or written.
+    pub async fn save(self) -> Result<()> {
+        let mut document = McpConfigDocument::open(&self.target.path)?;
+        ensure_unused(&document, &self.name, &self.target)?;
+        document.insert_server(self.name.clone(), self.entry.to_json());
+        document.save().await?;
+
+        let mut saved = serde_json::Map::new();
+        saved.insert(self.name.clone(), self.entry.to_redacted_json());
+        eprintln!(
+            "Saved server '{}' to {}:\n{}",
+            self.name,
+            self.target,
+            serde_json::to_string_pretty(&saved)?
+        );
+        Ok(())
+    }
+}
+
+/// Refuses to overwrite an existing entry from `--save-as`, which has no `--force`.
+fn ensure_unused(document: &McpConfigDocument, name: &str, target: &ConfigSource) -> Result<()> {
+    if document.server(name).is_some() {
+        bail!(
+            "server '{name}' already exists in {target}\n\
+             Hint: choose another --save-as name, or replace it with `server add {name} --force`"
+        );
+    }
+    Ok(())
+}
+
+/// The file edits go to.
+fn writable_config(locations: &McpConfigLocations) -> Result<ConfigSource> {
+    locations.writable_config().context(
+        "cannot locate ~/.claude/mcp.json (no home directory)\n\
+         Hint: pass --config <path> to choose the file to edit",
+    )
+}
+
+/// Error for a server missing from `target`, pointing at the file that defines it if there is
+/// one.
+fn not_in_target(
+    name: &str,
+    target: &ConfigSource,
+    locations: &McpConfigLocations,
+) -> anyhow::Error {
+    defined_elsewhere(name, target, locations).map_or_else(
+        || anyhow!("server '{name}' not found in {target}"),
+        |source| {
+            anyhow!(
+                "server '{name}' is defined in {source}, not in {target}\n\
+                 Hint: edit that file directly, or select it with --config <path>"
+            )
+        },
+    )
+}
+
+/// Warns when a higher-precedence file also defines `name`, so the entry just written is not
+/// the one other commands will use.
+fn warn_if_shadowed(name: &str, target: &ConfigSource, locations: &McpConfigLocations) {
+    if let Some(source) = defined_elsewhere(name, target, locations) {
+        warn!("server '{name}' is also defined in {source}, which takes precedence over {target}");
+    }
+}
+
+/// The file `name` resolves from across the config layers, when that is not `target`.
+fn defined_elsewhere(
+    name: &str,
+    target: &ConfigSource,
+    locations: &McpConfigLocations,
+) -> Option<ConfigSource> {
+    locations
+        .load()
+        .ok()?
+        .get(name)
+        .map(|server| server.source.clone())
+        .filter(|source| source.path != target.path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+    use std::path::{Path, PathBuf};
+
+    fn add_args(name: &str) -> AddServerArgs {
+        AddServerArgs {
+            name: name.to_string(),
+            command: Vec::new(),
+            http: None,
+            sse: None,
+            env: Vec::new(),
+            cwd: None,
+            headers: Vec::new(),
+            connect_timeout_secs: None,
+            discover_timeout_secs: None,
+            force: false,
+            generate: false,
+        }
+    }
+
+    fn stdio_args(name: &str, command: &[&str]) -> AddServerArgs {
+        AddServerArgs {
+            command: command.iter().map(ToString::to_string).collect(),
+            ..add_args(name)
+        }
+    }
+
+    /// Locations rooted at a temp home, with nothing else to discover.
+    fn locations(home: &Path) -> McpConfigLocations {
+        McpConfigLocations::new().with_home_dir(home)
+    }
+
+    fn user_config(home: &Path) -> PathBuf {
+        home.join(".claude").join("mcp.json")
+    }
+
+    fn read_json(path: &Path) -> Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn write_json(path: &Path, value: &Value) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, serde_json::to_string_pretty(value).unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_stdio_server_creates_user_config() {
+        let home = tempfile::tempdir().unwrap();
+        let mut args = stdio_args("github", &["npx", "-y", "server-github"]);
+        args.env = vec!["GITHUB_TOKEN=${GITHUB_TOKEN}".to_string()];
+        args.discover_timeout_secs = Some(90);
+
+        let code = add_server(args, OutputFormat::Json, &locations(home.path()))
+            .await
+            .unwrap();
+
+        assert_eq!(code, ExitCode::SUCCESS);
+        assert_eq!(
+            read_json(&user_config(home.path())),
+            json!({"mcpServers": {"github": {
+                "command": "npx",
+                "args": ["-y", "server-github"],
+                "env": {"GITHUB_TOKEN": "${GITHUB_TOKEN}"},
+                "discoverTimeoutSecs": 90
+            }}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_http_server_keeps_other_keys() {
+        let home = tempfile::tempdir().unwrap();
+        let config = user_config(home.path());
+        write_json(
+            &config,
+            &json!({"theme": "dark", "mcpServers": {"local": {"command": "node", "x-note": 1}}}),
+        );
+        let args = AddServerArgs {
+            http: Some("https://api.example.com/mcp".to_string()),
+            headers: vec!["Authorization=Bearer ${API_KEY}".to_string()],
+            ..add_args("remote")
+        };
+
+        add_server(args, OutputFormat::Json, &locations(home.path()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_json(&config),
+            json!({"theme": "dark", "mcpServers": {
+                "local": {"command": "node", "x-note": 1},
+                "remote": {
+                    "type": "http",
+                    "url": "https://api.example.com/mcp",
+                    "headers": {"Authorization": "Bearer ${API_KEY}"}
+                }
+            }})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_existing_server_requires_force() {
+        let home = tempfile::tempdir().unwrap();
+        let config = user_config(home.path());
+        write_json(
+            &config,
+            &json!({"mcpServers": {"github": {"command": "old"}}}),
+        );
+
+        let err = add_server(
+            stdio_args("github", &["new"]),
+            OutputFormat::Json,
+            &locations(home.path()),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+        assert_eq!(config_command(&config, "github"), "old");
+
+        let args = AddServerArgs {
+            force: true,
+            ..stdio_args("github", &["new"])
+        };
+        add_server(args, OutputFormat::Json, &locations(home.path()))
+            .await
+            .unwrap();
+        assert_eq!(config_command(&config, "github"), "new");
+    }
+
+    fn config_command(config: &Path, name: &str) -> String {
+        read_json(config)["mcpServers"][name]["command"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_add_rejects_insecure_entry_before_writing() {
+        let home = tempfile::tempdir().unwrap();
+        let mut args = stdio_args("evil", &["node"]);
+        args.env = vec!["LD_PRELOAD=/tmp/x.so".to_string()];
+
+        let err = add_server(args, OutputFormat::Json, &locations(home.path()))
+            .await
+            .unwrap_err();
+
+        assert!(format!("{err:#}").contains("was not added"), "{err:#}");
+        assert!(!user_config(home.path()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_add_rejects_invalid_name() {
+        let home = tempfile::tempdir().unwrap();
+
+        let result = add_server(
+            stdio_args("bad name", &["node"]),
+            OutputFormat::Json,
+            &locations(home.path()),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(!user_config(home.path()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_add_writes_explicit_config_only() {
+        let home = tempfile::tempdir().unwrap();
+        let explicit = home.path().join("team.json");
+        let locations = locations(home.path()).with_explicit(&explicit);
+
+        add_server(
+            stdio_args("github", &["node"]),
+            OutputFormat::Json,
+            &locations,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(config_command(&explicit, "github"), "node");
+        assert!(!user_config(home.path()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_remove_server_deletes_entry() {
+        let home = tempfile::tempdir().unwrap();
+        let config = user_config(home.path());
+        write_json(
+            &config,
+            &json!({"mcpServers": {"a": {"command": "x"}, "b": {"command": "y"}}}),
+        );
+
+        remove_server("a".to_string(), OutputFormat::Json, &locations(home.path()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read_json(&config),
+            json!({"mcpServers": {"b": {"command": "y"}}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_server_defined_in_other_layer_names_that_file() {
+        let home = tempfile::tempdir().unwrap();
+        write_json(&user_config(home.path()), &json!({"mcpServers": {}}));
+        let project = home.path().join("project");
+        write_json(
+            &project.join(".mcp.json"),
+            &json!({"mcpServers": {"shared": {"command": "x"}}}),
+        );
+        let locations = locations(home.path()).with_project_dir(&project);
+
+        let err = remove_server("shared".to_string(), OutputFormat::Json, &locations)
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains(".mcp.json"), "{err}");
+        assert!(err.contains("--config"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_remove_missing_server_errors() {
+        let home = tempfile::tempdir().unwrap();
+
+        let err = remove_server(
+            "ghost".to_string(),
+            OutputFormat::Json,
+            &locations(home.path()),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(err.to_string().contains("not found"), "{err}");
+        assert!(!user_config(home.path()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_set_timeout_keeps_other_fields() {
+        let home = tempfile::tempdir().unwrap();
+        let config = user_config(home.path());
+        write_json(
+            &config,
+            &json!({"mcpServers": {"github": {
+                "command": "node",
+                "connectTimeoutSecs": 10,
+                "x-owner": "platform"
+            }}}),
+        );
+
+        set_timeout(
+            "github".to_string(),
+            None,
+            Some(120),
+            OutputFormat::Json,
+            &locations(home.path()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            read_json(&config),
+            json!({"mcpServers": {"github": {
+                "command": "node",
+                "connectTimeoutSecs": 10,
+                "x-owner": "platform",
+                "discoverTimeoutSecs": 120
+            }}})
+        );
+    }
+
+    fn stdio_source(command: &str, env: &[&str]) -> ServerSource {
+        ServerSource::Flags {
+            transport: TransportArgs::Stdio {
+                command: command.to_string(),
+                args: vec!["--stdio".to_string()],
+                env: env.iter().map(ToString::to_string).collect(),
+                cwd: None,
+            },
+            connect_timeout_secs: None,
+            discover_timeout_secs: Some(45),
+        }
+    }
+
+    #[tokio::test]
+    #[allow(clippy::literal_string_with_formatting_args)] // `${VAR}` is config syntax here, not a format argument.
+    async fn test_save_as_writes_entry_unexpanded_and_keeps_other_keys() {
+        let home = tempfile::tempdir().unwrap();
+        let config = user_config(home.path());
+        write_json(&config, &json!({"theme": "dark", "mcpServers": {}}));
+        let source = stdio_source("node", &["TOKEN=${TOKEN}"]);
+
+        let save_as =
+            SaveAs::prepare("mine".to_string(), &source, &locations(home.path())).unwrap();
+        save_as.save().await.unwrap();
+
+        assert_eq!(
+            read_json(&config),
+            json!({"theme": "dark", "mcpServers": {"mine": {
+                "command": "node",
+                "args": ["--stdio"],
+                "env": {"TOKEN": "${TOKEN}"},
+                "discoverTimeoutSecs": 45
+            }}})
+        );
+    }
+
+    #[test]
+    fn test_save_as_rejects_taken_name_before_connecting() {
+        let home = tempfile::tempdir().unwrap();
+        write_json(
+            &user_config(home.path()),
+            &json!({"mcpServers": {"mine": {"command": "old"}}}),
+        );
+
+        let err = SaveAs::prepare(
+            "mine".to_string(),
+            &stdio_source("node", &[]),
+            &locations(home.path()),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("already exists"), "{err}");
+    }
+
+    #[test]
+    fn test_save_as_rejects_config_source_and_insecure_entry() {
+        let home = tempfile::tempdir().unwrap();
+        let config_source = ServerSource::Config {
+            name: "github".to_string(),
+        };
+
+        assert!(SaveAs::prepare("x".to_string(), &config_source, &locations(home.path())).is_err());
+        assert!(
+            SaveAs::prepare(
+                "x".to_string(),
+                &stdio_source("node", &["LD_PRELOAD=/tmp/x.so"]),
+                &locations(home.path()),
+            )
+            .is_err()
+        );
+        assert!(!user_config(home.path()).exists());
+    }
+
+    #[tokio::test]
+    async fn test_set_timeout_rejects_out_of_range_value() {
+        let home = tempfile::tempdir().unwrap();
+        let config = user_config(home.path());
+        let original = json!({"mcpServers": {"github": {"command": "node"}}});
+        write_json(&config, &original);
+
+        let result = set_timeout(
+            "github".to_string(),
+            Some(0),
+            None,
+            OutputFormat::Json,
+            &locations(home.path()),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(read_json(&config), original);
+    }
+}
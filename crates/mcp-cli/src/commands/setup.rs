@@ -7,15 +7,16 @@
 
 use anyhow::{Context, Result};
 use mcp_execution_core::cli::{ExitCode, OutputFormat};
+use mcp_execution_core::mcp_config::McpConfigLocations;
 #[cfg(unix)]
 use mcp_execution_core::sanitize_path_for_error;
 use serde::Serialize;
-#[cfg(unix)]
 use std::path::Path;
-use std::path::PathBuf;
 use std::process::Stdio;
 use tokio::process::Command;
 
+use super::common::resolve_paths;
+
 /// Structured result of the environment setup checks.
 ///
 /// Captures every check [`run`] performs so it can be rendered as JSON,
@@ -70,6 +71,9 @@ pub struct SetupResult {
 /// 2. **File permissions**: Makes TypeScript files executable (Unix only)
 /// 3. **Configuration**: Checks if ~/.claude/mcp.json exists
 ///
+/// `~/.claude` is wherever `locations` puts it (`--home`, `MCP_EXECUTION_HOME`,
+/// `CLAUDE_CONFIG_DIR`).
+///
 /// # Examples
 ///
 /// ```bash
@@ -93,18 +97,19 @@ pub struct SetupResult {
 /// - Node.js version is less than 18.0.0
 /// - Home directory cannot be determined
 /// - Output formatting fails (serialization error)
-pub async fn run(output_format: OutputFormat) -> Result<ExitCode> {
+pub async fn run(output_format: OutputFormat, locations: &McpConfigLocations) -> Result<ExitCode> {
     if output_format == OutputFormat::Pretty {
         println!("Checking runtime environment...\n");
     }
 
     let node_version = check_node_version().await?;
 
-    let mcp_config_path = get_mcp_config_path()?;
+    let paths = resolve_paths(locations)?;
+    let mcp_config_path = paths.user_mcp_config();
     let mcp_config_found = mcp_config_path.exists();
 
     let (servers_dir_found, files_made_executable, skipped_entries) =
-        check_files_executable().await?;
+        check_files_executable(&paths.servers_dir()).await?;
 
     let result = SetupResult {
         node_version,
@@ -244,7 +249,7 @@ async fn check_node_version() -> Result<String> {
 
 /// Checks for and makes TypeScript files executable (Unix only).
 ///
-/// Sets executable permissions (0755) on all .ts files in ~/.claude/servers/
+/// Sets executable permissions (0755) on all .ts files in `servers_dir` (~/.claude/servers/)
 /// This allows files to be executed with shebang: `./tool.ts`
 ///
 /// # Platform Support
@@ -255,20 +260,17 @@ async fn check_node_version() -> Result<String> {
 ///
 /// # Errors
 ///
-/// Returns error if:
-/// - Home directory cannot be determined
-/// - Permission changes fail
+/// Returns error if permission changes fail.
 #[cfg(unix)]
-async fn check_files_executable() -> Result<(bool, usize, usize)> {
-    let servers_dir = get_servers_dir()?;
-    check_files_executable_in(&servers_dir).await
+async fn check_files_executable(servers_dir: &Path) -> Result<(bool, usize, usize)> {
+    check_files_executable_in(servers_dir).await
 }
 
 /// Checks for and makes TypeScript files executable (Unix only).
 ///
 /// No-op on non-Unix platforms, since file permissions are not checked there.
 #[cfg(not(unix))]
-async fn check_files_executable() -> Result<(bool, usize, usize)> {
+async fn check_files_executable(_servers_dir: &Path) -> Result<(bool, usize, usize)> {
     Ok((false, 0, 0))
 }
 
@@ -417,18 +419,6 @@ async fn walk_entries(
     Ok(())
 }
 
-/// Gets the path to ~/.claude/mcp.json
-fn get_mcp_config_path() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Failed to get home directory")?;
-    Ok(home.join(".claude").join("mcp.json"))
-}
-
-/// Gets the path to ~/.claude/servers/
-fn get_servers_dir() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Failed to get home directory")?;
-    Ok(home.join(".claude").join("servers"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -450,31 +440,12 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_get_mcp_config_path() {
-        let path = get_mcp_config_path();
-        assert!(path.is_ok());
-
-        let path = path.unwrap();
-        assert!(path.to_string_lossy().contains(".claude"));
-        assert!(path.to_string_lossy().contains("mcp.json"));
-    }
-
-    #[test]
-    fn test_get_servers_dir() {
-        let path = get_servers_dir();
-        assert!(path.is_ok());
-
-        let path = path.unwrap();
-        assert!(path.to_string_lossy().contains(".claude"));
-        assert!(path.to_string_lossy().contains("servers"));
-    }
-
     #[tokio::test]
-    async fn test_check_files_executable_no_panic() {
-        // Should not panic regardless of whether ~/.claude/servers exists.
-        let result = check_files_executable().await;
-        assert!(result.is_ok());
+    async fn test_check_files_executable_missing_dir() {
+        // A home that has never had anything generated into it is not an error.
+        let home = tempfile::tempdir().unwrap();
+        let result = check_files_executable(&home.path().join("servers")).await;
+        assert!(matches!(result, Ok((false, 0, 0))));
     }
 
     #[cfg(unix)]
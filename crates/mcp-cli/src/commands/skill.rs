@@ -1,7 +1,8 @@
 //! Skill command implementation.
 //!
 //! Generates Claude Code instruction skill files (SKILL.md) from progressive loading
-//! TypeScript tools. This command:
+//! TypeScript tools, or the equivalent instructions for another agent host (`--host`). This
+//! command:
 //! 1. Scans generated TypeScript files in `~/.claude/servers/{server}/`
 //! 2. Extracts tool metadata and categories
 //! 3. Generates structured context for skill creation
@@ -10,14 +11,20 @@
 use anyhow::{Context, Result, bail};
 use mcp_execution_core::Error as CoreError;
 use mcp_execution_core::cli::{ExitCode, OutputFormat};
+use mcp_execution_core::mcp_config::McpConfigLocations;
+use mcp_execution_core::paths::Paths;
+use mcp_execution_skill::host::{InstructionsError, write_instructions};
 use mcp_execution_skill::{
-    GenerateSkillResult, ParsedToolFile, ScanResult, build_skill_context, render_skill_md,
-    scan_tools_directory, validate_server_id, validate_skill_name,
+    GenerateSkillResult, HostProfile, InstructionsFormat, ParsedToolFile, ScanResult, SkillLayout,
+    build_skill_context_for, extract_skill_metadata, render_skill_md, scan_tools_directory,
+    validate_server_id, validate_skill_name,
 };
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
+use super::common::resolve_paths;
+
 /// Output of a successful `skill` command invocation.
 #[derive(Debug, Serialize)]
 struct SkillWriteResult {
@@ -30,12 +37,6 @@ struct SkillWriteResult {
     warnings: Vec<String>,
 }
 
-/// Default base directory for generated servers.
-const DEFAULT_SERVERS_DIR: &str = ".claude/servers";
-
-/// Default base directory for skills.
-const DEFAULT_SKILLS_DIR: &str = ".claude/skills";
-
 /// Runs the skill command.
 ///
 /// Scans generated progressive loading TypeScript files and prepares context
@@ -59,6 +60,12 @@ const DEFAULT_SKILLS_DIR: &str = ".claude/skills";
 /// * `hints` - Use case hints for skill generation
 /// * `overwrite` - Whether to overwrite existing SKILL.md file
 /// * `output_format` - Output format (json, text, pretty)
+/// * `locations` - Where `~/.claude` is; supplies the default servers and skills directories
+/// * `host` - Agent host whose instruction artifact is written; for hosts other than Claude
+///   Code the default output is the host's own file (`.cursor/rules/{server}.mdc`, `AGENTS.md`)
+///   and an existing file is merged into rather than replaced
+/// * `target` - Whether the skill lives under the home directory or is committed with a
+///   project; see [`SkillTarget`]
 ///
 /// # Errors
 ///
@@ -85,6 +92,9 @@ const DEFAULT_SKILLS_DIR: &str = ".claude/skills";
 /// ```no_run
 /// use mcp_execution_cli::commands::skill;
 /// use mcp_execution_core::cli::OutputFormat;
+/// use mcp_execution_core::mcp_config::McpConfigLocations;
+/// use mcp_execution_skill::host::ClaudeCode;
+/// use skill::SkillTarget;
 ///
 /// # async fn example() -> anyhow::Result<()> {
 /// // Generate skill for GitHub server
@@ -95,13 +105,17 @@ const DEFAULT_SKILLS_DIR: &str = ".claude/skills";
 ///     None,
 ///     vec![],
 ///     false,
-///     OutputFormat::Json
+///     OutputFormat::Json,
+///     &McpConfigLocations::discover(None),
+///     &ClaudeCode,
+///     SkillTarget::Home,
 /// ).await?;
 /// # Ok(())
 /// # }
 /// ```
 // One argument per CLI flag; clap already destructures flags for us, and grouping them into a
 // struct would only benefit this function, not caller ergonomics.
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     server: String,
     servers_dir: Option<PathBuf>,
@@ -110,6 +124,9 @@ pub async fn run(
     hints: Vec<String>,
     overwrite: bool,
     output_format: OutputFormat,
+    locations: &McpConfigLocations,
+    host: &dyn HostProfile,
+    target: SkillTarget,
 ) -> Result<ExitCode> {
     debug!("Generating skill for server: {}", server);
     debug!("Servers directory: {:?}", servers_dir);
@@ -127,11 +144,41 @@ pub async fn run(
         .map_err(|e| CoreError::InvalidArgument(format!("Invalid server ID: {e}")))?;
     info!("Server ID validated: {}", server);
 
-    let tool_dir = resolve_tool_dir(&server, servers_dir.as_deref())?;
+    let paths = resolve_paths(locations)?;
+    let tool_dir = resolve_tool_dir(&server, servers_dir.as_deref(), paths)?;
 
     let scan_result = scan_server_tools(&tool_dir, &server).await?;
 
+    let project_dir = locations.project_dir().unwrap_or_else(|| Path::new("."));
+    let (layout, skills_dir, default_output_path) = match &target {
+        SkillTarget::Home => (
+            SkillLayout::for_host(
+                host,
+                paths,
+                &resolve_servers_dir(servers_dir.as_deref(), paths),
+                project_dir,
+                &server,
+            ),
+            paths.skills_dir(),
+            host.instructions_path(paths, project_dir, &server),
+        ),
+        SkillTarget::Project(root) => {
+            let default_output_path = host.project_instructions_path(root, &server);
+            // A `SKILL.md` host's path is `<skills dir>/<server>/SKILL.md`.
+            let skills_dir = default_output_path
+                .parent()
+                .and_then(Path::parent)
+                .unwrap_or(root)
+                .to_path_buf();
+            (
+                SkillLayout::for_project(host, root, &server),
+                skills_dir,
+                default_output_path,
+            )
+        }
+    };
     let (context, custom_output_path) = prepare_skill_context(
+        &layout,
         &server,
         &scan_result.tools,
         hints,
@@ -139,6 +186,76 @@ pub async fn run(
         output_path,
     )?;
 
+    let format = host.instructions_format();
+    let (output_path, bytes_written) = if format == InstructionsFormat::SkillMd {
+        write_skill_file(
+            &context,
+            custom_output_path,
+            &skills_dir,
+            &server,
+            overwrite,
+        )
+        .await?
+    } else {
+        let output_path = custom_output_path.unwrap_or(default_output_path);
+        let bytes_written =
+            write_host_instructions(format, &context, &output_path, &server, overwrite).await?;
+        (output_path, bytes_written)
+    };
+
+    info!(
+        "{} instructions written to {} ({} bytes, {} tools)",
+        host.name(),
+        output_path.display(),
+        bytes_written,
+        context.tool_count,
+    );
+
+    // Two independent, additive warning sources (issue #473): scan-time drift
+    // (`scan_result.warnings`) and `use_case_hints` sanitization warnings already carried on
+    // `context.warnings` (populated by `build_skill_context`, see its doc comment). Neither
+    // overwrites the other.
+    let mut warnings = scan_result.warnings;
+    warnings.extend(context.warnings.iter().cloned());
+
+    let result = SkillWriteResult {
+        success: true,
+        output_path: output_path.display().to_string(),
+        bytes_written,
+        tool_count: context.tool_count,
+        warnings,
+    };
+
+    crate::formatters::emit(&result, output_format, ExitCode::SUCCESS)
+}
+
+/// Where `skill` reads bindings from and writes instructions to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SkillTarget {
+    /// The host's directories under the user's home, e.g. `~/.claude/skills`.
+    #[default]
+    Home,
+    /// `--project`: the host's directories inside the project at this root. The skill refers
+    /// to its bindings relative to the root, so it can be committed with the project.
+    Project(PathBuf),
+}
+
+/// Renders `context` as `SKILL.md` and writes it to `custom_output_path`, or to the confined
+/// default under `skills_dir`. Returns the path written and its size.
+///
+/// See [`run`]'s `# Side Effects` for when the default `{server}/` directory is created.
+///
+/// # Errors
+///
+/// Returns an error if the file exists without `overwrite`, or resolving, rendering, or writing
+/// fails.
+async fn write_skill_file(
+    context: &GenerateSkillResult,
+    custom_output_path: Option<PathBuf>,
+    skills_dir: &Path,
+    server: &str,
+    overwrite: bool,
+) -> Result<(PathBuf, usize)> {
     // See this function's own doc comment (`# Side Effects`) for why the default branch below
     // creates `{server}/` this early, and why the custom branch's own directory creation is
     // deferred past the `--overwrite` gate instead of happening here.
@@ -158,8 +275,7 @@ pub async fn run(
         // as a symlink ahead of time (issue #501). See `resolve_default_output_path`'s own doc
         // comment for why this leaves the terminal `SKILL.md` component's pre-existing-symlink
         // case untouched.
-        let skills_dir = resolve_skills_dir()?;
-        resolve_default_output_path(&skills_dir, &server).await?
+        resolve_default_output_path(skills_dir, server).await?
     };
 
     // Check if output file exists and overwrite flag
@@ -172,7 +288,7 @@ pub async fn run(
     }
 
     // Step 7: Render SKILL.md and write atomically.
-    let rendered = render_skill_md(&context).context("failed to render SKILL.md template")?;
+    let rendered = render_skill_md(context).context("failed to render SKILL.md template")?;
 
     // A custom `--output` path's parent directory is created only now, after the `--overwrite`
     // gate and rendering above - matching this branch's pre-#501 behavior, where directory
@@ -188,41 +304,48 @@ pub async fn run(
 
     write_skill_md(&rendered, &output_path).await?;
 
-    let bytes_written = rendered.len();
-    info!(
-        "SKILL.md written to {} ({} bytes, {} tools)",
-        output_path.display(),
-        bytes_written,
-        context.tool_count,
-    );
-
-    // Two independent, additive warning sources (issue #473): scan-time drift
-    // (`scan_result.warnings`) and `use_case_hints` sanitization warnings already carried on
-    // `context.warnings` (populated by `build_skill_context`, see its doc comment). Neither
-    // overwrites the other.
-    let mut warnings = scan_result.warnings;
-    warnings.extend(context.warnings.iter().cloned());
-
-    let result = SkillWriteResult {
-        success: true,
-        output_path: output_path.display().to_string(),
-        bytes_written,
-        tool_count: context.tool_count,
-        warnings,
-    };
+    Ok((output_path, rendered.len()))
+}
 
-    crate::formatters::emit(&result, output_format, ExitCode::SUCCESS)
+/// Renders `context` as `SKILL.md`, adapts it to `format`, and merges it into `output_path`.
+/// Returns the size of the rendered instructions.
+///
+/// # Errors
+///
+/// Returns an error if instructions for `server` already exist without `overwrite`, or
+/// rendering or writing fails.
+async fn write_host_instructions(
+    format: InstructionsFormat,
+    context: &GenerateSkillResult,
+    output_path: &Path,
+    server: &str,
+    overwrite: bool,
+) -> Result<usize> {
+    let skill_md = render_skill_md(context).context("failed to render SKILL.md template")?;
+    let metadata =
+        extract_skill_metadata(&skill_md).context("rendered SKILL.md has invalid frontmatter")?;
+    let rendered = format.render(&skill_md, &metadata, server);
+
+    match write_instructions(format, output_path, &rendered, server, overwrite).await {
+        Ok(_) => Ok(rendered.len()),
+        Err(InstructionsError::AlreadyExists { path, .. }) => bail!(
+            "Instructions for '{server}' already exist in {path}\n\
+             Use --overwrite to replace them."
+        ),
+        Err(e) => Err(e.into()),
+    }
 }
 
-/// Resolves and validates the server's tool directory under `servers_dir` (or its default).
+/// Resolves and validates the server's tool directory under `servers_dir` (or `paths`'
+/// servers directory).
 ///
 /// # Errors
 ///
-/// Returns an error if the home directory cannot be determined, the resolved path escapes
-/// its base via a symlink, or the server directory does not exist.
-fn resolve_tool_dir(server: &str, servers_dir: Option<&Path>) -> Result<PathBuf> {
+/// Returns an error if the resolved path escapes its base via a symlink, or the server
+/// directory does not exist.
+fn resolve_tool_dir(server: &str, servers_dir: Option<&Path>, paths: &Paths) -> Result<PathBuf> {
     // Step 2: Resolve servers directory
-    let servers_base = resolve_servers_dir(servers_dir)?;
+    let servers_base = resolve_servers_dir(servers_dir, paths);
     debug!("Servers base directory: {}", servers_base.display());
 
     // Step 3: Build and validate server path
@@ -294,6 +417,7 @@ async fn scan_server_tools(tool_dir: &Path, server: &str) -> Result<ScanResult>
 ///
 /// Returns an error if a custom `output_path` fails traversal validation.
 fn prepare_skill_context(
+    layout: &SkillLayout,
     server: &str,
     tools: &[ParsedToolFile],
     hints: Vec<String>,
@@ -313,7 +437,7 @@ fn prepare_skill_context(
             .map_err(|e| CoreError::InvalidArgument(format!("Invalid skill name: {e}")))?;
     }
 
-    let context = build_skill_context(server, tools, hints_ref.as_deref(), skill_name);
+    let context = build_skill_context_for(layout, server, tools, hints_ref.as_deref(), skill_name);
 
     if let Some(path) = &output_path {
         validate_output_path(path)?;
@@ -389,47 +513,29 @@ async fn resolve_default_output_path(skills_dir: &Path, server: &str) -> Result<
     Ok(segment_dir.join("SKILL.md"))
 }
 
-/// Resolve servers directory from provided path or default.
+/// Resolve servers directory from optional custom path or `paths`' default.
+///
+/// A leading `~/` in a custom path is expanded against `paths`' home, so `--home` applies to
+/// it too.
 ///
 /// # Arguments
 ///
 /// * `servers_dir` - Optional custom servers directory
+/// * `paths` - Home and `.claude` directories
 ///
 /// # Returns
 ///
 /// Resolved path to servers directory.
-///
-/// # Errors
-///
-/// Returns error if home directory cannot be determined.
-fn resolve_servers_dir(servers_dir: Option<&Path>) -> Result<PathBuf> {
-    if let Some(dir) = servers_dir {
-        // Use provided path, expand ~ if needed
-        if let Some(stripped) = dir.to_str().and_then(|s| s.strip_prefix("~/")) {
-            let home = dirs::home_dir().context("Could not determine home directory")?;
-            Ok(home.join(stripped))
-        } else {
-            Ok(dir.to_path_buf())
-        }
-    } else {
-        // Use default: ~/.claude/servers
-        let home = dirs::home_dir().context("Could not determine home directory")?;
-        Ok(home.join(DEFAULT_SERVERS_DIR))
-    }
-}
-
-/// Resolve skills directory (default: ~/.claude/skills).
-///
-/// # Returns
-///
-/// Resolved path to skills directory.
-///
-/// # Errors
-///
-/// Returns error if home directory cannot be determined.
-fn resolve_skills_dir() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Could not determine home directory")?;
-    Ok(home.join(DEFAULT_SKILLS_DIR))
+fn resolve_servers_dir(servers_dir: Option<&Path>, paths: &Paths) -> PathBuf {
+    // Default: ~/.claude/servers; a provided path has a leading ~ expanded
+    servers_dir.map_or_else(
+        || paths.servers_dir(),
+        |dir| {
+            dir.to_str()
+                .and_then(|s| s.strip_prefix("~/"))
+                .map_or_else(|| dir.to_path_buf(), |stripped| paths.home().join(stripped))
+        },
+    )
 }
 
 /// Validate path security to prevent path traversal attacks.
@@ -541,6 +647,7 @@ mod tests {
     };
     use mcp_execution_core::provenance::GenerationProvenance;
     use mcp_execution_core::{ServerConfig, ServerId, ToolName};
+    use mcp_execution_skill::host::{ClaudeCode, Generic};
     use tempfile::TempDir;
 
     fn test_provenance() -> GenerationProvenance {
@@ -568,6 +675,8 @@ mod tests {
                 category: Some("testing".to_string()),
                 keywords: vec!["test".to_string()],
                 description: Some(format!("Test tool: {tool_name}")),
+                short_description: None,
+                source_digest: None,
                 parameters: vec![ParameterMetadata {
                     name: "input".to_string(),
                     typescript_type: "string".to_string(),
@@ -583,31 +692,29 @@ mod tests {
         std::fs::write(server_dir.join(format!("{tool_name}.ts")), "export {}").unwrap();
     }
 
+    fn home_locations(home: &Path) -> McpConfigLocations {
+        McpConfigLocations::new().with_home_dir(home)
+    }
+
     #[test]
     fn test_resolve_servers_dir_default() {
-        let result = resolve_servers_dir(None);
-        assert!(result.is_ok());
-        let path = result.unwrap();
-        assert!(path.to_string_lossy().contains(".claude/servers"));
+        let path = resolve_servers_dir(None, &Paths::new("/home/u"));
+        assert_eq!(path, Path::new("/home/u/.claude/servers"));
     }
 
     #[test]
     fn test_resolve_servers_dir_custom() {
         let custom = PathBuf::from("/custom/servers");
-        let result = resolve_servers_dir(Some(&custom));
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), custom);
+        let path = resolve_servers_dir(Some(&custom), &Paths::new("/home/u"));
+        assert_eq!(path, custom);
     }
 
     #[test]
     fn test_resolve_servers_dir_tilde() {
+        // `~` expands against the resolved home, so `--home` applies to it too.
         let custom = PathBuf::from("~/custom/servers");
-        let result = resolve_servers_dir(Some(&custom));
-        assert!(result.is_ok());
-        let path = result.unwrap();
-        // Should expand ~ to home directory
-        assert!(!path.to_string_lossy().starts_with('~'));
-        assert!(path.to_string_lossy().contains("custom/servers"));
+        let path = resolve_servers_dir(Some(&custom), &Paths::new("/sandbox"));
+        assert_eq!(path, Path::new("/sandbox/custom/servers"));
     }
 
     #[test]
@@ -651,12 +758,117 @@ mod tests {
         assert!(result.is_ok());
     }
 
-    #[test]
-    fn test_resolve_skills_dir() {
-        let result = resolve_skills_dir();
-        assert!(result.is_ok());
-        let path = result.unwrap();
-        assert!(path.to_string_lossy().contains(".claude/skills"));
+    #[tokio::test]
+    async fn test_run_default_paths_follow_home() {
+        let home = TempDir::new().unwrap();
+        let server_dir = home.path().join(".claude").join("servers").join("github");
+        std::fs::create_dir_all(&server_dir).unwrap();
+        write_meta_sidecar(&server_dir, "github", "create_issue");
+
+        let result = run(
+            "github".to_string(),
+            None,
+            None,
+            None,
+            vec![],
+            false,
+            OutputFormat::Json,
+            &home_locations(home.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
+        )
+        .await;
+
+        assert!(result.is_ok(), "run failed: {:?}", result.err());
+        let skill_md = home
+            .path()
+            .join(".claude")
+            .join("skills")
+            .join("github")
+            .join("SKILL.md");
+        assert!(skill_md.exists(), "SKILL.md must land under the given home");
+    }
+
+    #[tokio::test]
+    async fn test_run_generic_host_writes_agents_section() {
+        let home = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        let locations = Generic.configure(
+            McpConfigLocations::new()
+                .with_home_dir(home.path())
+                .with_project_dir(project.path()),
+        );
+        let server_dir = home
+            .path()
+            .join(".mcp-execution")
+            .join("servers")
+            .join("github");
+        std::fs::create_dir_all(&server_dir).unwrap();
+        write_meta_sidecar(&server_dir, "github", "create_issue");
+        let agents_md = project.path().join("AGENTS.md");
+        std::fs::write(&agents_md, "# Project\n").unwrap();
+
+        let generate = |overwrite| {
+            run(
+                "github".to_string(),
+                None,
+                None,
+                None,
+                vec![],
+                overwrite,
+                OutputFormat::Json,
+                &locations,
+                &Generic,
+                SkillTarget::Home,
+            )
+        };
+        generate(false).await.unwrap();
+
+        let content = std::fs::read_to_string(&agents_md).unwrap();
+        assert!(content.starts_with("# Project\n\n<!-- mcp-execution:begin github -->\n"));
+        assert!(content.contains("## github-progressive"));
+        assert!(content.contains("ls ~/.mcp-execution/servers/github/"));
+        assert!(!content.contains(".claude"));
+
+        let err = generate(false).await.unwrap_err();
+        assert!(err.to_string().contains("--overwrite"), "{err}");
+        generate(true).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&agents_md).unwrap(), content);
+    }
+
+    #[tokio::test]
+    async fn test_run_project_target_writes_repo_relative_skill() {
+        let home = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        let locations = McpConfigLocations::new()
+            .with_home_dir(home.path())
+            .with_project_dir(project.path());
+        let bindings_dir = ClaudeCode.project_bindings_dir(project.path());
+        let server_dir = bindings_dir.join("github");
+        std::fs::create_dir_all(&server_dir).unwrap();
+        write_meta_sidecar(&server_dir, "github", "create_issue");
+
+        run(
+            "github".to_string(),
+            Some(bindings_dir),
+            None,
+            None,
+            vec![],
+            false,
+            OutputFormat::Json,
+            &locations,
+            &ClaudeCode,
+            SkillTarget::Project(project.path().to_path_buf()),
+        )
+        .await
+        .unwrap();
+
+        let skill_md = project.path().join(".claude/skills/github/SKILL.md");
+        let content = std::fs::read_to_string(skill_md).unwrap();
+        assert!(content.contains("ls .claude/servers/github/"), "{content}");
+        assert!(!content.contains('~'), "{content}");
+        assert!(!content.contains(&*project.path().to_string_lossy()));
+        assert!(!content.contains(&*home.path().to_string_lossy()));
     }
 
     #[test]
@@ -714,6 +926,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -731,6 +946,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(Path::new("/nonexistent")),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -757,6 +975,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -785,6 +1006,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -814,6 +1038,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -851,6 +1078,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -883,6 +1113,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -941,6 +1174,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -969,8 +1205,15 @@ mod tests {
     fn test_prepare_skill_context_with_custom_skill_name_reflects_it_in_generation_prompt() {
         let tools = vec![];
 
-        let (context, _output_path) =
-            prepare_skill_context("github", &tools, vec![], Some("github-advanced"), None).unwrap();
+        let (context, _output_path) = prepare_skill_context(
+            &SkillLayout::claude("github"),
+            "github",
+            &tools,
+            vec![],
+            Some("github-advanced"),
+            None,
+        )
+        .unwrap();
 
         assert_eq!(context.skill_name, "github-advanced");
         assert!(
@@ -991,9 +1234,15 @@ mod tests {
         let tools = vec![];
         let custom_output = PathBuf::from("/tmp/custom/SKILL.md");
 
-        let (context, resolved_output_path) =
-            prepare_skill_context("github", &tools, vec![], None, Some(custom_output.clone()))
-                .unwrap();
+        let (context, resolved_output_path) = prepare_skill_context(
+            &SkillLayout::claude("github"),
+            "github",
+            &tools,
+            vec![],
+            None,
+            Some(custom_output.clone()),
+        )
+        .unwrap();
 
         assert_eq!(resolved_output_path, Some(custom_output));
         assert_eq!(
@@ -1017,8 +1266,15 @@ mod tests {
             .map(|i| format!("hint-{i}"))
             .collect();
 
-        let (context, _output_path) =
-            prepare_skill_context("github", &tools, hints, None, None).unwrap();
+        let (context, _output_path) = prepare_skill_context(
+            &SkillLayout::claude("github"),
+            "github",
+            &tools,
+            hints,
+            None,
+            None,
+        )
+        .unwrap();
 
         assert_eq!(context.warnings.len(), 1, "{:?}", context.warnings);
         assert!(
@@ -1049,6 +1305,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -1081,6 +1340,9 @@ mod tests {
             vec!["code review".to_string(), "CI/CD".to_string()],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -1119,6 +1381,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -1154,6 +1419,9 @@ mod tests {
             vec![],
             false, // no overwrite
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -1180,6 +1448,9 @@ mod tests {
             vec![],
             true, // overwrite
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -1207,6 +1478,9 @@ mod tests {
                 vec![],
                 false,
                 format,
+                &home_locations(temp.path()),
+                &ClaudeCode,
+                SkillTarget::Home,
             )
             .await;
 
@@ -1241,6 +1515,8 @@ mod tests {
                     category: Some("issues".to_string()),
                     keywords: vec!["create".to_string()],
                     description: Some("Create an issue".to_string()),
+                    short_description: None,
+                    source_digest: None,
                     parameters: vec![ParameterMetadata {
                         name: "title".to_string(),
                         typescript_type: "string".to_string(),
@@ -1254,6 +1530,8 @@ mod tests {
                     category: Some("repos".to_string()),
                     keywords: vec!["list".to_string()],
                     description: Some("List repos".to_string()),
+                    short_description: None,
+                    source_digest: None,
                     parameters: vec![],
                 },
             ],
@@ -1280,6 +1558,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -1314,6 +1595,9 @@ mod tests {
             vec![],
             false,
             OutputFormat::Json,
+            &home_locations(temp.path()),
+            &ClaudeCode,
+            SkillTarget::Home,
         )
         .await;
 
@@ -25,6 +25,8 @@ use anyhow::Result;
 use clap::Parser;
 use mcp_execution_cli::cli::Cli;
 use mcp_execution_cli::runner;
+use mcp_execution_core::mcp_config::McpConfigLocations;
+use mcp_execution_core::paths::Paths;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -35,7 +37,12 @@ async fn main() -> Result<()> {
     // `--format` is typed as `OutputFormat` directly (a `PossibleValuesParser`-based value
     // parser, see `cli.rs`), so an invalid value (e.g. `--format xml`) is already rejected by
     // clap itself before this point.
-    let exit_code = runner::execute_command(cli.command, cli.format).await?;
+    let mut locations = McpConfigLocations::discover(cli.config);
+    if let Some(paths) = cli.home.and_then(|home| Paths::discover(Some(home))) {
+        locations = locations.with_paths(paths);
+    }
+    let locations = cli.host.configure(locations);
+    let exit_code = runner::execute_command(cli.command, cli.format, &locations, cli.host).await?;
 
     std::process::exit(exit_code.as_i32());
 }
@@ -3,16 +3,22 @@
 //! Contains the main command execution loop and logging initialization.
 
 use std::io::{self, Write};
+use std::path::PathBuf;
 
 use anyhow::Result;
 use mcp_execution_core::Error as CoreError;
 use mcp_execution_core::cli::{ExitCode, LOG_FORMAT_ENV_VAR, LogFormat, OutputFormat};
+use mcp_execution_core::mcp_config::McpConfigLocations;
 use mcp_execution_files::FilesError;
+use mcp_execution_skill::HostProfile;
 use tracing_subscriber::{EnvFilter, Layer as _, layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::cli::Commands;
 use crate::commands;
-use crate::commands::common::ServerSource;
+use crate::commands::common::{ServerSource, resolve_project_root};
+use crate::commands::generate::{FilterSelection, GenerateMode, OutputDir};
+use crate::commands::server::SaveAs;
+use crate::commands::skill::SkillTarget;
 use crate::formatters::escape_error_text;
 
 /// [`Write`] wrapper that redacts embedded secrets out of each buffer before forwarding it to the
@@ -193,6 +199,9 @@ pub fn init_logging(verbose: bool, log_format: Option<LogFormat>) -> Result<()>
 ///
 /// * `command` - The parsed CLI command to execute
 /// * `output_format` - Output format preference (JSON, text, or pretty)
+/// * `locations` - Config files and home directories, already [configured](HostProfile::configure)
+///   for `host`
+/// * `host` - Agent host `skill` writes instructions for
 ///
 /// # Errors
 ///
@@ -206,20 +215,31 @@ pub fn init_logging(verbose: bool, log_format: Option<LogFormat>) -> Result<()>
 /// use mcp_execution_cli::cli::Commands;
 /// use mcp_execution_cli::runner;
 /// use mcp_execution_core::cli::OutputFormat;
+/// use mcp_execution_core::mcp_config::McpConfigLocations;
+/// use mcp_execution_skill::host::ClaudeCode;
 ///
 /// # async fn example() -> anyhow::Result<()> {
 /// let exit_code = runner::execute_command(
 ///     Commands::Setup,
 ///     OutputFormat::Pretty,
+///     &McpConfigLocations::discover(None),
+///     &ClaudeCode,
 /// ).await?;
 /// # Ok(())
 /// # }
 /// ```
-pub async fn execute_command(command: Commands, output_format: OutputFormat) -> Result<ExitCode> {
-    Ok(match dispatch(command, output_format).await {
-        Ok(code) => code,
-        Err(err) => report_and_classify(&err),
-    })
+pub async fn execute_command(
+    command: Commands,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+    host: &dyn HostProfile,
+) -> Result<ExitCode> {
+    Ok(
+        match dispatch(command, output_format, locations, host).await {
+            Ok(code) => code,
+            Err(err) => report_and_classify(&err),
+        },
+    )
 }
 
 /// Routes `command` to its handler and returns the handler's result unclassified.
@@ -227,11 +247,23 @@ pub async fn execute_command(command: Commands, output_format: OutputFormat) ->
 /// # Errors
 ///
 /// Returns whatever error the dispatched command handler produces.
-async fn dispatch(command: Commands, output_format: OutputFormat) -> Result<ExitCode> {
+async fn dispatch(
+    command: Commands,
+    output_format: OutputFormat,
+    locations: &McpConfigLocations,
+    host: &dyn HostProfile,
+) -> Result<ExitCode> {
     match command {
-        Commands::Introspect { flags, detailed } => {
+        Commands::Introspect {
+            flags,
+            detailed,
+            save_as,
+        } => {
             let source = ServerSource::try_from(flags)?;
-            commands::introspect::run(source, detailed, output_format).await
+            let save_as = prepare_save_as(save_as, &source, locations)?;
+            let code =
+                commands::introspect::run(source, detailed, output_format, locations).await?;
+            finish_save_as(save_as, code).await
         }
         Commands::Skill {
             server,
@@ -240,7 +272,9 @@ async fn dispatch(command: Commands, output_format: OutputFormat) -> Result<Exit
             skill_name,
             hints,
             overwrite,
+            project,
         } => {
+            let (servers_dir, target) = skill_target(project, servers_dir, locations, host)?;
             commands::skill::run(
                 server,
                 servers_dir,
@@ -249,6 +283,9 @@ async fn dispatch(command: Commands, output_format: OutputFormat) -> Result<Exit
                 hints,
                 overwrite,
                 output_format,
+                locations,
+                host,
+                target,
             )
             .await
         }
@@ -256,13 +293,51 @@ async fn dispatch(command: Commands, output_format: OutputFormat) -> Result<Exit
             flags,
             name,
             progressive_output,
+            project,
             dry_run,
+            diff,
+            check,
+            watch,
+            watch_paths,
+            include,
+            exclude,
+            no_filter,
+            save_as,
         } => {
             let source = ServerSource::try_from(flags)?;
-            commands::generate::run(source, name, progressive_output, dry_run, output_format).await
+            let filter = FilterSelection::from_flags(include, exclude, no_filter)?;
+            let save_as = prepare_save_as(save_as, &source, locations)?;
+            let (locations, output_dir) =
+                generate_target(project, progressive_output, locations, host)?;
+            let locations = &locations;
+            if watch {
+                return commands::generate::run_watch(
+                    source,
+                    name,
+                    output_dir,
+                    watch_paths,
+                    filter,
+                    output_format,
+                    locations,
+                )
+                .await;
+            }
+            let code = commands::generate::run(
+                source,
+                name,
+                output_dir,
+                GenerateMode::from_flags(dry_run, diff, check),
+                filter,
+                output_format,
+                locations,
+            )
+            .await?;
+            finish_save_as(save_as, code).await
+        }
+        Commands::Server { action } => {
+            commands::server::run(action, output_format, locations).await
         }
-        Commands::Server { action } => commands::server::run(action, output_format).await,
-        Commands::Setup => commands::setup::run(output_format).await,
+        Commands::Setup => commands::setup::run(output_format, locations).await,
         Commands::Completions { shell } => run_completions(shell).await,
     }
 }
@@ -276,6 +351,64 @@ async fn run_completions(shell: clap_complete::Shell) -> Result<ExitCode> {
     commands::completions::run(shell, &mut cmd).await
 }
 
+/// Checks a `--save-as` request before the server is contacted, so a taken name or an entry
+/// that could never be saved fails fast.
+fn prepare_save_as(
+    name: Option<String>,
+    source: &ServerSource,
+    locations: &McpConfigLocations,
+) -> Result<Option<SaveAs>> {
+    name.map(|name| SaveAs::prepare(name, source, locations))
+        .transpose()
+}
+
+/// Resolves `skill`'s servers directory and target, pointing both into the project for
+/// `--project`.
+fn skill_target(
+    project: bool,
+    servers_dir: Option<PathBuf>,
+    locations: &McpConfigLocations,
+    host: &dyn HostProfile,
+) -> Result<(Option<PathBuf>, SkillTarget)> {
+    if !project {
+        return Ok((servers_dir, SkillTarget::Home));
+    }
+    let root = resolve_project_root(locations)?;
+    Ok((
+        Some(host.project_bindings_dir(&root)),
+        SkillTarget::Project(root),
+    ))
+}
+
+/// Resolves `generate`'s output directory. For `--project`, the returned locations also look
+/// for the project's `.mcp.json` from its root rather than the current directory.
+fn generate_target(
+    project: bool,
+    progressive_output: Option<PathBuf>,
+    locations: &McpConfigLocations,
+    host: &dyn HostProfile,
+) -> Result<(McpConfigLocations, OutputDir)> {
+    if !project {
+        return Ok((locations.clone(), OutputDir::from(progressive_output)));
+    }
+    let root = resolve_project_root(locations)?;
+    let bindings_dir = host.project_bindings_dir(&root);
+    Ok((
+        locations.clone().with_project_dir(&root),
+        OutputDir::Project { root, bindings_dir },
+    ))
+}
+
+/// Saves the `--save-as` entry if the command succeeded, passing its exit code through.
+async fn finish_save_as(save_as: Option<SaveAs>, code: ExitCode) -> Result<ExitCode> {
+    if code == ExitCode::SUCCESS
+        && let Some(save_as) = save_as
+    {
+        save_as.save().await?;
+    }
+    Ok(code)
+}
+
 /// Prints `err` to stderr, then classifies it into a semantic [`ExitCode`].
 ///
 /// Structurally matches anyhow's default `main`-error format (a summary line, then a numbered
@@ -436,7 +569,8 @@ fn classify_exit_code(error: &anyhow::Error) -> ExitCode {
 /// wrapped cause's own exit-code classification (e.g. a wrapped
 /// [`CoreError::ResourceLimitExceeded`] should still report [`ExitCode::SERVER_ERROR`], not the
 /// generic code every other `ScriptGenerationError` gets). Recursing into `source` when it
-/// downcasts to another `CoreError` preserves that.
+/// downcasts to another `CoreError` preserves that, and [`CoreError::ServerStderr`] is
+/// classified by the failure it wraps the same way.
 fn classify_core_error(core_error: &CoreError) -> ExitCode {
     match core_error {
         CoreError::Timeout { .. } => ExitCode::TIMEOUT,
@@ -459,6 +593,8 @@ fn classify_core_error(core_error: &CoreError) -> ExitCode {
             .as_deref()
             .and_then(|source| source.downcast_ref::<CoreError>())
             .map_or(ExitCode::ERROR, classify_core_error),
+        // The captured stderr only explains the failure; the failure itself decides the code.
+        CoreError::ServerStderr { source, .. } => classify_core_error(source),
     }
 }
 
@@ -491,6 +627,18 @@ mod tests {
         assert_eq!(classify_exit_code(&err), ExitCode::SERVER_ERROR);
     }
 
+    #[test]
+    fn test_classify_exit_code_server_stderr_uses_wrapped_failure() {
+        let err = wrap(CoreError::ServerStderr {
+            stderr: "missing GITHUB_TOKEN".to_string(),
+            source: Box::new(CoreError::Timeout {
+                operation: "connect".to_string(),
+                duration_secs: 30,
+            }),
+        });
+        assert_eq!(classify_exit_code(&err), ExitCode::TIMEOUT);
+    }
+
     #[test]
     fn test_classify_exit_code_resource_limit_exceeded() {
         let err = wrap(CoreError::ResourceLimitExceeded {
@@ -622,12 +770,43 @@ mod tests {
             "introspect",
             "nonexistent-server-for-exit-code-test",
         ]);
-        let result = execute_command(cli.command, OutputFormat::Json).await;
+        let result = execute_command(
+            cli.command,
+            OutputFormat::Json,
+            &McpConfigLocations::new(),
+            &mcp_execution_skill::host::ClaudeCode,
+        )
+        .await;
 
         let exit_code = result.expect("execute_command must not propagate Err");
         assert_eq!(exit_code, ExitCode::SERVER_ERROR);
     }
 
+    #[tokio::test]
+    async fn test_execute_command_save_as_writes_nothing_when_introspection_fails() {
+        use clap::Parser as _;
+        let home = tempfile::tempdir().unwrap();
+        let cli = crate::cli::Cli::parse_from([
+            "mcp-execution-cli",
+            "introspect",
+            "nonexistent-server-for-exit-code-test",
+            "--save-as",
+            "saved",
+        ]);
+
+        let exit_code = execute_command(
+            cli.command,
+            OutputFormat::Json,
+            &McpConfigLocations::new().with_home_dir(home.path()),
+            &mcp_execution_skill::host::ClaudeCode,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(exit_code, ExitCode::SERVER_ERROR);
+        assert!(!home.path().join(".claude").join("mcp.json").exists());
+    }
+
     #[test]
     fn test_report_and_classify_prints_and_classifies() {
         // Regression test for #195/S2: `main` routes pre-dispatch failures
@@ -0,0 +1,180 @@
+//! Reuse of a previous generation's categorizations.
+//!
+//! Each tool in `_meta.json` records the [`ToolDigest`] of the tool as it was discovered
+//! (see [`tool_source_digest`]). When the same server is generated again, a tool whose name,
+//! description, and schemas are unchanged keeps its category, keywords, and short description
+//! instead of being categorized from scratch; new and changed tools are left out.
+//!
+//! # Examples
+//!
+//! ```
+//! use mcp_execution_codegen::progressive::{reusable_categorizations, tool_source_digest};
+//! use mcp_execution_core::metadata::ToolMetadata;
+//! use mcp_execution_core::{ServerId, ToolName};
+//! use mcp_execution_introspector::{ServerCapabilities, ServerInfo, ToolInfo};
+//! use serde_json::json;
+//!
+//! let tool = ToolInfo {
+//!     name: ToolName::new("create_issue").unwrap(),
+//!     description: "Creates an issue".to_string(),
+//!     input_schema: json!({"type": "object"}),
+//!     output_schema: None,
+//! };
+//! let previous = vec![ToolMetadata {
+//!     name: tool.name.clone(),
+//!     typescript_name: "createIssue".to_string(),
+//!     category: Some("issues".to_string()),
+//!     keywords: vec!["issue".to_string()],
+//!     description: Some(tool.description.clone()),
+//!     short_description: Some("Create an issue".to_string()),
+//!     source_digest: Some(tool_source_digest(&tool)),
+//!     parameters: vec![],
+//! }];
+//! let server_info = ServerInfo {
+//!     id: ServerId::new("github").unwrap(),
+//!     name: "GitHub".to_string(),
+//!     version: "1.0.0".to_string(),
+//!     tools: vec![tool],
+//!     capabilities: ServerCapabilities {
+//!         supports_tools: true,
+//!         supports_resources: false,
+//!         supports_prompts: false,
+//!     },
+//! };
+//!
+//! let reused = reusable_categorizations(&server_info, &previous);
+//! assert_eq!(reused["create_issue"].category, "issues");
+//! ```
+
+use crate::progressive::types::ToolCategorization;
+use mcp_execution_core::metadata::ToolMetadata;
+use mcp_execution_core::provenance::{ToolDigest, ToolDigestEntry};
+use mcp_execution_introspector::{ServerInfo, ToolInfo};
+use std::collections::HashMap;
+
+/// Computes the [`ToolDigest`] of `tool` alone, as recorded in
+/// [`ToolMetadata::source_digest`].
+#[must_use]
+pub fn tool_source_digest(tool: &ToolInfo) -> ToolDigest {
+    ToolDigest::compute(&[ToolDigestEntry {
+        name: tool.name.as_str(),
+        description: &tool.description,
+        input_schema: &tool.input_schema,
+        output_schema: tool.output_schema.as_ref(),
+    }])
+}
+
+/// Returns the categorizations from `previous` that still apply to `server_info.tools`, keyed
+/// by tool name.
+///
+/// A tool's categorization is reused only if it had a category and its recorded
+/// [`ToolMetadata::source_digest`] equals the digest of the tool as discovered now, so an entry
+/// from a sidecar written before digests were recorded is never reused. A missing short
+/// description falls back to the tool's own description, as it does for rule-based
+/// categorization.
+#[must_use]
+pub fn reusable_categorizations(
+    server_info: &ServerInfo,
+    previous: &[ToolMetadata],
+) -> HashMap<String, ToolCategorization> {
+    let previous: HashMap<&str, &ToolMetadata> = previous
+        .iter()
+        .map(|meta| (meta.name.as_str(), meta))
+        .collect();
+
+    server_info
+        .tools
+        .iter()
+        .filter_map(|tool| {
+            let meta = previous.get(tool.name.as_str())?;
+            let category = meta.category.as_ref()?;
+            if meta.source_digest.as_ref() != Some(&tool_source_digest(tool)) {
+                return None;
+            }
+            let categorization = ToolCategorization {
+                category: category.clone(),
+                keywords: meta.keywords.clone(),
+                short_description: meta
+                    .short_description
+                    .clone()
+                    .unwrap_or_else(|| tool.description.clone()),
+            };
+            Some((tool.name.as_str().to_string(), categorization))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_execution_core::{ServerId, ToolName};
+    use mcp_execution_introspector::ServerCapabilities;
+    use serde_json::json;
+
+    fn tool(name: &str, description: &str) -> ToolInfo {
+        ToolInfo {
+            name: ToolName::new(name).unwrap(),
+            description: description.to_string(),
+            input_schema: json!({"type": "object"}),
+            output_schema: None,
+        }
+    }
+
+    fn server(tools: Vec<ToolInfo>) -> ServerInfo {
+        ServerInfo {
+            id: ServerId::new("github").unwrap(),
+            name: "GitHub".to_string(),
+            version: "1.0.0".to_string(),
+            tools,
+            capabilities: ServerCapabilities {
+                supports_tools: true,
+                supports_resources: false,
+                supports_prompts: false,
+            },
+        }
+    }
+
+    fn recorded(tool: &ToolInfo, category: Option<&str>) -> ToolMetadata {
+        ToolMetadata {
+            name: tool.name.clone(),
+            typescript_name: tool.name.as_str().to_string(),
+            category: category.map(str::to_string),
+            keywords: vec!["kw".to_string()],
+            description: Some(tool.description.clone()),
+            short_description: None,
+            source_digest: Some(tool_source_digest(tool)),
+            parameters: vec![],
+        }
+    }
+
+    #[test]
+    fn test_reusable_categorizations_skips_changed_new_and_uncategorized_tools() {
+        let unchanged = tool("create_issue", "Creates an issue");
+        let uncategorized = tool("list_issues", "Lists issues");
+        let mut legacy = recorded(&tool("get_issue", "Gets an issue"), Some("issues"));
+        legacy.source_digest = None;
+        let previous = vec![
+            recorded(&unchanged, Some("issues")),
+            recorded(&tool("close_issue", "Closes an issue"), Some("issues")),
+            recorded(&uncategorized, None),
+            legacy,
+        ];
+
+        let reused = reusable_categorizations(
+            &server(vec![
+                unchanged,
+                tool("close_issue", "Closes an issue, now with a reason"),
+                uncategorized,
+                tool("get_issue", "Gets an issue"),
+                tool("new_tool", "Something new"),
+            ]),
+            &previous,
+        );
+
+        assert_eq!(reused.len(), 1);
+        let categorization = &reused["create_issue"];
+        assert_eq!(categorization.category, "issues");
+        assert_eq!(categorization.keywords, vec!["kw".to_string()]);
+        assert_eq!(categorization.short_description, "Creates an issue");
+    }
+}
@@ -29,15 +29,24 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Determinism
+//!
+//! For the same tools, config, categorizations, and filter, every generated file is
+//! byte-identical across runs and machines, apart from `_meta.json`'s
+//! `provenance.generated_at` timestamp. Tools are processed in raw-name order rather than the
+//! order the server happened to list them in, so name-collision suffixes (`_2`, `_3`, ...) always
+//! land on the same tool. `generate --check` relies on this.
 
 use crate::common::types::{GeneratedCode, GeneratedFile};
 use crate::common::typescript::{
     MAX_SCHEMA_RECURSION_DEPTH, disambiguate_identifier, extract_properties,
     sanitize_ts_identifier, to_camel_case,
 };
+use crate::progressive::carry_over::tool_source_digest;
 use crate::progressive::types::{
-    BridgeContext, CategoryInfo, IndexContext, PropertyInfo, ToolCategorization, ToolContext,
-    ToolSummary,
+    BridgeConfigPaths, BridgeContext, CategoryInfo, IndexContext, PropertyInfo, ToolCategorization,
+    ToolContext, ToolSummary,
 };
 use crate::template_engine::TemplateEngine;
 use mcp_execution_core::ResourceKind;
@@ -46,8 +55,10 @@ use mcp_execution_core::metadata::{
     ServerMetadata, ToolMetadata,
 };
 use mcp_execution_core::provenance::{GenerationProvenance, ToolDigestEntry};
+use mcp_execution_core::tool_filter::ToolFilter;
 use mcp_execution_core::{Error, Result, ServerConfig};
 use mcp_execution_introspector::{ServerInfo, ToolInfo};
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
 /// Files emitted by every `generate`/`generate_with_categories` call regardless of tool
@@ -101,6 +112,21 @@ pub const MAX_GENERATED_BYTES: usize = 2
         + mcp_execution_introspector::MAX_TOOL_DESCRIPTION_LEN
         + mcp_execution_introspector::MAX_SCHEMA_SIZE_BYTES);
 
+/// Top-level entries `npm install`, the documented step after generating, creates in a server
+/// directory.
+///
+/// The generator never writes them, so a regeneration leaves them in place and
+/// `generate --check` does not compare them.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_codegen::progressive::generator::NPM_INSTALL_ARTIFACTS;
+///
+/// assert!(NPM_INSTALL_ARTIFACTS.contains(&"node_modules"));
+/// ```
+pub const NPM_INSTALL_ARTIFACTS: &[&str] = &["node_modules", "package-lock.json"];
+
 /// Contents of the generated `package.json`.
 ///
 /// Declares `@types/node` as a `devDependency` — pinned to major version 22, matching the
@@ -184,6 +210,7 @@ const TSCONFIG_JSON: &str = r#"{
 #[derive(Debug)]
 pub struct ProgressiveGenerator<'a> {
     engine: TemplateEngine<'a>,
+    config_paths: BridgeConfigPaths,
 }
 
 impl ProgressiveGenerator<'_> {
@@ -206,7 +233,33 @@ impl ProgressiveGenerator<'_> {
     /// ```
     pub fn new() -> Result<Self> {
         let engine = TemplateEngine::new()?;
-        Ok(Self { engine })
+        Ok(Self {
+            engine,
+            config_paths: BridgeConfigPaths::default(),
+        })
+    }
+
+    /// Bakes config file locations into the generated runtime bridge.
+    ///
+    /// By default the bridge resolves `~/.claude/mcp.json` and `~/.claude.json` from the
+    /// runtime user's home directory; with these set it reads the files the generator itself
+    /// resolved, so a server generated under a custom home runs against the same config.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_codegen::progressive::{BridgeConfigPaths, ProgressiveGenerator};
+    /// use mcp_execution_core::mcp_config::McpConfigLocations;
+    ///
+    /// let locations = McpConfigLocations::new().with_home_dir("/sandbox");
+    /// let generator = ProgressiveGenerator::new()
+    ///     .unwrap()
+    ///     .with_config_paths(BridgeConfigPaths::from_locations(&locations));
+    /// ```
+    #[must_use]
+    pub fn with_config_paths(mut self, config_paths: BridgeConfigPaths) -> Self {
+        self.config_paths = config_paths;
+        self
     }
 
     /// Generates progressive loading files for a server.
@@ -335,16 +388,91 @@ impl ProgressiveGenerator<'_> {
     /// # Ok(())
     /// # }
     /// ```
+    pub fn generate_with_categories(
+        &self,
+        server_info: &ServerInfo,
+        server_config: &ServerConfig,
+        categorizations: &HashMap<String, ToolCategorization>,
+    ) -> Result<GeneratedCode> {
+        self.generate_filtered(
+            server_info,
+            server_config,
+            categorizations,
+            &ToolFilter::default(),
+        )
+    }
+
+    /// Generates progressive loading files for only the tools `filter` keeps.
+    ///
+    /// Like [`generate_with_categories`](Self::generate_with_categories), but a tool `filter`
+    /// rejects is left out of every generated file — it gets no `.ts` file, no `index.ts`
+    /// export, and no `_meta.json` entry (so no `SKILL.md` entry either). Categorizations for
+    /// excluded tools are ignored.
+    ///
+    /// The `_meta.json` sidecar's [`GenerationProvenance`] records `filter` and computes its
+    /// tool digest over the *unfiltered* `server_info.tools`, so changing only the filter is
+    /// distinguishable from the server's own tool surface changing.
+    ///
+    /// The kept tools are generated in raw-name order (see [`select_tools`]), so the output does
+    /// not depend on the order the server listed them in.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if template rendering fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use mcp_execution_codegen::progressive::ProgressiveGenerator;
+    /// use mcp_execution_core::tool_filter::ToolFilter;
+    /// use mcp_execution_core::{ServerConfig, ServerId};
+    /// use mcp_execution_introspector::{ServerCapabilities, ServerInfo};
+    /// use std::collections::HashMap;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let generator = ProgressiveGenerator::new()?;
+    /// let info = ServerInfo {
+    ///     id: ServerId::new("github").unwrap(),
+    ///     name: "GitHub".to_string(),
+    ///     version: "1.0.0".to_string(),
+    ///     tools: vec![],
+    ///     capabilities: ServerCapabilities {
+    ///         supports_tools: true,
+    ///         supports_resources: false,
+    ///         supports_prompts: false,
+    ///     },
+    /// };
+    /// let config = ServerConfig::builder().command("/path/to/github-server".to_string()).build()?;
+    /// let filter = ToolFilter::new(vec!["issue_*".to_string()], vec!["*_admin_*".to_string()])?;
+    ///
+    /// let code = generator.generate_filtered(&info, &config, &HashMap::new(), &filter)?;
+    /// # Ok(())
+    /// # }
+    /// ```
     #[tracing::instrument(
         skip_all,
         fields(server_id = %server_info.id, tool_count = server_info.tools.len())
     )]
-    pub fn generate_with_categories(
+    pub fn generate_filtered(
         &self,
         server_info: &ServerInfo,
         server_config: &ServerConfig,
         categorizations: &HashMap<String, ToolCategorization>,
+        filter: &ToolFilter,
     ) -> Result<GeneratedCode> {
+        // Everything below except the provenance digest works from the selected view; the
+        // unfiltered `server_info` is kept for `create_metadata_file`.
+        let discovered = server_info;
+        let selected = select_tools(discovered, filter);
+        let server_info: &ServerInfo = &selected;
+        if !filter.is_empty() {
+            tracing::info!(
+                "Tool filter kept {} of {} tools",
+                server_info.tools.len(),
+                discovered.tools.len()
+            );
+        }
+
         if categorizations.is_empty() {
             tracing::info!(
                 "Generating progressive loading code for server: {}",
@@ -385,7 +513,7 @@ impl ProgressiveGenerator<'_> {
         add_tracked(
             &mut code,
             &mut total_bytes,
-            Self::create_metadata_file(server_info, server_config, tool_metadata)?,
+            Self::create_metadata_file(discovered, server_config, filter, tool_metadata)?,
         )?;
 
         tracing::debug!("Generated {}", METADATA_FILE_NAME);
@@ -514,7 +642,7 @@ impl ProgressiveGenerator<'_> {
         total_bytes: &mut usize,
     ) -> Result<()> {
         // Generate runtime bridge (same as non-categorized)
-        let bridge_context = BridgeContext::default();
+        let bridge_context = BridgeContext::default().with_config_paths(&self.config_paths);
         let bridge_code = self
             .engine
             .render("progressive/runtime-bridge", &bridge_context)?;
@@ -835,6 +963,8 @@ impl ProgressiveGenerator<'_> {
             category,
             keywords,
             description,
+            short_description: categorization.map(|c| c.short_description.clone()),
+            source_digest: Some(tool_source_digest(tool)),
             parameters: properties
                 .into_iter()
                 .map(|(p, raw_description)| ParameterMetadata {
@@ -852,7 +982,9 @@ impl ProgressiveGenerator<'_> {
     ///
     /// Computes [`GenerationProvenance`] from `server_config` and `server_info.tools` — the
     /// same inputs this whole call is generating from — so the recorded digest can never drift
-    /// from the files actually emitted.
+    /// from the files actually emitted. `server_info` must be the *unfiltered* discovery result
+    /// and `tools` the metadata for just the tools `filter` kept; `filter` itself is recorded
+    /// alongside the digest rather than narrowing it.
     ///
     /// # Errors
     ///
@@ -861,6 +993,7 @@ impl ProgressiveGenerator<'_> {
     fn create_metadata_file(
         server_info: &ServerInfo,
         server_config: &ServerConfig,
+        filter: &ToolFilter,
         tools: Vec<ToolMetadata>,
     ) -> Result<GeneratedFile> {
         let digest_entries: Vec<ToolDigestEntry<'_>> = server_info
@@ -873,7 +1006,8 @@ impl ProgressiveGenerator<'_> {
                 output_schema: tool.output_schema.as_ref(),
             })
             .collect();
-        let provenance = GenerationProvenance::capture(server_config, &digest_entries);
+        let provenance = GenerationProvenance::capture(server_config, &digest_entries)
+            .with_tool_filter(filter.clone());
 
         let meta = ServerMetadata {
             schema_version: METADATA_SCHEMA_VERSION,
@@ -1122,6 +1256,40 @@ const RESERVED_WORDS: &[&str] = &[
 /// like any other name collision.
 const RESERVED_OUTPUT_NAMES: &[&str] = &["index"];
 
+/// Returns the tools of `server_info` that `filter` keeps, sorted by raw tool name.
+///
+/// Sorting pins the iteration order every later step depends on, [`resolve_typescript_names`]'s
+/// collision suffixes in particular, to the tool names themselves instead of the order the
+/// server happened to list them in. The sort is stable, so two tools with an identical raw name
+/// (which [`resolve_typescript_names`] still disambiguates) keep their reported relative order.
+///
+/// Borrows `server_info` unchanged in the common case of no filter and an already-sorted list,
+/// rather than cloning every tool's schema.
+fn select_tools<'a>(server_info: &'a ServerInfo, filter: &ToolFilter) -> Cow<'a, ServerInfo> {
+    let sorted = server_info
+        .tools
+        .is_sorted_by(|a, b| a.name.as_str() <= b.name.as_str());
+    if filter.is_empty() && sorted {
+        return Cow::Borrowed(server_info);
+    }
+
+    let mut tools: Vec<ToolInfo> = server_info
+        .tools
+        .iter()
+        .filter(|tool| filter.matches(tool.name.as_str()))
+        .cloned()
+        .collect();
+    tools.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+
+    Cow::Owned(ServerInfo {
+        id: server_info.id.clone(),
+        name: server_info.name.clone(),
+        version: server_info.version.clone(),
+        tools,
+        capabilities: server_info.capabilities.clone(),
+    })
+}
+
 /// Resolves a collision-free TypeScript identifier for each tool, in tool order.
 ///
 /// `sanitize_ts_identifier` can map distinct tool names to the same identifier (e.g.
@@ -1515,6 +1683,55 @@ mod tests {
         );
     }
 
+    /// An excluded tool is absent from its own `.ts` file, `index.ts`, and `_meta.json`, while
+    /// provenance still digests the full tool list and records the filter.
+    #[test]
+    fn test_generate_filtered_omits_excluded_tools_everywhere() {
+        let generator = ProgressiveGenerator::new().unwrap();
+        let server_info = create_test_server_info();
+        let config = test_config();
+        let filter = ToolFilter::new(vec![], vec!["update_*".to_string()]).unwrap();
+
+        let unfiltered = generator.generate(&server_info, &config).unwrap();
+        let filtered = generator
+            .generate_filtered(&server_info, &config, &HashMap::new(), &filter)
+            .unwrap();
+
+        assert!(filtered.files.iter().any(|f| f.path == "createIssue.ts"));
+        assert!(!filtered.files.iter().any(|f| f.path == "updateIssue.ts"));
+
+        let index = filtered
+            .files
+            .iter()
+            .find(|f| f.path == INDEX_FILE_NAME)
+            .unwrap();
+        assert!(index.content.contains("createIssue"));
+        assert!(!index.content.contains("updateIssue"));
+
+        let meta_of = |code: &GeneratedCode| -> ServerMetadata {
+            let file = code
+                .files
+                .iter()
+                .find(|f| f.path == METADATA_FILE_NAME)
+                .unwrap();
+            serde_json::from_str(&file.content).unwrap()
+        };
+        let filtered_meta = meta_of(&filtered);
+        let unfiltered_meta = meta_of(&unfiltered);
+
+        let names: Vec<&str> = filtered_meta
+            .tools
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, ["create_issue"]);
+        assert_eq!(filtered_meta.provenance.tool_filter, filter);
+        assert_eq!(
+            filtered_meta.provenance.tool_digest, unfiltered_meta.provenance.tool_digest,
+            "a filter change must not look like a tool-surface change"
+        );
+    }
+
     #[test]
     fn test_generate_with_categories_meta_json_includes_categorization() {
         let generator = ProgressiveGenerator::new().unwrap();
@@ -2915,6 +3132,71 @@ mod tests {
         );
     }
 
+    /// Pins which colliding tool gets the `_2` suffix: the one whose raw name sorts later,
+    /// whatever order the server listed them in.
+    #[test]
+    fn test_generate_disambiguation_is_independent_of_reported_tool_order() {
+        let generator = ProgressiveGenerator::new().unwrap();
+        let tool = |name: &str, description: &str| ToolInfo {
+            name: ToolName::new(name).unwrap(),
+            description: description.to_string(),
+            input_schema: json!({}),
+            output_schema: None,
+        };
+
+        for tools in [
+            vec![tool("foo-bar", "Dash"), tool("foo.bar", "Dot")],
+            vec![tool("foo.bar", "Dot"), tool("foo-bar", "Dash")],
+        ] {
+            let mut server_info = create_test_server_info();
+            server_info.tools = tools;
+            let code = generator.generate(&server_info, &test_config()).unwrap();
+
+            let first = code.files.iter().find(|f| f.path == "foo_bar.ts").unwrap();
+            let second = code
+                .files
+                .iter()
+                .find(|f| f.path == "foo_bar_2.ts")
+                .unwrap();
+            assert!(first.content.contains("Dash"), "`foo-bar` sorts first");
+            assert!(second.content.contains("Dot"), "`foo.bar` sorts second");
+        }
+    }
+
+    /// Every generated file is byte-identical whatever order the server listed its tools in,
+    /// apart from `_meta.json`'s `generated_at`.
+    #[test]
+    fn test_generate_output_is_independent_of_reported_tool_order() {
+        let generator = ProgressiveGenerator::new().unwrap();
+        let server_info = create_test_server_info();
+        let mut reversed = server_info.clone();
+        reversed.tools.reverse();
+        assert_ne!(
+            server_info.tools.first().map(|t| t.name.as_str()),
+            reversed.tools.first().map(|t| t.name.as_str()),
+            "the fixture must have more than one tool for reversing it to mean anything"
+        );
+
+        let render = |info: &ServerInfo| -> Vec<(String, String)> {
+            let code = generator.generate(info, &test_config()).unwrap();
+            code.files
+                .iter()
+                .map(|f| {
+                    let content = if f.path == METADATA_FILE_NAME {
+                        let mut meta: serde_json::Value = serde_json::from_str(&f.content).unwrap();
+                        meta["provenance"]["generated_at"] = json!(null);
+                        meta.to_string()
+                    } else {
+                        f.content.clone()
+                    };
+                    (f.path.clone(), content)
+                })
+                .collect()
+        };
+
+        assert_eq!(render(&server_info), render(&reversed));
+    }
+
     #[test]
     fn test_generate_disambiguates_identical_raw_tool_names() {
         // An MCP server reporting two tools with the exact same raw `name` is invalid per
@@ -3171,6 +3453,183 @@ mod tests {
         );
     }
 
+    /// The env-policy allowlist baseline is rendered from `mcp_execution_core`, and
+    /// `createConnection` spawns with the filtered environment rather than all of
+    /// `process.env`.
+    #[test]
+    fn test_generate_runtime_bridge_renders_minimal_env_names() {
+        let code = ProgressiveGenerator::new()
+            .unwrap()
+            .generate(&create_test_server_info(), &test_config())
+            .unwrap();
+        let bridge = code
+            .files
+            .iter()
+            .find(|f| f.path == "_runtime/mcp-bridge.ts")
+            .unwrap();
+
+        let names: Vec<String> = mcp_execution_core::minimal_env_names()
+            .iter()
+            .map(|name| format!("'{name}'"))
+            .collect();
+        assert!(bridge.content.contains(&format!(
+            "const MINIMAL_ENV_NAMES = [{}];",
+            names.join(", ")
+        )));
+        assert!(
+            bridge
+                .content
+                .contains("env: { ...inheritedEnv(config.envPolicy), ...config.env },")
+        );
+        assert!(!bridge.content.contains("...process.env, ...config.env"));
+    }
+
+    /// Server stderr is piped into a tail bounded by the introspector's own limit rather than
+    /// inherited into the agent's terminal.
+    #[test]
+    fn test_generate_runtime_bridge_pipes_and_bounds_server_stderr() {
+        let code = ProgressiveGenerator::new()
+            .unwrap()
+            .generate(&create_test_server_info(), &test_config())
+            .unwrap();
+        let bridge = code
+            .files
+            .iter()
+            .find(|f| f.path == "_runtime/mcp-bridge.ts")
+            .unwrap();
+
+        assert!(bridge.content.contains(&format!(
+            "const MAX_STDERR_TAIL_BYTES = {};",
+            mcp_execution_introspector::MAX_STDERR_TAIL_BYTES
+        )));
+        assert!(bridge.content.contains("stdio: ['pipe', 'pipe', 'pipe'],"));
+        assert!(
+            bridge
+                .content
+                .contains("attachStderrCollector(connection, serverId);")
+        );
+    }
+
+    /// Config paths are baked only when given, as exact string literals; otherwise the bridge
+    /// keeps resolving them from `os.homedir()`.
+    #[test]
+    fn test_generate_runtime_bridge_bakes_config_paths() {
+        let bridge_of = |generator: ProgressiveGenerator<'_>| {
+            let code = generator
+                .generate(&create_test_server_info(), &test_config())
+                .unwrap();
+            code.files
+                .into_iter()
+                .find(|f| f.path == "_runtime/mcp-bridge.ts")
+                .unwrap()
+                .content
+        };
+
+        let default_bridge = bridge_of(ProgressiveGenerator::new().unwrap());
+        assert!(
+            default_bridge
+                .contains("const BAKED_USER_CONFIG_PATH: string | undefined = undefined;")
+        );
+        assert!(
+            default_bridge.contains("const BAKED_CONFIG_PATH: string | undefined = undefined;")
+        );
+
+        let baked_bridge = bridge_of(ProgressiveGenerator::new().unwrap().with_config_paths(
+            BridgeConfigPaths {
+                explicit: None,
+                user_config: Some(std::path::PathBuf::from("/sandbox/it's \"here\"/mcp.json")),
+                claude_json: Some(std::path::PathBuf::from("/sandbox/.claude.json")),
+                project_root: None,
+            },
+        ));
+        assert!(baked_bridge.contains(
+            r#"const BAKED_USER_CONFIG_PATH: string | undefined = "/sandbox/it's \"here\"/mcp.json";"#
+        ));
+        assert!(baked_bridge.contains(
+            r#"const BAKED_CLAUDE_JSON_PATH: string | undefined = "/sandbox/.claude.json";"#
+        ));
+        assert!(baked_bridge.contains("const BAKED_CONFIG_PATH: string | undefined = undefined;"));
+        assert!(default_bridge.contains("const PROJECT_ROOT: string | undefined = undefined;"));
+        assert!(!default_bridge.contains("fileURLToPath"));
+    }
+
+    /// Two users generating with their config in its default place under their own homes get
+    /// the same files, apart from `_meta.json`'s `generated_at`: neither home is baked into the
+    /// bridge, so committed bindings pass `--check` on any machine.
+    #[test]
+    fn test_generate_output_is_identical_under_different_default_homes() {
+        use mcp_execution_core::mcp_config::McpConfigLocations;
+        use mcp_execution_core::paths::Paths;
+
+        let render = |home: &str| -> Vec<(String, String)> {
+            let config_paths = BridgeConfigPaths::differing_from_runtime(
+                &McpConfigLocations::new().with_home_dir(home),
+                Some(&Paths::new(home)),
+            );
+            let code = ProgressiveGenerator::new()
+                .unwrap()
+                .with_config_paths(config_paths)
+                .generate(&create_test_server_info(), &test_config())
+                .unwrap();
+            code.files
+                .iter()
+                .map(|f| {
+                    let content = if f.path == METADATA_FILE_NAME {
+                        let mut meta: serde_json::Value = serde_json::from_str(&f.content).unwrap();
+                        meta["provenance"]["generated_at"] = json!(null);
+                        meta.to_string()
+                    } else {
+                        f.content.clone()
+                    };
+                    (f.path.clone(), content)
+                })
+                .collect()
+        };
+
+        let alice = render("/home/alice");
+        assert_eq!(alice, render("/Users/bob"));
+        assert!(
+            alice
+                .iter()
+                .all(|(_, content)| !content.contains("/home/alice"))
+        );
+    }
+
+    /// Project bindings bake only a root relative to the bridge itself, one level above the
+    /// server directory, and no absolute path.
+    #[test]
+    fn test_generate_runtime_bridge_bakes_relative_project_root() {
+        let code = ProgressiveGenerator::new()
+            .unwrap()
+            .with_config_paths(BridgeConfigPaths::project("../../.."))
+            .generate(&create_test_server_info(), &test_config())
+            .unwrap();
+        let bridge = code
+            .files
+            .iter()
+            .find(|f| f.path == "_runtime/mcp-bridge.ts")
+            .unwrap();
+
+        assert!(
+            bridge
+                .content
+                .contains("import { fileURLToPath } from 'url';")
+        );
+        assert!(bridge.content.contains(
+            r#"const PROJECT_ROOT: string | undefined = resolve(dirname(fileURLToPath(import.meta.url)), "../../../..");"#
+        ));
+        assert!(
+            bridge
+                .content
+                .contains("const BAKED_USER_CONFIG_PATH: string | undefined = undefined;")
+        );
+        assert!(
+            bridge
+                .content
+                .contains("const BAKED_CLAUDE_JSON_PATH: string | undefined = undefined;")
+        );
+    }
+
     /// #471/#467 drift guard: the `DoS` size/count ceilings and the env-name charset pattern
     /// must be rendered from `mcp_execution_core`'s constants/accessor, not hand-copied
     /// literals — same "read from the accessor, not a hardcoded second copy" reasoning as
@@ -91,12 +91,14 @@
 //! mcp-codegen = { version = "0.1", features = ["progressive"] }
 //! ```
 
+pub mod carry_over;
 pub mod generator;
 pub mod types;
 
 // Re-export main types
+pub use carry_over::{reusable_categorizations, tool_source_digest};
 pub use generator::ProgressiveGenerator;
 pub use types::{
-    BridgeContext, CategoryInfo, IndexContext, PropertyInfo, ToolCategorization, ToolContext,
-    ToolSummary,
+    BridgeConfigPaths, BridgeContext, CategoryInfo, IndexContext, PropertyInfo, ToolCategorization,
+    ToolContext, ToolSummary,
 };
@@ -3,7 +3,10 @@
 //! Defines data structures used during progressive code generation,
 //! where each tool is generated as a separate file.
 
+use mcp_execution_core::mcp_config::McpConfigLocations;
+use mcp_execution_core::paths::Paths;
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// Context for rendering a single tool template.
 ///
@@ -271,6 +274,8 @@ pub struct BridgeContext {
     forbidden_env_names: Vec<String>,
     /// Environment-variable-name prefix rejected regardless of exact match (e.g. `DYLD_`).
     forbidden_env_prefix: String,
+    /// Names a `minimal`/`allowlist` env policy always inherits from the parent environment.
+    minimal_env_names: Vec<String>,
     /// POSIX/Windows environment-variable-name identifier charset, as an anchored JavaScript
     /// `RegExp`-compatible pattern source (see `mcp_execution_core::env_name_charset_pattern`),
     /// pre-escaped for safe embedding inside a single-quoted TypeScript string literal — same
@@ -301,6 +306,18 @@ pub struct BridgeContext {
     /// Maximum byte length for a single HTTP header value
     /// (`mcp_execution_core::MAX_HEADER_VALUE_LEN`).
     pub max_header_value_len: usize,
+    /// Bytes of a server's stderr kept for error messages
+    /// (`mcp_execution_introspector::MAX_STDERR_TAIL_BYTES`).
+    pub max_stderr_tail_bytes: usize,
+    /// Explicit config file baked in by [`with_config_paths`](Self::with_config_paths), as a
+    /// TypeScript string literal.
+    config_path: Option<String>,
+    /// User-level `mcp.json`, as a TypeScript string literal.
+    user_config_path: Option<String>,
+    /// Claude Code's `.claude.json`, as a TypeScript string literal.
+    claude_json_path: Option<String>,
+    /// Project root relative to the bridge file, as a TypeScript string literal.
+    project_root: Option<String>,
 }
 
 impl BridgeContext {
@@ -333,6 +350,21 @@ impl BridgeContext {
         &self.forbidden_env_names
     }
 
+    /// Names a `minimal`/`allowlist` env policy always inherits from the parent environment
+    /// (`mcp_execution_core::minimal_env_names`). Never empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_codegen::progressive::BridgeContext;
+    ///
+    /// assert!(BridgeContext::default().minimal_env_names().contains(&"PATH".to_string()));
+    /// ```
+    #[must_use]
+    pub fn minimal_env_names(&self) -> &[String] {
+        &self.minimal_env_names
+    }
+
     /// Environment-variable-name prefix rejected regardless of exact match (e.g. `DYLD_`).
     /// Never empty.
     ///
@@ -362,6 +394,204 @@ impl BridgeContext {
     pub fn env_name_charset_pattern(&self) -> &str {
         &self.env_name_charset_pattern
     }
+
+    /// Bakes config file locations into the bridge, replacing its `os.homedir()` defaults.
+    ///
+    /// Paths that are not valid UTF-8 are left out, so the bridge falls back to its default for
+    /// them rather than reading a mangled path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_codegen::progressive::{BridgeConfigPaths, BridgeContext};
+    ///
+    /// let paths = BridgeConfigPaths {
+    ///     user_config: Some("/sandbox/.claude/mcp.json".into()),
+    ///     ..BridgeConfigPaths::default()
+    /// };
+    /// let context = BridgeContext::default().with_config_paths(&paths);
+    /// assert_eq!(context.user_config_path(), Some(r#""/sandbox/.claude/mcp.json""#));
+    /// assert_eq!(context.config_path(), None);
+    /// ```
+    #[must_use]
+    pub fn with_config_paths(mut self, paths: &BridgeConfigPaths) -> Self {
+        self.config_path = paths.explicit.as_deref().and_then(ts_path_literal);
+        self.user_config_path = paths.user_config.as_deref().and_then(ts_path_literal);
+        self.claude_json_path = paths.claude_json.as_deref().and_then(ts_path_literal);
+        self.project_root = paths
+            .project_root
+            .as_deref()
+            .and_then(|root| ts_relative_path_literal(&Path::new("..").join(root)));
+        self
+    }
+
+    /// The baked explicit config file, as a TypeScript string literal.
+    #[must_use]
+    pub fn config_path(&self) -> Option<&str> {
+        self.config_path.as_deref()
+    }
+
+    /// The baked user-level `mcp.json`, as a TypeScript string literal.
+    #[must_use]
+    pub fn user_config_path(&self) -> Option<&str> {
+        self.user_config_path.as_deref()
+    }
+
+    /// The baked `.claude.json`, as a TypeScript string literal.
+    #[must_use]
+    pub fn claude_json_path(&self) -> Option<&str> {
+        self.claude_json_path.as_deref()
+    }
+
+    /// The baked project root, relative to the bridge file, as a TypeScript string literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_codegen::progressive::{BridgeConfigPaths, BridgeContext};
+    ///
+    /// let context =
+    ///     BridgeContext::default().with_config_paths(&BridgeConfigPaths::project("../../.."));
+    /// assert_eq!(context.project_root(), Some(r#""../../../..""#));
+    /// ```
+    #[must_use]
+    pub fn project_root(&self) -> Option<&str> {
+        self.project_root.as_deref()
+    }
+}
+
+/// A path as a double-quoted TypeScript string literal.
+///
+/// JSON string syntax is a subset of TypeScript's, and unlike `sanitize_ts_string_literal` it
+/// never truncates or rewrites characters, so the bridge reads exactly the path it was given.
+fn ts_path_literal(path: &Path) -> Option<String> {
+    path.to_str()
+        .and_then(|path| serde_json::to_string(path).ok())
+}
+
+/// A relative path as a double-quoted TypeScript string literal, with `/` separators so the
+/// bridge resolves it the same way on every platform it is checked out on.
+fn ts_relative_path_literal(path: &Path) -> Option<String> {
+    let parts = path
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    serde_json::to_string(&parts.join("/")).ok()
+}
+
+/// Config file locations the generated runtime bridge reads instead of resolving them itself.
+///
+/// Without these, the bridge derives `~/.claude/mcp.json` and `~/.claude.json` from
+/// `os.homedir()` at runtime, which disagrees with the generator whenever its home was moved
+/// with `--home`, `MCP_EXECUTION_HOME`, or `CLAUDE_CONFIG_DIR`. Only such a moved location is
+/// baked: a path the bridge would find anyway would tie the output to the generating user's
+/// home directory and make it differ between machines. An explicit config file is always
+/// baked, since nothing tells the generator whether the bridge will run with
+/// `MCP_EXECUTION_CONFIG` set.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_codegen::progressive::BridgeConfigPaths;
+/// use mcp_execution_core::mcp_config::McpConfigLocations;
+///
+/// let locations = McpConfigLocations::new().with_home_dir("/sandbox");
+/// let paths = BridgeConfigPaths::from_locations(&locations);
+/// assert!(paths.explicit.is_none());
+/// assert_eq!(paths.user_config.unwrap(), std::path::Path::new("/sandbox/.claude/mcp.json"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BridgeConfigPaths {
+    /// Explicit config file replacing discovery. `MCP_EXECUTION_CONFIG` still overrides it.
+    pub explicit: Option<PathBuf>,
+    /// User-level `mcp.json`.
+    pub user_config: Option<PathBuf>,
+    /// Claude Code's `.claude.json`.
+    pub claude_json: Option<PathBuf>,
+    /// Project root, relative to the server's output directory, for bindings committed into a
+    /// project. The bridge resolves it against its own location and reads the project's
+    /// `.mcp.json` from there instead of from the current directory.
+    pub project_root: Option<PathBuf>,
+}
+
+impl BridgeConfigPaths {
+    /// The paths `locations` resolves to that the bridge would not find on its own.
+    ///
+    /// Compares against the operating system's home directory; see
+    /// [`Self::differing_from_runtime`].
+    #[must_use]
+    pub fn from_locations(locations: &McpConfigLocations) -> Self {
+        Self::differing_from_runtime(locations, Paths::os_default().as_ref())
+    }
+
+    /// The paths `locations` resolves to, leaving out the user-level files the bridge finds the
+    /// same way under `runtime_paths` (its `os.homedir()`). An explicit config file is always
+    /// kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_codegen::progressive::BridgeConfigPaths;
+    /// use mcp_execution_core::mcp_config::McpConfigLocations;
+    /// use mcp_execution_core::paths::Paths;
+    ///
+    /// let home = Paths::new("/home/alice");
+    /// let unmoved = McpConfigLocations::new().with_home_dir("/home/alice");
+    /// assert_eq!(
+    ///     BridgeConfigPaths::differing_from_runtime(&unmoved, Some(&home)),
+    ///     BridgeConfigPaths::default()
+    /// );
+    ///
+    /// let explicit = unmoved.with_explicit("/home/alice/mcp.json");
+    /// let paths = BridgeConfigPaths::differing_from_runtime(&explicit, Some(&home));
+    /// assert_eq!(paths.explicit.unwrap(), std::path::Path::new("/home/alice/mcp.json"));
+    ///
+    /// let moved = McpConfigLocations::new().with_home_dir("/sandbox");
+    /// let paths = BridgeConfigPaths::differing_from_runtime(&moved, Some(&home));
+    /// assert_eq!(paths.user_config.unwrap(), std::path::Path::new("/sandbox/.claude/mcp.json"));
+    /// ```
+    #[must_use]
+    pub fn differing_from_runtime(
+        locations: &McpConfigLocations,
+        runtime_paths: Option<&Paths>,
+    ) -> Self {
+        let unless_default = |path: Option<PathBuf>, default: Option<PathBuf>| {
+            path.filter(|path| Some(path) != default.as_ref())
+        };
+        Self {
+            explicit: locations.explicit().map(Path::to_path_buf),
+            user_config: unless_default(
+                locations.user_config_path(),
+                runtime_paths.map(Paths::user_mcp_config),
+            ),
+            claude_json: unless_default(
+                locations.claude_json_path(),
+                runtime_paths.map(Paths::claude_json),
+            ),
+            project_root: None,
+        }
+    }
+
+    /// Paths for bindings committed into a project: only `relative_root` is baked, so no
+    /// generated file names a path on the generating machine and user-level files are found
+    /// from the runtime user's home directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_codegen::progressive::BridgeConfigPaths;
+    ///
+    /// let paths = BridgeConfigPaths::project("../../..");
+    /// assert!(paths.user_config.is_none());
+    /// assert_eq!(paths.project_root.unwrap(), std::path::Path::new("../../.."));
+    /// ```
+    #[must_use]
+    pub fn project(relative_root: impl Into<PathBuf>) -> Self {
+        Self {
+            project_root: Some(relative_root.into()),
+            ..Self::default()
+        }
+    }
 }
 
 impl Default for BridgeContext {
@@ -385,6 +615,10 @@ impl Default for BridgeContext {
                 .map(|&s| s.to_string())
                 .collect(),
             forbidden_env_prefix: mcp_execution_core::forbidden_env_prefix().to_string(),
+            minimal_env_names: mcp_execution_core::minimal_env_names()
+                .iter()
+                .map(|&s| s.to_string())
+                .collect(),
             env_name_charset_pattern: crate::progressive::generator::sanitize_ts_string_literal(
                 mcp_execution_core::env_name_charset_pattern(),
             ),
@@ -398,6 +632,11 @@ impl Default for BridgeContext {
             max_url_len: mcp_execution_core::MAX_URL_LEN,
             max_header_count: mcp_execution_core::MAX_HEADER_COUNT,
             max_header_value_len: mcp_execution_core::MAX_HEADER_VALUE_LEN,
+            max_stderr_tail_bytes: mcp_execution_introspector::MAX_STDERR_TAIL_BYTES,
+            config_path: None,
+            user_config_path: None,
+            claude_json_path: None,
+            project_root: None,
         }
     }
 }
@@ -487,6 +726,10 @@ mod tests {
         assert!(!context.forbidden_env_names().is_empty());
         assert!(!context.forbidden_env_prefix().is_empty());
         assert!(!context.env_name_charset_pattern().is_empty());
+        assert_eq!(
+            context.minimal_env_names(),
+            mcp_execution_core::minimal_env_names()
+        );
 
         // #471: the DoS size/count ceilings must be populated from mcp_execution_core, not
         // left at zero (which would reject every config, silently breaking every generated
@@ -507,6 +750,10 @@ mod tests {
             context.max_header_value_len,
             mcp_execution_core::MAX_HEADER_VALUE_LEN
         );
+        assert_eq!(
+            context.max_stderr_tail_bytes,
+            mcp_execution_introspector::MAX_STDERR_TAIL_BYTES
+        );
         // Escaping is a no-op on this quote/backslash-free pattern, so the sanitized copy
         // still equals the raw Rust source of truth.
         assert_eq!(
@@ -3,7 +3,7 @@
 //! Tests the full pipeline from `ServerInfo` to generated TypeScript files
 //! for progressive loading pattern.
 
-use mcp_execution_codegen::progressive::ProgressiveGenerator;
+use mcp_execution_codegen::progressive::{BridgeConfigPaths, ProgressiveGenerator};
 use mcp_execution_core::{Error, ServerConfig, ServerId, ToolName};
 use mcp_execution_introspector::{ServerCapabilities, ServerInfo, ToolInfo};
 use serde_json::json;
@@ -1008,7 +1008,14 @@ fn compile_and_run_bridge_harness(
             // both must point at the fake home directory for the harness to isolate itself
             // from the real runner's home on every platform.
             .env("HOME", &home_dir)
-            .env("USERPROFILE", &home_dir);
+            .env("USERPROFILE", &home_dir)
+            // The bridge also reads `.mcp.json` walking up from the working directory, the
+            // Claude Desktop config under the platform config directory, and an explicit
+            // `MCP_EXECUTION_CONFIG`; keep all three away from the real runner's files.
+            .current_dir(&home_dir)
+            .env("XDG_CONFIG_HOME", home_dir.join(".config"))
+            .env("APPDATA", home_dir.join("AppData"))
+            .env_remove("MCP_EXECUTION_CONFIG");
         for (key, value) in &owned_extra_env {
             cmd.env(key, value);
         }
@@ -1033,6 +1040,230 @@ fn compile_and_run_bridge_harness(
     ))
 }
 
+/// `MCP_EXECUTION_CONFIG` must replace config discovery in the rendered bridge exactly as it
+/// does for `mcp-execution-cli`: the server is resolved from that file alone, even though
+/// `~/.claude/mcp.json` defines the same server id. Each file's entry carries a different
+/// forbidden env var, so the rejection message shows which definition was used.
+///
+/// Skips (does not fail) when `tsc` or `node` is not on `PATH`.
+#[test]
+fn test_runtime_bridge_explicit_config_env_replaces_discovery() {
+    let generator = ProgressiveGenerator::new().expect("Failed to create generator");
+    let code = generator
+        .generate(&create_test_server_info(), &test_config())
+        .expect("Failed to generate code");
+    let bridge = code
+        .files
+        .iter()
+        .find(|f| f.path == "_runtime/mcp-bridge.ts")
+        .expect("_runtime/mcp-bridge.ts not found");
+
+    let user_mcp_json = json!({
+        "mcpServers": {
+            "github": {
+                "command": "node",
+                "args": ["--version"],
+                "env": { "LD_PRELOAD": "/tmp/evil.so" }
+            }
+        }
+    });
+    let explicit_dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let explicit_path = explicit_dir.path().join("servers.json");
+    let explicit_json = json!({
+        "mcpServers": {
+            "github": {
+                "command": "node",
+                "args": ["--version"],
+                "env": { "DYLD_INSERT_LIBRARIES": "/tmp/evil.dylib" }
+            }
+        }
+    });
+    std::fs::write(&explicit_path, explicit_json.to_string())
+        .expect("Failed to write explicit config");
+    let explicit_path = explicit_path.to_str().expect("temp path is UTF-8");
+
+    let Some((success, stdout, stderr)) = run_bridge_harness_with_env(
+        "test_runtime_bridge_explicit_config_env_replaces_discovery",
+        &bridge.content,
+        &user_mcp_json,
+        "github",
+        &[("MCP_EXECUTION_CONFIG", explicit_path)],
+    ) else {
+        return;
+    };
+
+    assert!(
+        success && stdout.contains("REJECTED:"),
+        "expected the bridge to reject the explicit config's entry:\n\
+         stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        stdout.contains("DYLD_INSERT_LIBRARIES") && !stdout.contains("LD_PRELOAD"),
+        "the entry from MCP_EXECUTION_CONFIG must be used, not ~/.claude/mcp.json's: {stdout}"
+    );
+}
+
+/// A user config path baked in at generation time (`--home`, `MCP_EXECUTION_HOME`,
+/// `CLAUDE_CONFIG_DIR`) replaces the bridge's `os.homedir()` default, so a server generated for
+/// a sandboxed home runs against that home's `mcp.json` rather than the runtime user's.
+///
+/// Skips (does not fail) when `tsc` or `node` is not on `PATH`.
+#[test]
+fn test_runtime_bridge_reads_baked_user_config() {
+    let sandbox = tempfile::tempdir().expect("Failed to create temp dir");
+    let baked_path = sandbox.path().join("mcp.json");
+    let baked_json = json!({
+        "mcpServers": {
+            "github": {
+                "command": "node",
+                "args": ["--version"],
+                "env": { "DYLD_INSERT_LIBRARIES": "/tmp/evil.dylib" }
+            }
+        }
+    });
+    std::fs::write(&baked_path, baked_json.to_string()).expect("Failed to write baked config");
+
+    let generator = ProgressiveGenerator::new()
+        .expect("Failed to create generator")
+        .with_config_paths(BridgeConfigPaths {
+            user_config: Some(baked_path),
+            ..BridgeConfigPaths::default()
+        });
+    let code = generator
+        .generate(&create_test_server_info(), &test_config())
+        .expect("Failed to generate code");
+    let bridge = code
+        .files
+        .iter()
+        .find(|f| f.path == "_runtime/mcp-bridge.ts")
+        .expect("_runtime/mcp-bridge.ts not found");
+
+    let home_mcp_json = json!({
+        "mcpServers": {
+            "github": {
+                "command": "node",
+                "args": ["--version"],
+                "env": { "LD_PRELOAD": "/tmp/evil.so" }
+            }
+        }
+    });
+    let Some((success, stdout, stderr)) = run_bridge_harness_with_env(
+        "test_runtime_bridge_reads_baked_user_config",
+        &bridge.content,
+        &home_mcp_json,
+        "github",
+        &[],
+    ) else {
+        return;
+    };
+
+    assert!(
+        success && stdout.contains("REJECTED:"),
+        "expected the bridge to reject the baked config's entry:\n\
+         stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        stdout.contains("DYLD_INSERT_LIBRARIES") && !stdout.contains("LD_PRELOAD"),
+        "the baked user config must be used, not ~/.claude/mcp.json: {stdout}"
+    );
+}
+
+/// A `${VAR}` reference to an unset variable is rejected before spawn with a message naming the
+/// variable and the field, mirroring `build_core_config` on the Rust side.
+///
+/// Skips (does not fail) when `tsc` or `node` is not on `PATH`.
+#[test]
+// `${VAR}` is config syntax here, not a format argument.
+#[allow(clippy::literal_string_with_formatting_args)]
+fn test_runtime_bridge_rejects_unset_interpolated_variable() {
+    let generator = ProgressiveGenerator::new().expect("Failed to create generator");
+    let code = generator
+        .generate(&create_test_server_info(), &test_config())
+        .expect("Failed to generate code");
+    let bridge = code
+        .files
+        .iter()
+        .find(|f| f.path == "_runtime/mcp-bridge.ts")
+        .expect("_runtime/mcp-bridge.ts not found");
+
+    let mcp_json = json!({
+        "mcpServers": {
+            "github": {
+                "command": "node",
+                "args": ["--version"],
+                "env": { "GITHUB_TOKEN": "${MCP_EXECUTION_TEST_UNSET_GITHUB_TOKEN}" }
+            }
+        }
+    });
+
+    let Some((success, stdout, stderr)) = run_bridge_harness_with_env(
+        "test_runtime_bridge_rejects_unset_interpolated_variable",
+        &bridge.content,
+        &mcp_json,
+        "github",
+        &[],
+    ) else {
+        return;
+    };
+
+    assert!(
+        success && stdout.contains("REJECTED:"),
+        "expected the bridge to reject the unset variable:\nstdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        stdout.contains("env.GITHUB_TOKEN")
+            && stdout.contains("MCP_EXECUTION_TEST_UNSET_GITHUB_TOKEN"),
+        "rejection must name the field and the variable: {stdout}"
+    );
+}
+
+/// Expanded values are validated like literal ones: a shell metacharacter arriving through
+/// `${VAR}` is still rejected, and the expanded value never appears in the message.
+///
+/// Skips (does not fail) when `tsc` or `node` is not on `PATH`.
+#[test]
+// `${VAR}` is config syntax here, not a format argument.
+#[allow(clippy::literal_string_with_formatting_args)]
+fn test_runtime_bridge_validates_interpolated_values() {
+    let generator = ProgressiveGenerator::new().expect("Failed to create generator");
+    let code = generator
+        .generate(&create_test_server_info(), &test_config())
+        .expect("Failed to generate code");
+    let bridge = code
+        .files
+        .iter()
+        .find(|f| f.path == "_runtime/mcp-bridge.ts")
+        .expect("_runtime/mcp-bridge.ts not found");
+
+    let mcp_json = json!({
+        "mcpServers": {
+            "github": {
+                "command": "${MCP_EXECUTION_TEST_UNSET_BIN:-node}",
+                "args": ["--token", "${MCP_EXECUTION_TEST_INJECTED_ARG}"]
+            }
+        }
+    });
+
+    let Some((success, stdout, stderr)) = run_bridge_harness_with_env(
+        "test_runtime_bridge_validates_interpolated_values",
+        &bridge.content,
+        &mcp_json,
+        "github",
+        &[("MCP_EXECUTION_TEST_INJECTED_ARG", "sk-secret;rm -rf ~")],
+    ) else {
+        return;
+    };
+
+    assert!(
+        success && stdout.contains("REJECTED:"),
+        "expected the bridge to reject the expanded argument:\nstdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        !stdout.contains("sk-secret"),
+        "the expanded value must not be echoed: {stdout}"
+    );
+}
+
 /// Behavioral regression guard for #201: a hostile `~/.claude/mcp.json` entry (a forbidden
 /// `LD_PRELOAD` env var) must be rejected by the rendered `_runtime/mcp-bridge.ts` before it
 /// ever spawns the configured subprocess. Unlike a string-grep over the rendered source (see
@@ -2752,6 +2983,62 @@ fn test_runtime_bridge_rejects_when_child_exits_before_responding() {
     );
 }
 
+/// A server that echoes one of its arguments to stderr before exiting must have that argument
+/// redacted from the stderr report attached to the rejection and from the debug log, the same
+/// way the introspector redacts it.
+///
+/// Skips (does not fail) when `tsc` or `node` is not on `PATH`.
+#[test]
+fn test_runtime_bridge_redacts_argument_from_stderr_report() {
+    let generator = ProgressiveGenerator::new().expect("Failed to create generator");
+    let code = generator
+        .generate(&create_test_server_info(), &test_config())
+        .expect("Failed to generate code");
+    let bridge = code
+        .files
+        .iter()
+        .find(|f| f.path == "_runtime/mcp-bridge.ts")
+        .expect("_runtime/mcp-bridge.ts not found");
+
+    let script_path =
+        write_test_script("console.error(`bad flag ${process.argv[2]}`);\nprocess.exit(1);\n");
+    let mcp_json = json!({
+        "mcpServers": {
+            "github": {
+                "command": "node",
+                "args": [script_path, "--token=sk-arg-secret"]
+            }
+        }
+    });
+
+    let Some((success, stdout, stderr)) = run_bridge_harness_with_env(
+        "test_runtime_bridge_redacts_argument_from_stderr_report",
+        &bridge.content,
+        &mcp_json,
+        "github",
+        &[("MCPBRIDGE_DEBUG", "1")],
+    ) else {
+        return;
+    };
+
+    assert!(
+        success,
+        "bridge did not reject the dead server cleanly:\nstdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        stdout.contains("bad flag <redacted>"),
+        "expected the redacted stderr tail in the rejection: {stdout}"
+    );
+    assert!(
+        stderr.contains("<redacted>"),
+        "expected the redacted line in the debug log: {stderr}"
+    );
+    assert!(
+        !stdout.contains("sk-arg-secret") && !stderr.contains("sk-arg-secret"),
+        "the argument leaked: stdout: {stdout}, stderr: {stderr}"
+    );
+}
+
 /// #221 item 4 — a subprocess that spawns successfully but never replies at all must still
 /// fail with a clear timeout error rather than hang forever. Uses
 /// `MCPBRIDGE_REQUEST_TIMEOUT_MS` (well under the harness's 5s watchdog) so this test doesn't
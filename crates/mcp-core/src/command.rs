@@ -9,6 +9,7 @@
 //! - Command validation (absolute path or binary name)
 //! - Argument sanitization (no shell metacharacters)
 //! - Environment variable validation (block dangerous names)
+//! - Environment policy validation (bounded allowlist of valid names)
 //! - Executable permission checks (for absolute paths)
 //!
 //! # Examples
@@ -35,7 +36,7 @@
 //! assert!(err.is_security_error());
 //! ```
 
-use crate::{Error, Result, ServerConfig, Transport};
+use crate::{EnvPolicy, Error, Result, ServerConfig, Transport};
 use std::path::Path;
 use std::time::Duration;
 
@@ -408,12 +409,17 @@ pub const fn env_name_charset_desc() -> &'static str {
 pub fn validate_server_config(config: &ServerConfig) -> Result<()> {
     match config.transport() {
         Transport::Stdio {
-            command, args, env, ..
+            command,
+            args,
+            env,
+            env_policy,
+            ..
         } => {
             // Element counts/lengths (denial-of-service protection, CWE-400) are bounded
             // before the command-injection-specific checks below.
             validate_stdio_size_bounds(command, args, env)?;
             validate_stdio_config(command, args, env)?;
+            validate_env_policy(env_policy)?;
         }
         Transport::Http { url, headers } | Transport::Sse { url, headers } => {
             validate_network_size_bounds(url, headers)?;
@@ -590,6 +596,40 @@ fn validate_stdio_config(
     Ok(())
 }
 
+/// Validates an [`EnvPolicy::Allowlist`]: bounded like `env`, and every name a valid,
+/// non-forbidden environment variable name.
+///
+/// Names [`EnvPolicy::Minimal`] already passes (`PATH` among them) are accepted as redundant
+/// rather than rejected as forbidden: they come from the user's own environment, not from the
+/// config.
+fn validate_env_policy(policy: &EnvPolicy) -> Result<()> {
+    let EnvPolicy::Allowlist(names) = policy else {
+        return Ok(());
+    };
+    if names.len() > MAX_ENV_COUNT {
+        return Err(Error::SecurityViolation {
+            reason: format!(
+                "too many envPolicy allowlist entries: {} exceeds the {MAX_ENV_COUNT} limit",
+                names.len()
+            ),
+        });
+    }
+    for name in names {
+        if name.len() > MAX_ARG_LEN {
+            return Err(Error::SecurityViolation {
+                reason: format!(
+                    "envPolicy allowlist name too long: {} bytes exceeds the {MAX_ARG_LEN} limit",
+                    name.len()
+                ),
+            });
+        }
+        if !EnvPolicy::Minimal.allows(name) {
+            validate_env_name(name)?;
+        }
+    }
+    Ok(())
+}
+
 /// Validates the Http/Sse-transport-specific fields of a `ServerConfig`.
 ///
 /// `url` is a required field of [`Transport::Http`]/[`Transport::Sse`] (see #313), so unlike
@@ -923,6 +963,32 @@ mod tests {
     use std::fs;
     use std::io::Write;
 
+    #[test]
+    fn test_validate_env_policy_allowlist() {
+        let with_policy = |names: &[&str]| {
+            ServerConfig::builder()
+                .command("server".to_string())
+                .env_policy(EnvPolicy::Allowlist(
+                    names.iter().map(ToString::to_string).collect(),
+                ))
+                .build()
+        };
+
+        assert!(with_policy(&["AWS_PROFILE", "path"]).is_ok());
+        assert!(
+            with_policy(&["LD_PRELOAD"])
+                .unwrap_err()
+                .is_security_error()
+        );
+        assert!(
+            with_policy(&["NOT-A-NAME"])
+                .unwrap_err()
+                .is_security_error()
+        );
+        let too_many = vec!["A"; MAX_ENV_COUNT + 1];
+        assert!(with_policy(&too_many).unwrap_err().is_security_error());
+    }
+
     #[test]
     fn test_validate_server_config_binary_name() {
         // Binary names (not absolute paths) should be valid
@@ -560,6 +560,56 @@ pub fn open_confined_write(path: &Path) -> std::io::Result<std::fs::File> {
     options.open(path)
 }
 
+/// Opens `path` for reading, refusing to follow a symlink at that exact location.
+///
+/// The read-side counterpart of [`open_confined_write`], for serving files out of a confined
+/// directory: a symlink planted where a generated file is expected must not let a reader pull in
+/// a file from outside it. On Unix the open carries `O_NOFOLLOW`; elsewhere a
+/// [`std::fs::symlink_metadata`] pre-check narrows, but does not close, the same window.
+///
+/// Only the terminal component is guarded. Callers confine the directories above it themselves.
+///
+/// # Errors
+///
+/// Returns an error if the symlink check (non-Unix only) or the open failed — including the
+/// platform's "too many levels of symbolic links" error on Unix when `path`'s terminal component
+/// is a symlink.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::open_confined_read;
+/// use std::io::Read;
+/// use tempfile::TempDir;
+///
+/// let dir = TempDir::new().unwrap();
+/// let path = dir.path().join("index.ts");
+/// std::fs::write(&path, "export {};").unwrap();
+///
+/// let mut content = String::new();
+/// open_confined_read(&path).unwrap().read_to_string(&mut content).unwrap();
+/// assert_eq!(content, "export {};");
+/// ```
+pub fn open_confined_read(path: &Path) -> std::io::Result<std::fs::File> {
+    #[cfg(not(unix))]
+    if std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_symlink()) {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            "refusing to read through a symlink",
+        ));
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.read(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+
+    options.open(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1013,6 +1063,20 @@ mod tests {
         assert!(!outside_file.exists());
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn open_confined_read_rejects_a_symlink_at_the_target() {
+        let base = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let outside_file = outside.path().join("secret.txt");
+        std::fs::write(&outside_file, "outside").unwrap();
+
+        let confined_path = base.path().join("tool.ts");
+        std::os::unix::fs::symlink(&outside_file, &confined_path).unwrap();
+
+        assert!(open_confined_read(&confined_path).is_err());
+    }
+
     #[cfg(windows)]
     #[tokio::test]
     async fn windows_root_relative_intermediate_cannot_escape_base() {
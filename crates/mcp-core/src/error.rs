@@ -210,6 +210,21 @@ pub enum Error {
         /// The path that was already present when a second file was added at it.
         path: String,
     },
+
+    /// A stdio MCP server failed and had written to stderr.
+    ///
+    /// Wraps the underlying failure (usually [`Error::ConnectionFailed`] or [`Error::Timeout`])
+    /// so a server that exits with e.g. "missing `GITHUB_TOKEN`" explains itself. `stderr` is
+    /// the bounded tail the introspector kept, with secrets redacted and control characters
+    /// removed line by line.
+    #[error("MCP server failed; its stderr ended with: {stderr}")]
+    ServerStderr {
+        /// Last lines the server wrote to stderr.
+        stderr: String,
+        /// The failure itself
+        #[source]
+        source: Box<Self>,
+    },
 }
 
 impl Error {
@@ -288,6 +303,30 @@ impl Error {
     pub const fn is_resource_limit_exceeded(&self) -> bool {
         matches!(self, Self::ResourceLimitExceeded { .. })
     }
+
+    /// Returns the server stderr tail attached by [`Error::ServerStderr`], if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::Error;
+    ///
+    /// let err = Error::ServerStderr {
+    ///     stderr: "missing GITHUB_TOKEN".to_string(),
+    ///     source: Box::new(Error::Timeout {
+    ///         operation: "connect".to_string(),
+    ///         duration_secs: 30,
+    ///     }),
+    /// };
+    /// assert_eq!(err.server_stderr(), Some("missing GITHUB_TOKEN"));
+    /// ```
+    #[must_use]
+    pub fn server_stderr(&self) -> Option<&str> {
+        match self {
+            Self::ServerStderr { stderr, .. } => Some(stderr),
+            _ => None,
+        }
+    }
 }
 
 /// Result type alias for MCP operations.
@@ -353,6 +392,25 @@ mod tests {
         assert!(display.contains("1000"));
     }
 
+    #[test]
+    fn test_server_stderr_keeps_wrapped_failure_as_source() {
+        use std::error::Error as _;
+
+        let err = Error::ServerStderr {
+            stderr: "missing GITHUB_TOKEN".to_string(),
+            source: Box::new(Error::Timeout {
+                operation: "connect".to_string(),
+                duration_secs: 30,
+            }),
+        };
+        assert!(err.to_string().contains("missing GITHUB_TOKEN"));
+        assert!(err.source().unwrap().to_string().contains("timed out"));
+        assert_eq!(
+            Error::InvalidArgument("x".to_string()).server_stderr(),
+            None
+        );
+    }
+
     #[test]
     fn test_resource_kind_display_variants() {
         assert_eq!(ResourceKind::ToolNameLength.to_string(), "tool name length");
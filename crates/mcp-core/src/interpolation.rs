@@ -0,0 +1,416 @@
+//! `${VAR}` and `${VAR:-default}` expansion for MCP server config entries.
+//!
+//! Real-world `mcp.json` files keep secrets out of the file by referencing the environment:
+//! `"env": {"GITHUB_TOKEN": "${GITHUB_TOKEN}"}`, `"headers": {"Authorization": "Bearer
+//! ${API_KEY}"}`. [`expand_transport`] resolves those references in every string-valued field
+//! of a [`Transport`] — `command`, each of `args`, every `env` value and `cwd` for stdio;
+//! `url` and every `headers` value for HTTP/SSE. Map *keys* (variable and header names) are
+//! never expanded.
+//!
+//! The generated TypeScript runtime bridge (`runtime-bridge.ts`) implements the same grammar,
+//! field labels and error reasons, so a server resolves identically whether it is spawned by
+//! the CLI or by generated code.
+//!
+//! # Syntax
+//!
+//! - `${NAME}` expands to the value of `NAME`; an unset variable is an error. A variable set to
+//!   the empty string expands to the empty string.
+//! - `${NAME:-default}` expands to the value of `NAME`, or to `default` (taken literally, never
+//!   expanded itself) when `NAME` is unset *or empty* — the POSIX shell semantics.
+//! - `NAME` must match `[A-Za-z_][A-Za-z0-9_]*`.
+//! - A `$` not followed by `{` is kept literally, so `$HOME` and `$1` pass through untouched.
+//!   There is no escape for a literal `${`.
+//!
+//! # Security
+//!
+//! Expanded values are routinely secrets, so no error raised here ever contains a variable's
+//! value, a default, or the surrounding field text — only the field's label (e.g.
+//! `env.GITHUB_TOKEN`, `args[2]`) and, for an unset variable, its name. Expansion happens
+//! *before* [`crate::validate_server_config`] runs, so an expanded value is held to exactly the
+//! same rules as a literal one (a `;` smuggled in through `${VAR}` is still rejected).
+//!
+//! [`ConfigFingerprint`](crate::provenance::ConfigFingerprint) is computed over the
+//! *unexpanded* transport (see [`ServerConfig::unexpanded_transport`]), so rotating a secret
+//! behind `${VAR}` is not reported as config drift.
+//!
+//! # Examples
+//!
+//! ```
+//! use mcp_execution_core::interpolation::expand_vars;
+//!
+//! let lookup = |name: &str| (name == "API_KEY").then(|| "sk-123".to_string());
+//!
+//! assert_eq!(
+//!     expand_vars("Bearer ${API_KEY}", "headers.Authorization", lookup).unwrap(),
+//!     "Bearer sk-123"
+//! );
+//! assert_eq!(
+//!     expand_vars("${REGION:-us-east-1}", "url", lookup).unwrap(),
+//!     "us-east-1"
+//! );
+//!
+//! let err = expand_vars("${MISSING}", "env.TOKEN", lookup).unwrap_err();
+//! assert!(err.to_string().contains("MISSING"));
+//! ```
+//!
+//! [`ServerConfig::unexpanded_transport`]: crate::ServerConfig::unexpanded_transport
+
+use crate::{Error, Result, Transport};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Separator between a variable name and its default value inside `${...}`.
+const DEFAULT_SEPARATOR: &str = ":-";
+
+/// Looks `name` up in the current process environment.
+///
+/// A variable whose value is not valid UTF-8 is treated as unset. This is the lookup the CLI
+/// passes to [`ServerConfigBuilder::build_interpolated`](crate::ServerConfigBuilder::build_interpolated).
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::interpolation::process_env;
+///
+/// assert!(process_env("MCP_EXECUTION_SURELY_UNSET_VARIABLE").is_none());
+/// ```
+#[must_use]
+pub fn process_env(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Expands every `${NAME}` / `${NAME:-default}` reference in `input`.
+///
+/// `field` labels `input` in error messages (e.g. `command`, `env.GITHUB_TOKEN`); `lookup`
+/// resolves a variable name to its value, returning `None` when it is unset.
+///
+/// # Errors
+///
+/// Returns [`Error::ValidationError`] with `field` as the field if:
+/// - a `${` has no closing `}`
+/// - a reference's name is empty or not of the form `[A-Za-z_][A-Za-z0-9_]*`
+/// - a `${NAME}` reference without a default names a variable `lookup` reports as unset
+///
+/// The message names the variable in the last case and never includes any value.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::interpolation::expand_vars;
+///
+/// let lookup = |_: &str| None;
+/// assert_eq!(expand_vars("cost: $5", "args[0]", lookup).unwrap(), "cost: $5");
+/// assert!(expand_vars("${UNCLOSED", "args[0]", lookup).is_err());
+/// ```
+pub fn expand_vars<F>(input: &str, field: &str, lookup: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find('}') else {
+            return Err(invalid(field, "unterminated '${' reference (missing '}')"));
+        };
+        let reference = &after_open[..end];
+
+        let (name, default) = match reference.split_once(DEFAULT_SEPARATOR) {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+        if !is_valid_name(name) {
+            return Err(invalid(
+                field,
+                "invalid '${...}' reference: variable names must match [A-Za-z_][A-Za-z0-9_]*",
+            ));
+        }
+
+        let value = lookup(name);
+        match (value, default) {
+            (Some(value), Some(default)) if value.is_empty() => output.push_str(default),
+            (Some(value), _) => output.push_str(&value),
+            (None, Some(default)) => output.push_str(default),
+            (None, None) => {
+                return Err(invalid(
+                    field,
+                    &format!("environment variable '{name}' is not set"),
+                ));
+            }
+        }
+
+        rest = &after_open[end + 1..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Returns a copy of `transport` with every `${...}` reference expanded.
+///
+/// Expanded fields are `command`, each of `args`, each `env` value, and `cwd` (when it is valid
+/// UTF-8) for stdio; `url` and each `headers` value for HTTP/SSE. Map keys are left as written.
+///
+/// # Errors
+///
+/// Returns the first [`expand_vars`] error, labelled with the offending field
+/// (`command`, `args[N]`, `env.NAME`, `cwd`, `url`, or `headers.NAME`).
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::interpolation::expand_transport;
+/// use mcp_execution_core::Transport;
+/// use std::collections::HashMap;
+///
+/// let transport = Transport::Http {
+///     url: "https://${HOST:-api.example.com}/mcp".to_string(),
+///     headers: HashMap::from([(
+///         "Authorization".to_string(),
+///         "Bearer ${API_KEY}".to_string(),
+///     )]),
+/// };
+///
+/// let err = expand_transport(&transport, |_| None).unwrap_err();
+/// assert!(err.to_string().contains("headers.Authorization"));
+/// assert!(err.to_string().contains("API_KEY"));
+/// ```
+pub fn expand_transport<F>(transport: &Transport, lookup: F) -> Result<Transport>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match transport {
+        Transport::Stdio {
+            command,
+            args,
+            env,
+            cwd,
+            env_policy,
+        } => {
+            let command = expand_vars(command, "command", &lookup)?;
+            let args = args
+                .iter()
+                .enumerate()
+                .map(|(index, arg)| expand_vars(arg, &format!("args[{index}]"), &lookup))
+                .collect::<Result<Vec<_>>>()?;
+            let env = expand_map_values(env, "env", &lookup)?;
+            let cwd = cwd
+                .as_ref()
+                .map(|path| {
+                    path.to_str().map_or_else(
+                        || Ok(path.clone()),
+                        |text| expand_vars(text, "cwd", &lookup).map(PathBuf::from),
+                    )
+                })
+                .transpose()?;
+
+            Ok(Transport::Stdio {
+                command,
+                args,
+                env,
+                cwd,
+                env_policy: env_policy.clone(),
+            })
+        }
+        Transport::Http { url, headers } => Ok(Transport::Http {
+            url: expand_vars(url, "url", &lookup)?,
+            headers: expand_map_values(headers, "headers", &lookup)?,
+        }),
+        Transport::Sse { url, headers } => Ok(Transport::Sse {
+            url: expand_vars(url, "url", &lookup)?,
+            headers: expand_map_values(headers, "headers", &lookup)?,
+        }),
+    }
+}
+
+/// Expands every value of `map`, labelling errors `{prefix}.{key}`.
+///
+/// Keys are visited in sorted order so that, when several values fail, the reported error does
+/// not depend on `HashMap` iteration order.
+fn expand_map_values<F>(
+    map: &HashMap<String, String>,
+    prefix: &str,
+    lookup: &F,
+) -> Result<HashMap<String, String>>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort_unstable();
+
+    keys.into_iter()
+        .map(|key| {
+            let value = expand_vars(&map[key], &format!("{prefix}.{key}"), lookup)?;
+            Ok((key.clone(), value))
+        })
+        .collect()
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn invalid(field: &str, reason: &str) -> Error {
+    Error::ValidationError {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+#[cfg(test)]
+// `${VAR}` is config syntax throughout these tests, not a format argument.
+#[allow(clippy::literal_string_with_formatting_args)]
+mod tests {
+    use super::*;
+    use crate::EnvPolicy;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "TOKEN" => Some("sk-secret-value".to_string()),
+            "EMPTY" => Some(String::new()),
+            "HOST" => Some("api.example.com".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_expand_vars_replaces_references() {
+        assert_eq!(
+            expand_vars("Bearer ${TOKEN}", "f", lookup).unwrap(),
+            "Bearer sk-secret-value"
+        );
+        assert_eq!(
+            expand_vars("https://${HOST}/${HOST}", "f", lookup).unwrap(),
+            "https://api.example.com/api.example.com"
+        );
+    }
+
+    #[test]
+    fn test_expand_vars_leaves_plain_text_and_bare_dollars() {
+        assert_eq!(expand_vars("no refs", "f", lookup).unwrap(), "no refs");
+        assert_eq!(
+            expand_vars("$HOME $1 $", "f", lookup).unwrap(),
+            "$HOME $1 $"
+        );
+        assert_eq!(expand_vars("", "f", lookup).unwrap(), "");
+    }
+
+    #[test]
+    fn test_expand_vars_default_applies_when_unset_or_empty() {
+        assert_eq!(expand_vars("${MISSING:-d}", "f", lookup).unwrap(), "d");
+        assert_eq!(expand_vars("${EMPTY:-d}", "f", lookup).unwrap(), "d");
+        assert_eq!(
+            expand_vars("${TOKEN:-d}", "f", lookup).unwrap(),
+            "sk-secret-value"
+        );
+        assert_eq!(expand_vars("${MISSING:-}", "f", lookup).unwrap(), "");
+    }
+
+    #[test]
+    fn test_expand_vars_default_is_literal() {
+        assert_eq!(
+            expand_vars("${MISSING:-$TOKEN:-x}", "f", lookup).unwrap(),
+            "$TOKEN:-x"
+        );
+    }
+
+    #[test]
+    fn test_expand_vars_empty_value_without_default_is_empty() {
+        assert_eq!(expand_vars("a${EMPTY}b", "f", lookup).unwrap(), "ab");
+    }
+
+    #[test]
+    fn test_expand_vars_missing_variable_names_variable_and_field() {
+        let err = expand_vars("prefix ${GITHUB_TOKEN} suffix", "env.GH", lookup).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("GITHUB_TOKEN"), "{message}");
+        assert!(message.contains("env.GH"), "{message}");
+        assert!(!message.contains("prefix"), "{message}");
+    }
+
+    #[test]
+    fn test_expand_vars_errors_never_leak_values() {
+        for input in ["${TOKEN}${MISSING}", "${TOKEN}${bad name}", "${TOKEN}${"] {
+            let err = expand_vars(input, "f", lookup).unwrap_err();
+            assert!(!err.to_string().contains("sk-secret-value"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_expand_vars_rejects_malformed_references() {
+        for input in [
+            "${UNCLOSED",
+            "${}",
+            "${1ABC}",
+            "${A-B}",
+            "${:-x}",
+            "${ TOKEN}",
+        ] {
+            assert!(
+                expand_vars(input, "f", lookup).is_err(),
+                "{input} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_transport_stdio_fields() {
+        let transport = Transport::Stdio {
+            command: "${MISSING:-npx}".to_string(),
+            args: vec!["--host".to_string(), "${HOST}".to_string()],
+            env: HashMap::from([("GITHUB_TOKEN".to_string(), "${TOKEN}".to_string())]),
+            cwd: Some(PathBuf::from("/srv/${MISSING:-app}")),
+            env_policy: EnvPolicy::default(),
+        };
+
+        let Transport::Stdio {
+            command,
+            args,
+            env,
+            cwd,
+            env_policy: _,
+        } = expand_transport(&transport, lookup).unwrap()
+        else {
+            panic!("expected stdio transport");
+        };
+        assert_eq!(command, "npx");
+        assert_eq!(args, vec!["--host", "api.example.com"]);
+        assert_eq!(env["GITHUB_TOKEN"], "sk-secret-value");
+        assert_eq!(cwd, Some(PathBuf::from("/srv/app")));
+    }
+
+    #[test]
+    fn test_expand_transport_http_fields_and_keys_untouched() {
+        let transport = Transport::Sse {
+            url: "https://${HOST}/sse".to_string(),
+            headers: HashMap::from([("X-${HOST}".to_string(), "Bearer ${TOKEN}".to_string())]),
+        };
+
+        let Transport::Sse { url, headers } = expand_transport(&transport, lookup).unwrap() else {
+            panic!("expected sse transport");
+        };
+        assert_eq!(url, "https://api.example.com/sse");
+        assert_eq!(headers["X-${HOST}"], "Bearer sk-secret-value");
+    }
+
+    #[test]
+    fn test_expand_transport_labels_failing_arg() {
+        let transport = Transport::Stdio {
+            command: "npx".to_string(),
+            args: vec!["ok".to_string(), "${NOPE}".to_string()],
+            env: HashMap::new(),
+            cwd: None,
+            env_policy: EnvPolicy::default(),
+        };
+
+        let err = expand_transport(&transport, lookup).unwrap_err();
+        assert!(err.to_string().contains("args[1]"), "{err}");
+        assert!(err.to_string().contains("NOPE"), "{err}");
+    }
+}
@@ -40,8 +40,12 @@ mod server_config;
 mod types;
 
 pub mod cli;
+pub mod interpolation;
+pub mod mcp_config;
 pub mod metadata;
+pub mod paths;
 pub mod provenance;
+pub mod tool_filter;
 pub mod untrusted;
 
 // Re-export error types
@@ -54,7 +58,10 @@ pub use types::{
 };
 
 // Re-export server configuration types
-pub use server_config::{ServerConfig, ServerConfigBuilder, Transport};
+pub use server_config::{
+    EnvPolicy, REDACTED_CONFIG_VALUE, ServerConfig, ServerConfigBuilder, Transport,
+    minimal_env_names,
+};
 
 // Re-export command validation
 pub use command::{
@@ -72,11 +79,12 @@ pub use path::{
 
 // Re-export the shared path-confinement walk
 pub use confinement::{
-    ConfinementError, ConfinementTarget, open_confined_write, resolve_confined_path,
-    write_confined_file,
+    ConfinementError, ConfinementTarget, open_confined_read, open_confined_write,
+    resolve_confined_path, write_confined_file,
 };
 
 // Re-export Debug-redaction helpers shared by secret-shaped fields
 pub use redact::{
-    REDACTED_PLACEHOLDER, RedactedItems, RedactedMapValues, RedactedUrl, redact_urls_in_text,
+    REDACTED_PLACEHOLDER, RedactedItems, RedactedMapValues, RedactedUrl, redact_secrets_in_text,
+    redact_urls_in_text,
 };
@@ -0,0 +1,1461 @@
+//! Discovery of MCP server definitions across the config files MCP hosts read.
+//!
+//! Server definitions are not kept in one place: Claude Code reads a project-scoped `.mcp.json`
+//! and per-project and user entries in `~/.claude.json`, this project has historically read
+//! `~/.claude/mcp.json`, and Claude Desktop keeps its own `claude_desktop_config.json`.
+//! [`McpConfigLocations`] finds all of them and [`McpConfigLocations::load`] merges their
+//! `mcpServers` maps into one [`LayeredMcpConfig`], remembering which file each server came
+//! from.
+//!
+//! # Precedence
+//!
+//! When the same server name is defined in several places, the first of these wins:
+//!
+//! 1. **Local**: the `projects["<project dir>"].mcpServers` entry in `~/.claude.json`, for the
+//!    project directory or its nearest ancestor that has one (Claude Code's "local" scope).
+//! 2. **Project**: `.mcp.json` in the project directory or its nearest ancestor that has one.
+//! 3. **User**: `~/.claude/mcp.json`, then the top-level `mcpServers` in `~/.claude.json`.
+//! 4. **Desktop**: Claude Desktop's `claude_desktop_config.json`.
+//!
+//! An explicit config file — `--config <path>` or the [`CONFIG_OVERRIDE_ENV`] environment
+//! variable — replaces discovery entirely: only that file is read.
+//!
+//! The generated TypeScript runtime bridge (`_runtime/mcp-bridge.ts`) resolves servers with the
+//! same rules, so a server introspected from one file is never executed from another.
+//!
+//! # Examples
+//!
+//! ```
+//! use mcp_execution_core::mcp_config::{ConfigScope, McpConfigLocations};
+//!
+//! let home = tempfile::tempdir().unwrap();
+//! std::fs::create_dir(home.path().join(".claude")).unwrap();
+//! std::fs::write(
+//!     home.path().join(".claude").join("mcp.json"),
+//!     r#"{"mcpServers": {"github": {"command": "github-mcp-server"}}}"#,
+//! )
+//! .unwrap();
+//!
+//! let config = McpConfigLocations::new()
+//!     .with_home_dir(home.path())
+//!     .load()
+//!     .unwrap();
+//! let github = config.get("github").unwrap();
+//! assert_eq!(github.source.scope, ConfigScope::User);
+//! assert_eq!(github.entry["command"], "github-mcp-server");
+//! ```
+
+use crate::path::sanitize_path_for_error;
+use crate::paths::Paths;
+use crate::{EnvPolicy, ServerConfig};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error as ThisError;
+
+/// Environment variable naming an explicit MCP config file, used when no `--config` is given.
+///
+/// Read by both the CLI and the generated runtime bridge, so pointing both at the same file
+/// only takes setting it once.
+pub const CONFIG_OVERRIDE_ENV: &str = "MCP_EXECUTION_CONFIG";
+
+/// File name of a project-scoped MCP config.
+pub const PROJECT_CONFIG_FILE_NAME: &str = ".mcp.json";
+
+/// Upper bound on the size of a config file [`McpConfigLocations::load`] reads.
+///
+/// `~/.claude.json` also holds Claude Code's per-project history and can grow to several
+/// megabytes, so this is well above what an `mcp.json` ever needs.
+const MAX_CONFIG_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Which layer of the [precedence](self#precedence) a server definition came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigScope {
+    /// An explicit `--config` file or [`CONFIG_OVERRIDE_ENV`].
+    Explicit,
+    /// A per-project entry in `~/.claude.json`.
+    Local,
+    /// A project-scoped `.mcp.json`.
+    Project,
+    /// `~/.claude/mcp.json` or the top level of `~/.claude.json`.
+    User,
+    /// Claude Desktop's `claude_desktop_config.json`.
+    Desktop,
+}
+
+impl ConfigScope {
+    /// Lowercase name, as serialized.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Explicit => "explicit",
+            Self::Local => "local",
+            Self::Project => "project",
+            Self::User => "user",
+            Self::Desktop => "desktop",
+        }
+    }
+}
+
+impl fmt::Display for ConfigScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The file, and layer, a server definition was read from.
+///
+/// `Display` sanitizes the path (see [`crate::sanitize_path_for_error`]), so it is safe to put
+/// in error messages.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigSource {
+    /// Layer the file belongs to.
+    pub scope: ConfigScope,
+    /// Path of the file.
+    pub path: PathBuf,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} config {}",
+            self.scope,
+            sanitize_path_for_error(&self.path)
+        )
+    }
+}
+
+/// Error returned by [`McpConfigLocations::load`] and [`McpConfigDocument`].
+#[derive(Debug, ThisError)]
+pub enum McpConfigError {
+    /// A file that must exist (the explicit config file, or one passed to
+    /// [`read_server_table`]) does not.
+    #[error("MCP config file {path} does not exist")]
+    NotFound {
+        /// Sanitized path of the file (see [`crate::sanitize_path_for_error`]).
+        path: String,
+    },
+    /// A config file exists but could not be read.
+    #[error("failed to read MCP config {path}: {source}")]
+    Io {
+        /// Sanitized path of the file.
+        path: String,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// A config file is larger than any real config could be.
+    #[error("MCP config {path} is {size} bytes, exceeding the {limit} byte limit")]
+    TooLarge {
+        /// Sanitized path of the file.
+        path: String,
+        /// Observed size in bytes.
+        size: u64,
+        /// Maximum accepted size in bytes.
+        limit: u64,
+    },
+    /// A config file is not valid JSON.
+    #[error("failed to parse MCP config {path}: {source}")]
+    Parse {
+        /// Sanitized path of the file.
+        path: String,
+        /// Underlying parse error.
+        #[source]
+        source: serde_json::Error,
+    },
+    /// A config file is valid JSON but not shaped like an MCP config.
+    #[error("invalid MCP config {path}: {reason}")]
+    Invalid {
+        /// Sanitized path of the file.
+        path: String,
+        /// What is wrong with it.
+        reason: String,
+    },
+    /// A config file could not be written.
+    #[error("failed to write MCP config {path}: {source}")]
+    Write {
+        /// Sanitized path of the file.
+        path: String,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Where to look for MCP config files.
+///
+/// [`discover`](Self::discover) fills in the standard locations for the current process;
+/// [`new`](Self::new) starts from none, so tests and embedders can point each one somewhere
+/// specific.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::mcp_config::McpConfigLocations;
+///
+/// let locations = McpConfigLocations::new().with_explicit("/etc/mcp/servers.json");
+/// assert!(locations.explicit().is_some());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct McpConfigLocations {
+    explicit: Option<PathBuf>,
+    project_dir: Option<PathBuf>,
+    paths: Option<Paths>,
+    desktop_config: Option<PathBuf>,
+}
+
+impl McpConfigLocations {
+    /// Creates locations with nothing to search.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Standard locations for the current process.
+    ///
+    /// `explicit` (typically `--config`) falls back to [`CONFIG_OVERRIDE_ENV`] when `None`. The
+    /// project directory is the current directory, the home and `.claude` directory come from
+    /// [`Paths::discover`], and the desktop config is
+    /// `claude_desktop_config.json` under the platform config directory's `Claude` folder.
+    #[must_use]
+    pub fn discover(explicit: Option<PathBuf>) -> Self {
+        let explicit = explicit.or_else(|| {
+            std::env::var_os(CONFIG_OVERRIDE_ENV)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        });
+        Self {
+            explicit,
+            project_dir: std::env::current_dir().ok(),
+            paths: Paths::discover(None),
+            desktop_config: dirs::config_dir()
+                .map(|dir| dir.join("Claude").join("claude_desktop_config.json")),
+        }
+    }
+
+    /// Reads only `path`, skipping discovery.
+    #[must_use]
+    pub fn with_explicit(mut self, path: impl Into<PathBuf>) -> Self {
+        self.explicit = Some(path.into());
+        self
+    }
+
+    /// Sets the directory whose project config applies.
+    #[must_use]
+    pub fn with_project_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.project_dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the home directory holding `.claude/mcp.json` and `.claude.json`.
+    #[must_use]
+    pub fn with_home_dir(self, dir: impl Into<PathBuf>) -> Self {
+        self.with_paths(Paths::new(dir))
+    }
+
+    /// Sets the home and `.claude` directories together.
+    #[must_use]
+    pub fn with_paths(mut self, paths: Paths) -> Self {
+        self.paths = Some(paths);
+        self
+    }
+
+    /// Sets the path of Claude Desktop's config file.
+    #[must_use]
+    pub fn with_desktop_config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.desktop_config = Some(path.into());
+        self
+    }
+
+    /// The explicit config file, if one replaces discovery.
+    #[must_use]
+    pub fn explicit(&self) -> Option<&Path> {
+        self.explicit.as_deref()
+    }
+
+    /// The directory whose project config applies, if known.
+    #[must_use]
+    pub fn project_dir(&self) -> Option<&Path> {
+        self.project_dir.as_deref()
+    }
+
+    /// The home directory, if known.
+    #[must_use]
+    pub fn home_dir(&self) -> Option<&Path> {
+        self.paths.as_ref().map(Paths::home)
+    }
+
+    /// The home and `.claude` directories, if known.
+    #[must_use]
+    pub const fn paths(&self) -> Option<&Paths> {
+        self.paths.as_ref()
+    }
+
+    /// The path of Claude Desktop's config file, if known.
+    #[must_use]
+    pub fn desktop_config(&self) -> Option<&Path> {
+        self.desktop_config.as_deref()
+    }
+
+    /// `~/.claude/mcp.json`, this project's own user-level config file.
+    #[must_use]
+    pub fn user_config_path(&self) -> Option<PathBuf> {
+        self.paths.as_ref().map(Paths::user_mcp_config)
+    }
+
+    /// The file server-management commands edit: the explicit file if one is set, otherwise
+    /// [`user_config_path`](Self::user_config_path).
+    ///
+    /// Other layers (`.mcp.json`, `~/.claude.json`, Claude Desktop's config) belong to other
+    /// tools and are only ever read.
+    #[must_use]
+    pub fn writable_config(&self) -> Option<ConfigSource> {
+        self.explicit.as_ref().map_or_else(
+            || {
+                self.user_config_path().map(|path| ConfigSource {
+                    scope: ConfigScope::User,
+                    path,
+                })
+            },
+            |path| {
+                Some(ConfigSource {
+                    scope: ConfigScope::Explicit,
+                    path: path.clone(),
+                })
+            },
+        )
+    }
+
+    /// Every existing file [`load`](Self::load) would read, in precedence order.
+    ///
+    /// Useful for watching the config for changes.
+    #[must_use]
+    pub fn existing_files(&self) -> Vec<PathBuf> {
+        if let Some(explicit) = &self.explicit {
+            return vec![explicit.clone()];
+        }
+        let mut files = Vec::new();
+        files.extend(self.claude_json_path());
+        files.extend(self.project_config_path());
+        files.extend(self.user_config_path());
+        files.extend(self.desktop_config.clone());
+        files.retain(|path| path.is_file());
+        files.dedup();
+        files
+    }
+
+    /// Reads every config file and merges their servers by [precedence](self#precedence).
+    ///
+    /// Files that do not exist are skipped, so a machine with no config at all yields an empty
+    /// [`LayeredMcpConfig`]. A file without an `mcpServers` key contributes nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpConfigError`] if the explicit file does not exist, or any file that exists
+    /// cannot be read, is implausibly large, is not valid JSON, or has an `mcpServers` value
+    /// that is not an object.
+    pub fn load(&self) -> Result<LayeredMcpConfig, McpConfigError> {
+        let mut config = LayeredMcpConfig::default();
+
+        if let Some(path) = &self.explicit {
+            let document = read_config_document(path)?.ok_or_else(|| McpConfigError::NotFound {
+                path: sanitize_path_for_error(path),
+            })?;
+            config.add_layer(ConfigScope::Explicit, path, &document, None)?;
+            return Ok(config);
+        }
+
+        let claude_json = self
+            .claude_json_path()
+            .map(|path| read_config_document(&path).map(|document| (path, document)))
+            .transpose()?
+            .and_then(|(path, document)| document.map(|document| (path, document)));
+
+        if let (Some((path, document)), Some(project_dir)) = (&claude_json, &self.project_dir)
+            && let Some(project) = nearest_project_entry(document, project_dir)
+        {
+            config.add_layer(ConfigScope::Local, path, project, Some("projects"))?;
+        }
+        if let Some(path) = self.project_config_path()
+            && let Some(document) = read_config_document(&path)?
+        {
+            config.add_layer(ConfigScope::Project, &path, &document, None)?;
+        }
+        if let Some(path) = self.user_config_path()
+            && let Some(document) = read_config_document(&path)?
+        {
+            config.add_layer(ConfigScope::User, &path, &document, None)?;
+        }
+        if let Some((path, document)) = &claude_json {
+            config.add_layer(ConfigScope::User, path, document, None)?;
+        }
+        if let Some(path) = &self.desktop_config
+            && let Some(document) = read_config_document(path)?
+        {
+            config.add_layer(ConfigScope::Desktop, path, &document, None)?;
+        }
+
+        Ok(config)
+    }
+
+    /// `~/.claude.json`, Claude Code's own config file.
+    #[must_use]
+    pub fn claude_json_path(&self) -> Option<PathBuf> {
+        self.paths.as_ref().map(Paths::claude_json)
+    }
+
+    /// The `.mcp.json` in the project directory or its nearest ancestor that has one.
+    fn project_config_path(&self) -> Option<PathBuf> {
+        self.project_dir.as_ref()?.ancestors().find_map(|dir| {
+            let candidate = dir.join(PROJECT_CONFIG_FILE_NAME);
+            candidate.is_file().then_some(candidate)
+        })
+    }
+}
+
+/// A server definition found by [`McpConfigLocations::load`].
+///
+/// `entry` is the raw JSON object from the file; [`to_server_config`](Self::to_server_config)
+/// interprets it. Its `Debug` output lists only the entry's keys, since values such as `env`
+/// and `headers` routinely hold secrets.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResolvedServerEntry {
+    /// Where the definition was read from.
+    pub source: ConfigSource,
+    /// The definition as written under `mcpServers`.
+    pub entry: Value,
+}
+
+impl fmt::Debug for ResolvedServerEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<&str> = self
+            .entry
+            .as_object()
+            .map(|entry| entry.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        f.debug_struct("ResolvedServerEntry")
+            .field("source", &self.source)
+            .field("entry_keys", &keys)
+            .finish()
+    }
+}
+
+/// The fields of an `mcpServers` entry [`ResolvedServerEntry::to_server_config`] reads. Other
+/// keys (`disabled`, `alwaysAllow`, ...) belong to other MCP clients and are ignored.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawServerEntry {
+    #[serde(rename = "type")]
+    transport: Option<String>,
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: HashMap<String, String>,
+    cwd: Option<PathBuf>,
+    env_policy: Option<EnvPolicy>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    connect_timeout_secs: Option<u64>,
+    discover_timeout_secs: Option<u64>,
+}
+
+impl ResolvedServerEntry {
+    /// Builds the [`ServerConfig`] this entry describes, expanding `${VAR}` references against
+    /// the process environment.
+    ///
+    /// Follows the same rules as the CLI: the transport is `type`, or stdio when only
+    /// `command` is set and http when only `url` is; stdio entries must not set `url`, and
+    /// http/sse entries must not set `command` or `envPolicy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`crate::Error::ValidationError`] if the entry is not shaped like a server
+    /// definition, and any error [`crate::ServerConfigBuilder::build_interpolated`] returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::mcp_config::{ConfigScope, ConfigSource, ResolvedServerEntry};
+    /// use std::path::PathBuf;
+    ///
+    /// let resolved = ResolvedServerEntry {
+    ///     source: ConfigSource { scope: ConfigScope::User, path: PathBuf::from("mcp.json") },
+    ///     entry: serde_json::json!({"type": "http", "url": "https://api.example.com/mcp"}),
+    /// };
+    /// let config = resolved.to_server_config().unwrap();
+    /// assert_eq!(config.url(), Some("https://api.example.com/mcp"));
+    /// ```
+    pub fn to_server_config(&self) -> crate::Result<ServerConfig> {
+        let invalid = |reason: String| crate::Error::ValidationError {
+            field: "mcpServers entry".to_string(),
+            reason,
+        };
+        let raw = RawServerEntry::deserialize(&self.entry)
+            .map_err(|e| invalid(format!("{e} in {}", self.source)))?;
+
+        let transport = match raw.transport.as_deref() {
+            Some(transport) => transport,
+            None if raw.command.is_some() => "stdio",
+            None if raw.url.is_some() => "http",
+            None => {
+                return Err(invalid(
+                    "server entry must set either \"command\" (stdio) or \"type\" and \"url\" \
+                     (http/sse)"
+                        .to_string(),
+                ));
+            }
+        };
+
+        let mut builder = match (transport, raw.command, raw.url) {
+            ("stdio", _, Some(_)) => {
+                return Err(invalid(
+                    "stdio server entry must not set \"url\"".to_string(),
+                ));
+            }
+            ("stdio", Some(command), None) => {
+                let mut builder = ServerConfig::builder()
+                    .command(command)
+                    .args(raw.args)
+                    .env_policy(raw.env_policy.unwrap_or_default());
+                for (key, value) in raw.env {
+                    builder = builder.env(key, value);
+                }
+                if let Some(cwd) = raw.cwd {
+                    builder = builder.cwd(cwd);
+                }
+                builder
+            }
+            ("stdio", None, None) => {
+                return Err(invalid(
+                    "stdio server entry requires \"command\"".to_string(),
+                ));
+            }
+            ("http" | "sse", Some(_), _) => {
+                return Err(invalid(format!(
+                    "{transport} server entry must not set \"command\""
+                )));
+            }
+            ("http" | "sse", None, _) if raw.env_policy.is_some() => {
+                return Err(invalid(format!(
+                    "{transport} server entry must not set \"envPolicy\""
+                )));
+            }
+            ("http" | "sse", None, Some(url)) => {
+                let builder = if transport == "http" {
+                    ServerConfig::builder().http_transport(url)
+                } else {
+                    ServerConfig::builder().sse_transport(url)
+                };
+                builder.headers(raw.headers)
+            }
+            ("http" | "sse", None, None) => {
+                return Err(invalid(format!(
+                    "{transport} server entry requires \"url\""
+                )));
+            }
+            (other, ..) => {
+                return Err(invalid(format!(
+                    "unknown transport type \"{}\"",
+                    crate::untrusted::sanitize_untrusted_inline(other)
+                )));
+            }
+        };
+
+        if let Some(secs) = raw.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(secs));
+        }
+        if let Some(secs) = raw.discover_timeout_secs {
+            builder = builder.discover_timeout(Duration::from_secs(secs));
+        }
+        builder.build_interpolated(crate::interpolation::process_env)
+    }
+}
+
+/// Servers merged from every MCP config file, each from the highest-precedence file that
+/// defines it.
+#[derive(Debug, Clone, Default)]
+pub struct LayeredMcpConfig {
+    servers: BTreeMap<String, ResolvedServerEntry>,
+    sources: Vec<ConfigSource>,
+}
+
+impl LayeredMcpConfig {
+    /// The definition of `name`, if any file has one.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&ResolvedServerEntry> {
+        self.servers.get(name)
+    }
+
+    /// Every server, sorted by name.
+    pub fn servers(&self) -> impl Iterator<Item = (&str, &ResolvedServerEntry)> {
+        self.servers
+            .iter()
+            .map(|(name, server)| (name.as_str(), server))
+    }
+
+    /// Consumes the config, returning every server sorted by name.
+    #[must_use]
+    pub fn into_servers(self) -> BTreeMap<String, ResolvedServerEntry> {
+        self.servers
+    }
+
+    /// Every file that was read, in precedence order, whether or not it defined a server.
+    #[must_use]
+    pub fn sources(&self) -> &[ConfigSource] {
+        &self.sources
+    }
+
+    /// Whether no file defines any server.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.servers.is_empty()
+    }
+
+    /// Adds the servers under `document`'s `mcpServers`, keeping any already added by a
+    /// higher-precedence layer.
+    ///
+    /// `parent_key` names the key `document` sits under in its file, for error messages.
+    fn add_layer(
+        &mut self,
+        scope: ConfigScope,
+        path: &Path,
+        document: &Value,
+        parent_key: Option<&str>,
+    ) -> Result<(), McpConfigError> {
+        let source = ConfigSource {
+            scope,
+            path: path.to_path_buf(),
+        };
+        if let Some(servers) = mcp_servers(document, path, parent_key)? {
+            for (name, entry) in servers {
+                self.servers
+                    .entry(name.clone())
+                    .or_insert_with(|| ResolvedServerEntry {
+                        source: source.clone(),
+                        entry: entry.clone(),
+                    });
+            }
+        }
+        self.sources.push(source);
+        Ok(())
+    }
+}
+
+/// One MCP config file opened for editing its `mcpServers` map.
+///
+/// Every key this project does not model — other top-level settings, and unknown fields inside
+/// a server entry — is kept as read, in its original order, so editing a file shared with other
+/// MCP hosts never drops their data. A file that does not exist yet opens as an empty document
+/// and is created by [`save`](Self::save).
+///
+/// Its `Debug` output lists only server names, since entries routinely hold secrets.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::mcp_config::McpConfigDocument;
+/// use serde_json::json;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("mcp.json");
+/// std::fs::write(&path, r#"{"theme": "dark", "mcpServers": {}}"#).unwrap();
+///
+/// let mut document = McpConfigDocument::open(&path).unwrap();
+/// document.insert_server("github", json!({ "command": "github-mcp-server" }));
+/// document.save().await.unwrap();
+///
+/// let saved: serde_json::Value =
+///     serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+/// assert_eq!(saved["theme"], "dark");
+/// assert_eq!(saved["mcpServers"]["github"]["command"], "github-mcp-server");
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct McpConfigDocument {
+    path: PathBuf,
+    root: Map<String, Value>,
+}
+
+impl fmt::Debug for McpConfigDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("McpConfigDocument")
+            .field("path", &sanitize_path_for_error(&self.path))
+            .field("servers", &self.server_names().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl McpConfigDocument {
+    /// Reads `path` for editing, or starts an empty document if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpConfigError`] if the file exists but cannot be read, is implausibly large,
+    /// is not valid JSON, is not a JSON object, or has an `mcpServers` value that is not an
+    /// object.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, McpConfigError> {
+        let path = path.into();
+        let root = match read_config_document(&path)? {
+            None => Map::new(),
+            Some(document) => {
+                mcp_servers(&document, &path, None)?;
+                match document {
+                    Value::Object(root) => root,
+                    _ => unreachable!("mcp_servers rejects a non-object document"),
+                }
+            }
+        };
+        Ok(Self { path, root })
+    }
+
+    /// The file this document is read from and saved to.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The entry for `name`, as written.
+    #[must_use]
+    pub fn server(&self, name: &str) -> Option<&Value> {
+        self.servers()?.get(name)
+    }
+
+    /// The entry for `name`, for in-place edits that keep its other keys.
+    pub fn server_mut(&mut self, name: &str) -> Option<&mut Value> {
+        match self.root.get_mut("mcpServers") {
+            Some(Value::Object(servers)) => servers.get_mut(name),
+            _ => None,
+        }
+    }
+
+    /// Every server name, in file order.
+    pub fn server_names(&self) -> impl Iterator<Item = &str> {
+        self.servers()
+            .into_iter()
+            .flat_map(|servers| servers.keys().map(String::as_str))
+    }
+
+    /// Sets the entry for `name`, returning the entry it replaced.
+    ///
+    /// A replaced entry keeps its position in the file; a new one is appended.
+    pub fn insert_server(&mut self, name: impl Into<String>, entry: Value) -> Option<Value> {
+        let servers = self
+            .root
+            .entry("mcpServers")
+            .and_modify(|servers| {
+                if servers.is_null() {
+                    *servers = Value::Object(Map::new());
+                }
+            })
+            .or_insert_with(|| Value::Object(Map::new()));
+        match servers {
+            Value::Object(servers) => servers.insert(name.into(), entry),
+            _ => unreachable!("open rejects a non-object mcpServers"),
+        }
+    }
+
+    /// Removes the entry for `name`, returning it.
+    ///
+    /// Later entries keep their relative order.
+    pub fn remove_server(&mut self, name: &str) -> Option<Value> {
+        match self.root.get_mut("mcpServers") {
+            Some(Value::Object(servers)) => servers.shift_remove(name),
+            _ => None,
+        }
+    }
+
+    /// Writes the document back to [`path`](Self::path), atomically.
+    ///
+    /// The new content is staged in a hidden sibling file through
+    /// [`write_confined_file`](crate::write_confined_file), which refuses to follow a symlink
+    /// planted at the staging path, and then renamed over the target, so a reader sees either
+    /// the old file or the new one, never a partial write. The target keeps its permissions; a
+    /// new file is created readable by its owner only on Unix, since entries hold secrets. A
+    /// missing parent directory is created.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpConfigError::Invalid`] if the target is a symlink (replacing it would
+    /// silently detach the file it points to), and [`McpConfigError::Write`] if creating the
+    /// directory, staging, or renaming fails.
+    pub async fn save(&self) -> Result<(), McpConfigError> {
+        let write_error = |source| McpConfigError::Write {
+            path: sanitize_path_for_error(&self.path),
+            source,
+        };
+
+        let existing = match tokio::fs::symlink_metadata(&self.path).await {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                return Err(McpConfigError::Invalid {
+                    path: sanitize_path_for_error(&self.path),
+                    reason: "is a symlink; edit the file it points to with --config".to_string(),
+                });
+            }
+            Ok(metadata) => Some(metadata.permissions()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(write_error(err)),
+        };
+
+        let file_name = self
+            .path
+            .file_name()
+            .ok_or_else(|| McpConfigError::Invalid {
+                path: sanitize_path_for_error(&self.path),
+                reason: "is not a file path".to_string(),
+            })?;
+        let parent = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(write_error)?;
+
+        let mut content =
+            serde_json::to_vec_pretty(&self.root).map_err(|err| write_error(err.into()))?;
+        content.push(b'\n');
+
+        let staging = parent.join(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+        let staged = stage_config_file(&staging, &content, existing).await;
+        let result = match staged {
+            Ok(()) => tokio::fs::rename(&staging, &self.path).await,
+            Err(err) => Err(err),
+        };
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(&staging).await;
+        }
+        result.map_err(write_error)
+    }
+
+    fn servers(&self) -> Option<&Map<String, Value>> {
+        self.root.get("mcpServers")?.as_object()
+    }
+}
+
+/// Writes `content` to the staging file `path` and gives it `permissions` (or owner-only access
+/// on Unix when `None`).
+async fn stage_config_file(
+    path: &Path,
+    content: &[u8],
+    permissions: Option<std::fs::Permissions>,
+) -> std::io::Result<()> {
+    crate::write_confined_file(path, content)
+        .await
+        .map_err(|err| match err {
+            crate::ConfinementError::Io(err) => err,
+            other => std::io::Error::other(other),
+        })?;
+
+    #[cfg(unix)]
+    let permissions = permissions.unwrap_or_else(|| {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::Permissions::from_mode(0o600)
+    });
+    #[cfg(not(unix))]
+    let Some(permissions) = permissions else {
+        return Ok(());
+    };
+    tokio::fs::set_permissions(path, permissions).await
+}
+
+/// Reads the server table stored under `key` in the JSON file at `path`.
+///
+/// For config files of other MCP hosts, which keep the same entries under a different key
+/// (VS Code uses `servers`). A file without `key` yields an empty table. The same size limit as
+/// [`McpConfigLocations::load`] applies.
+///
+/// # Errors
+///
+/// Returns [`McpConfigError`] if the file does not exist, cannot be read, is implausibly large,
+/// is not a JSON object, or has a `key` value that is not an object.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mcp_execution_core::mcp_config::read_server_table;
+///
+/// let servers = read_server_table(".vscode/mcp.json".as_ref(), "servers")?;
+/// for name in servers.keys() {
+///     println!("{name}");
+/// }
+/// # Ok::<(), mcp_execution_core::mcp_config::McpConfigError>(())
+/// ```
+pub fn read_server_table(path: &Path, key: &str) -> Result<Map<String, Value>, McpConfigError> {
+    let document = read_config_document(path)?.ok_or_else(|| McpConfigError::NotFound {
+        path: sanitize_path_for_error(path),
+    })?;
+    let invalid = |reason: String| McpConfigError::Invalid {
+        path: sanitize_path_for_error(path),
+        reason,
+    };
+    let Value::Object(mut document) = document else {
+        return Err(invalid("expected a JSON object".to_string()));
+    };
+    match document.remove(key) {
+        None | Some(Value::Null) => Ok(Map::new()),
+        Some(Value::Object(servers)) => Ok(servers),
+        Some(_) => Err(invalid(format!("\"{key}\" must be an object"))),
+    }
+}
+
+/// Reads and parses the JSON file at `path`, or `None` if it does not exist.
+fn read_config_document(path: &Path) -> Result<Option<Value>, McpConfigError> {
+    let io_error = |source| McpConfigError::Io {
+        path: sanitize_path_for_error(path),
+        source,
+    };
+
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(io_error(err)),
+    };
+    if metadata.len() > MAX_CONFIG_FILE_SIZE {
+        return Err(McpConfigError::TooLarge {
+            path: sanitize_path_for_error(path),
+            size: metadata.len(),
+            limit: MAX_CONFIG_FILE_SIZE,
+        });
+    }
+
+    let content = std::fs::read_to_string(path).map_err(io_error)?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|source| McpConfigError::Parse {
+            path: sanitize_path_for_error(path),
+            source,
+        })
+}
+
+/// The `mcpServers` object of `document`, or `None` if it has none.
+fn mcp_servers<'a>(
+    document: &'a Value,
+    path: &Path,
+    parent_key: Option<&str>,
+) -> Result<Option<&'a Map<String, Value>>, McpConfigError> {
+    let invalid = |reason: String| McpConfigError::Invalid {
+        path: sanitize_path_for_error(path),
+        reason,
+    };
+    let Some(document) = document.as_object() else {
+        return Err(invalid(parent_key.map_or_else(
+            || "expected a JSON object".to_string(),
+            |key| format!("expected \"{key}\" entries to be objects"),
+        )));
+    };
+    match document.get("mcpServers") {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Object(servers)) => Ok(Some(servers)),
+        Some(_) => Err(invalid("\"mcpServers\" must be an object".to_string())),
+    }
+}
+
+/// The entry under `~/.claude.json`'s `projects` for `project_dir` or its nearest ancestor that
+/// has one.
+fn nearest_project_entry<'a>(claude_json: &'a Value, project_dir: &Path) -> Option<&'a Value> {
+    let projects = claude_json.get("projects")?.as_object()?;
+    project_dir
+        .ancestors()
+        .find_map(|dir| projects.get(dir.to_str()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// A fake home, project, and desktop config directory under one temp dir.
+    struct Fixture {
+        _temp: TempDir,
+        home: PathBuf,
+        project: PathBuf,
+        desktop: PathBuf,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let temp = TempDir::new().unwrap();
+            let home = temp.path().join("home");
+            let project = temp.path().join("work").join("repo");
+            std::fs::create_dir_all(home.join(".claude")).unwrap();
+            std::fs::create_dir_all(&project).unwrap();
+            let desktop = temp.path().join("claude_desktop_config.json");
+            Self {
+                _temp: temp,
+                home,
+                project,
+                desktop,
+            }
+        }
+
+        fn locations(&self) -> McpConfigLocations {
+            McpConfigLocations::new()
+                .with_home_dir(&self.home)
+                .with_project_dir(&self.project)
+                .with_desktop_config(&self.desktop)
+        }
+
+        fn write(path: &Path, value: &Value) {
+            std::fs::write(path, value.to_string()).unwrap();
+        }
+    }
+
+    fn servers(names: &[(&str, &str)]) -> Value {
+        let servers: Map<String, Value> = names
+            .iter()
+            .map(|(name, command)| ((*name).to_string(), json!({ "command": command })))
+            .collect();
+        json!({ "mcpServers": servers })
+    }
+
+    #[test]
+    fn load_with_no_files_is_empty() {
+        let fixture = Fixture::new();
+        let config = fixture.locations().load().unwrap();
+        assert!(config.is_empty());
+        assert!(config.sources().is_empty());
+    }
+
+    #[test]
+    fn load_applies_precedence_across_layers() {
+        let fixture = Fixture::new();
+        Fixture::write(
+            &fixture.desktop,
+            &servers(&[
+                ("a", "desktop"),
+                ("b", "desktop"),
+                ("c", "desktop"),
+                ("d", "desktop"),
+            ]),
+        );
+        let mut claude_json =
+            servers(&[("a", "user-json"), ("b", "user-json"), ("c", "user-json")]);
+        claude_json["projects"] = json!({
+            fixture.project.to_str().unwrap(): servers(&[("a", "local")]),
+        });
+        Fixture::write(&fixture.home.join(".claude.json"), &claude_json);
+        Fixture::write(
+            &fixture.home.join(".claude").join("mcp.json"),
+            &servers(&[("b", "user-mcp")]),
+        );
+        Fixture::write(
+            &fixture.project.join(PROJECT_CONFIG_FILE_NAME),
+            &servers(&[("a", "project"), ("c", "project")]),
+        );
+
+        let config = fixture.locations().load().unwrap();
+        let winner = |name: &str| {
+            let server = config.get(name).unwrap();
+            (server.source.scope, server.entry["command"].clone())
+        };
+        assert_eq!(winner("a"), (ConfigScope::Local, json!("local")));
+        assert_eq!(winner("b"), (ConfigScope::User, json!("user-mcp")));
+        assert_eq!(winner("c"), (ConfigScope::Project, json!("project")));
+        assert_eq!(winner("d"), (ConfigScope::Desktop, json!("desktop")));
+        assert_eq!(config.sources().len(), 5);
+    }
+
+    #[test]
+    fn load_finds_project_config_in_nearest_ancestor() {
+        let fixture = Fixture::new();
+        let repo_root = fixture.project.parent().unwrap();
+        Fixture::write(
+            &repo_root.join(PROJECT_CONFIG_FILE_NAME),
+            &servers(&[("outer", "x")]),
+        );
+        let nested = fixture.project.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let config = fixture
+            .locations()
+            .with_project_dir(&nested)
+            .load()
+            .unwrap();
+        let server = config.get("outer").unwrap();
+        assert_eq!(server.source.scope, ConfigScope::Project);
+        assert_eq!(server.source.path, repo_root.join(PROJECT_CONFIG_FILE_NAME));
+    }
+
+    #[test]
+    fn load_explicit_replaces_discovery() {
+        let fixture = Fixture::new();
+        Fixture::write(
+            &fixture.home.join(".claude").join("mcp.json"),
+            &servers(&[("user-only", "x")]),
+        );
+        let explicit = fixture.project.join("custom.json");
+        Fixture::write(&explicit, &servers(&[("custom", "y")]));
+
+        let config = fixture.locations().with_explicit(&explicit).load().unwrap();
+        assert!(config.get("user-only").is_none());
+        assert_eq!(
+            config.get("custom").unwrap().source.scope,
+            ConfigScope::Explicit
+        );
+        assert_eq!(
+            fixture
+                .locations()
+                .with_explicit(&explicit)
+                .existing_files(),
+            vec![explicit]
+        );
+    }
+
+    #[test]
+    fn load_missing_explicit_file_errors() {
+        let fixture = Fixture::new();
+        let err = fixture
+            .locations()
+            .with_explicit(fixture.project.join("missing.json"))
+            .load()
+            .unwrap_err();
+        assert!(matches!(err, McpConfigError::NotFound { .. }), "{err}");
+    }
+
+    #[test]
+    fn load_malformed_file_errors_naming_it() {
+        let fixture = Fixture::new();
+        std::fs::write(fixture.project.join(PROJECT_CONFIG_FILE_NAME), "{not json").unwrap();
+
+        let err = fixture.locations().load().unwrap_err();
+        assert!(matches!(err, McpConfigError::Parse { .. }));
+        assert!(err.to_string().contains(PROJECT_CONFIG_FILE_NAME), "{err}");
+    }
+
+    #[test]
+    fn load_rejects_non_object_mcp_servers() {
+        let fixture = Fixture::new();
+        Fixture::write(&fixture.desktop, &json!({ "mcpServers": ["a"] }));
+
+        let err = fixture.locations().load().unwrap_err();
+        assert!(matches!(err, McpConfigError::Invalid { .. }), "{err}");
+    }
+
+    #[test]
+    fn load_file_without_mcp_servers_contributes_nothing() {
+        let fixture = Fixture::new();
+        Fixture::write(
+            &fixture.home.join(".claude.json"),
+            &json!({ "numStartups": 3 }),
+        );
+
+        let config = fixture.locations().load().unwrap();
+        assert!(config.is_empty());
+        assert_eq!(config.sources().len(), 1);
+    }
+
+    #[test]
+    fn writable_config_prefers_explicit_file() {
+        let fixture = Fixture::new();
+        let user = fixture.locations().writable_config().unwrap();
+        assert_eq!(user.scope, ConfigScope::User);
+        assert_eq!(user.path, fixture.home.join(".claude").join("mcp.json"));
+
+        let explicit = fixture.home.join("servers.json");
+        let target = fixture
+            .locations()
+            .with_explicit(&explicit)
+            .writable_config()
+            .unwrap();
+        assert_eq!(target.scope, ConfigScope::Explicit);
+        assert_eq!(target.path, explicit);
+    }
+
+    #[test]
+    fn document_open_missing_file_is_empty() {
+        let fixture = Fixture::new();
+        let document = McpConfigDocument::open(fixture.home.join("absent.json")).unwrap();
+        assert_eq!(document.server_names().count(), 0);
+        assert!(document.server("github").is_none());
+    }
+
+    #[test]
+    fn document_open_rejects_non_object_servers() {
+        let fixture = Fixture::new();
+        let path = fixture.home.join("bad.json");
+        Fixture::write(&path, &json!({ "mcpServers": ["github"] }));
+        assert!(matches!(
+            McpConfigDocument::open(&path),
+            Err(McpConfigError::Invalid { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn document_edits_preserve_unknown_keys_and_order() {
+        let fixture = Fixture::new();
+        let path = fixture.home.join(".claude").join("mcp.json");
+        Fixture::write(
+            &path,
+            &json!({
+                "theme": "dark",
+                "mcpServers": {
+                    "a": { "command": "a", "alwaysAllow": ["x"] },
+                    "b": { "command": "b" },
+                    "c": { "command": "c" }
+                },
+                "zeta": 1
+            }),
+        );
+
+        let mut document = McpConfigDocument::open(&path).unwrap();
+        let previous = document.insert_server("a", json!({ "command": "a2" }));
+        assert_eq!(previous.unwrap()["alwaysAllow"], json!(["x"]));
+        document.insert_server("d", json!({ "command": "d" }));
+        assert!(document.remove_server("b").is_some());
+        assert!(document.remove_server("missing").is_none());
+        document.server_mut("c").unwrap()["connectTimeoutSecs"] = json!(5);
+        document.save().await.unwrap();
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        let value: Value = serde_json::from_str(&saved).unwrap();
+        let top: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(top, ["theme", "mcpServers", "zeta"]);
+        let document = McpConfigDocument::open(&path).unwrap();
+        let servers: Vec<&str> = document.server_names().collect();
+        assert_eq!(servers, ["a", "c", "d"]);
+        assert_eq!(value["mcpServers"]["a"], json!({ "command": "a2" }));
+        assert_eq!(value["mcpServers"]["c"]["connectTimeoutSecs"], 5);
+        assert!(saved.ends_with('\n'));
+    }
+
+    #[tokio::test]
+    async fn document_save_creates_missing_file_and_directory() {
+        let fixture = Fixture::new();
+        let path = fixture.home.join("new-dir").join("mcp.json");
+
+        let mut document = McpConfigDocument::open(&path).unwrap();
+        document.insert_server("github", json!({ "command": "srv" }));
+        document.save().await.unwrap();
+
+        let value: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            value,
+            json!({ "mcpServers": { "github": { "command": "srv" } } })
+        );
+        let leftovers: Vec<_> = std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn document_save_keeps_existing_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let fixture = Fixture::new();
+        let path = fixture.home.join("mcp.json");
+        Fixture::write(&path, &json!({}));
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+        let mut document = McpConfigDocument::open(&path).unwrap();
+        document.insert_server("github", json!({ "command": "srv" }));
+        document.save().await.unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn document_save_refuses_symlinked_target() {
+        let fixture = Fixture::new();
+        let real = fixture.home.join("real.json");
+        Fixture::write(&real, &json!({}));
+        let link = fixture.home.join("link.json");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        let mut document = McpConfigDocument::open(&link).unwrap();
+        document.insert_server("github", json!({ "command": "srv" }));
+        let err = document.save().await.unwrap_err();
+
+        assert!(matches!(err, McpConfigError::Invalid { .. }), "{err}");
+        assert_eq!(std::fs::read_to_string(&real).unwrap(), "{}");
+    }
+
+    #[test]
+    fn relocated_claude_dir_holds_user_configs() {
+        let fixture = Fixture::new();
+        let claude_dir = fixture.project.join("claude-config");
+        std::fs::create_dir_all(&claude_dir).unwrap();
+        Fixture::write(
+            &claude_dir.join("mcp.json"),
+            &servers(&[("a", "relocated")]),
+        );
+        Fixture::write(
+            &claude_dir.join(".claude.json"),
+            &servers(&[("b", "claude")]),
+        );
+        Fixture::write(
+            &fixture.home.join(".claude").join("mcp.json"),
+            &servers(&[("a", "home")]),
+        );
+
+        let locations = McpConfigLocations::new()
+            .with_paths(Paths::new(&fixture.home).with_claude_dir(&claude_dir));
+        let config = locations.load().unwrap();
+
+        assert_eq!(config.get("a").unwrap().entry["command"], "relocated");
+        assert_eq!(config.get("b").unwrap().entry["command"], "claude");
+        assert_eq!(
+            locations.writable_config().unwrap().path,
+            claude_dir.join("mcp.json")
+        );
+    }
+
+    #[test]
+    fn read_server_table_uses_given_key() {
+        let fixture = Fixture::new();
+        let path = fixture.project.join("vscode-mcp.json");
+        Fixture::write(
+            &path,
+            &json!({"inputs": [], "servers": {"b": {"command": "y"}, "a": {"command": "x"}}}),
+        );
+
+        let servers = read_server_table(&path, "servers").unwrap();
+
+        assert_eq!(servers.keys().collect::<Vec<_>>(), ["b", "a"]);
+        assert!(read_server_table(&path, "mcpServers").unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_server_table_errors() {
+        let fixture = Fixture::new();
+        let missing = fixture.project.join("missing.json");
+        assert!(matches!(
+            read_server_table(&missing, "mcpServers"),
+            Err(McpConfigError::NotFound { .. })
+        ));
+
+        let path = fixture.project.join("bad.json");
+        Fixture::write(&path, &json!({"mcpServers": ["x"]}));
+        assert!(matches!(
+            read_server_table(&path, "mcpServers"),
+            Err(McpConfigError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn document_debug_hides_entries() {
+        let mut document = McpConfigDocument::open("/nonexistent/mcp.json").unwrap();
+        document.insert_server("github", json!({ "env": { "TOKEN": "sk-secret" } }));
+        let debug = format!("{document:?}");
+        assert!(debug.contains("github"));
+        assert!(!debug.contains("sk-secret"));
+    }
+
+    #[test]
+    fn resolved_server_entry_debug_hides_values() {
+        let server = ResolvedServerEntry {
+            source: ConfigSource {
+                scope: ConfigScope::User,
+                path: PathBuf::from("/x/mcp.json"),
+            },
+            entry: json!({ "command": "srv", "env": { "TOKEN": "sk-secret" } }),
+        };
+        let debug = format!("{server:?}");
+        assert!(debug.contains("env"));
+        assert!(!debug.contains("sk-secret"));
+    }
+
+    fn resolved(entry: Value) -> ResolvedServerEntry {
+        ResolvedServerEntry {
+            source: ConfigSource {
+                scope: ConfigScope::User,
+                path: PathBuf::from("/x/mcp.json"),
+            },
+            entry,
+        }
+    }
+
+    #[test]
+    fn to_server_config_infers_stdio_and_applies_fields() {
+        let config = resolved(json!({
+            "command": "srv",
+            "args": ["--stdio"],
+            "env": { "MODE": "test" },
+            "envPolicy": "minimal",
+            "connectTimeoutSecs": 5,
+            "alwaysAllow": ["x"],
+        }))
+        .to_server_config()
+        .unwrap();
+        assert_eq!(config.command(), Some("srv"));
+        assert_eq!(config.args(), ["--stdio"]);
+        assert_eq!(config.env().get("MODE").map(String::as_str), Some("test"));
+        assert_eq!(config.connect_timeout(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn to_server_config_builds_remote_transports() {
+        let http = resolved(json!({
+            "url": "https://api.example.com/mcp",
+            "headers": { "Authorization": "Bearer t" },
+        }))
+        .to_server_config()
+        .unwrap();
+        assert_eq!(http.url(), Some("https://api.example.com/mcp"));
+        assert!(http.headers().contains_key("Authorization"));
+
+        let sse = resolved(json!({ "type": "sse", "url": "https://api.example.com/sse" }))
+            .to_server_config()
+            .unwrap();
+        assert_eq!(sse.url(), Some("https://api.example.com/sse"));
+    }
+
+    #[test]
+    fn to_server_config_rejects_malformed_entries() {
+        for (entry, expected) in [
+            (json!({}), "must set either"),
+            (
+                json!({ "command": "srv", "url": "https://x" }),
+                "must not set \"url\"",
+            ),
+            (
+                json!({ "type": "http", "command": "srv" }),
+                "must not set \"command\"",
+            ),
+            (
+                json!({ "type": "http", "url": "https://x", "envPolicy": "minimal" }),
+                "must not set \"envPolicy\"",
+            ),
+            (json!({ "type": "sse" }), "requires \"url\""),
+            (
+                json!({ "type": "ws", "url": "wss://x" }),
+                "unknown transport type",
+            ),
+            (json!({ "command": 7 }), "/x/mcp.json"),
+        ] {
+            let err = resolved(entry.clone()).to_server_config().unwrap_err();
+            assert!(err.to_string().contains(expected), "{entry}: {err}");
+        }
+    }
+}
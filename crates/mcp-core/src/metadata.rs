@@ -30,6 +30,8 @@
 //!         category: Some("issues".to_string()),
 //!         keywords: vec!["create".to_string(), "issue".to_string()],
 //!         description: Some("Creates a new issue".to_string()),
+//!         short_description: None,
+//!         source_digest: None,
 //!         parameters: vec![],
 //!     }],
 //!     provenance: GenerationProvenance::capture(&config, &[]),
@@ -40,9 +42,12 @@
 //! assert_eq!(round_tripped, meta);
 //! ```
 
-use crate::provenance::GenerationProvenance;
+use crate::path::sanitize_path_for_error;
+use crate::provenance::{GenerationProvenance, ToolDigest};
 use crate::{ServerId, ToolName};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error as ThisError;
 
 /// Current schema version of the `_meta.json` sidecar format.
 ///
@@ -95,6 +100,10 @@ pub const METADATA_FILE_NAME: &str = "_meta.json";
 /// ```
 pub const INDEX_FILE_NAME: &str = "index.ts";
 
+/// Upper bound on the size of a `_meta.json` sidecar read back from disk, matching
+/// `mcp-execution-skill`'s own 10MB per-file read cap.
+pub(crate) const MAX_PERSISTED_METADATA_SIZE: u64 = 10 * 1024 * 1024;
+
 /// Structured sidecar describing one server's generated tools.
 ///
 /// Serialized as `_meta.json` by `mcp-execution-codegen` and deserialized by
@@ -170,6 +179,8 @@ pub struct ServerMetadata {
 ///     category: Some("issues".to_string()),
 ///     keywords: vec!["create".to_string(), "issue".to_string()],
 ///     description: Some("Creates a new issue".to_string()),
+///     short_description: Some("Create an issue".to_string()),
+///     source_digest: None,
 ///     parameters: vec![],
 /// };
 ///
@@ -197,10 +208,113 @@ pub struct ToolMetadata {
     /// Human-readable tool description, as reported by the MCP server.
     pub description: Option<String>,
 
+    /// Concise description from the tool's categorization, when it had one.
+    ///
+    /// Added without a [`METADATA_SCHEMA_VERSION`] bump, like [`Self::source_digest`]: a
+    /// sidecar written before it existed simply has none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_description: Option<String>,
+
+    /// [`ToolDigest`] of this tool alone (name, description, and schemas, as discovered), so a
+    /// later generation can tell whether the tool changed since its categorization was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_digest: Option<ToolDigest>,
+
     /// Metadata for each of the tool's input parameters.
     pub parameters: Vec<ParameterMetadata>,
 }
 
+/// Error returned by [`read_persisted_tool_metadata`].
+#[derive(Debug, ThisError)]
+pub enum PersistedMetadataError {
+    /// The sidecar exists but could not be read.
+    #[error("failed to read {path}: {source}")]
+    Io {
+        /// Sanitized path of the sidecar (see [`crate::sanitize_path_for_error`]).
+        path: String,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The sidecar is larger than any generation would plausibly write.
+    #[error("{path} is {size} bytes, exceeding the {limit} byte limit")]
+    TooLarge {
+        /// Sanitized path of the sidecar.
+        path: String,
+        /// Observed size in bytes.
+        size: u64,
+        /// Maximum accepted size in bytes.
+        limit: u64,
+    },
+
+    /// The sidecar's `tools` list is not valid JSON of the expected shape.
+    #[error("failed to parse the tool metadata recorded in {path}: {source}")]
+    Parse {
+        /// Sanitized path of the sidecar.
+        path: String,
+        /// Underlying parse error.
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+#[derive(Deserialize)]
+struct PersistedToolsProbe {
+    #[serde(default)]
+    tools: Vec<ToolMetadata>,
+}
+
+/// Reads the per-tool metadata a previous generation recorded in `server_dir`'s `_meta.json`.
+///
+/// Returns an empty list when there is no sidecar yet. Only the `tools` list is parsed, so a
+/// sidecar of any schema version is accepted; entries from before
+/// [`ToolMetadata::source_digest`] existed carry none.
+///
+/// # Errors
+///
+/// Returns [`PersistedMetadataError`] if the sidecar exists but cannot be read, is
+/// implausibly large, or its `tools` list does not parse.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::metadata::read_persisted_tool_metadata;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// assert!(read_persisted_tool_metadata(dir.path()).unwrap().is_empty());
+/// ```
+pub fn read_persisted_tool_metadata(
+    server_dir: &Path,
+) -> std::result::Result<Vec<ToolMetadata>, PersistedMetadataError> {
+    let meta_path = server_dir.join(METADATA_FILE_NAME);
+    let io_error = |source| PersistedMetadataError::Io {
+        path: sanitize_path_for_error(&meta_path),
+        source,
+    };
+
+    let file_metadata = match std::fs::metadata(&meta_path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(io_error(err)),
+    };
+    if file_metadata.len() > MAX_PERSISTED_METADATA_SIZE {
+        return Err(PersistedMetadataError::TooLarge {
+            path: sanitize_path_for_error(&meta_path),
+            size: file_metadata.len(),
+            limit: MAX_PERSISTED_METADATA_SIZE,
+        });
+    }
+
+    let content = std::fs::read_to_string(&meta_path).map_err(io_error)?;
+    let probe: PersistedToolsProbe =
+        serde_json::from_str(&content).map_err(|source| PersistedMetadataError::Parse {
+            path: sanitize_path_for_error(&meta_path),
+            source,
+        })?;
+    Ok(probe.tools)
+}
+
 /// Structured metadata for a single tool parameter.
 ///
 /// # Examples
@@ -234,8 +348,11 @@ pub struct ParameterMetadata {
 
 #[cfg(test)]
 mod tests {
-    use super::{METADATA_SCHEMA_VERSION, ParameterMetadata, ServerMetadata, ToolMetadata};
-    use crate::provenance::GenerationProvenance;
+    use super::{
+        METADATA_FILE_NAME, METADATA_SCHEMA_VERSION, ParameterMetadata, ServerMetadata,
+        ToolMetadata, read_persisted_tool_metadata,
+    };
+    use crate::provenance::{GenerationProvenance, ToolDigest};
     use crate::{ServerConfig, ServerId, ToolName};
 
     fn test_provenance() -> GenerationProvenance {
@@ -259,6 +376,8 @@ mod tests {
                 category: Some("issues".to_string()),
                 keywords: vec!["create".to_string(), "issue".to_string()],
                 description: Some("Creates a new issue".to_string()),
+                short_description: Some("Create an issue".to_string()),
+                source_digest: Some(ToolDigest::compute(&[])),
                 parameters: vec![ParameterMetadata {
                     name: "title".to_string(),
                     typescript_type: "string".to_string(),
@@ -320,4 +439,35 @@ mod tests {
         let result: Result<ServerMetadata, _> = serde_json::from_str(json);
         assert!(result.is_err());
     }
+
+    /// `read_persisted_tool_metadata` only needs the `tools` list, so it also accepts a sidecar
+    /// whose other fields it could not deserialize (such as the v1 document above).
+    #[test]
+    fn read_persisted_tool_metadata_reads_tools_of_any_schema_version() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(METADATA_FILE_NAME),
+            r#"{
+                "schema_version": 1,
+                "tools": [{
+                    "name": "create_issue",
+                    "typescript_name": "createIssue",
+                    "category": "issues",
+                    "keywords": ["issue"],
+                    "parameters": []
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let tools = read_persisted_tool_metadata(dir.path()).unwrap();
+
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].category.as_deref(), Some("issues"));
+        assert!(tools[0].short_description.is_none());
+        assert!(tools[0].source_digest.is_none());
+
+        std::fs::write(dir.path().join(METADATA_FILE_NAME), "{ not json").unwrap();
+        assert!(read_persisted_tool_metadata(dir.path()).is_err());
+    }
 }
@@ -0,0 +1,301 @@
+//! Where mcp-execution keeps its files.
+//!
+//! Generated servers, skills, and the user-level `mcp.json` all live under one Claude config
+//! directory, `~/.claude` by default. [`Paths`] resolves that directory once so every
+//! component — the CLI, the MCP server, and the generated TypeScript bridge — agrees on it.
+//!
+//! # Resolution
+//!
+//! 1. An explicit home (`--home <DIR>`) wins; its config directory is `<DIR>/.claude`.
+//! 2. Otherwise the home is [`HOME_ENV`] if set, else the user's home directory.
+//! 3. [`CLAUDE_CONFIG_DIR_ENV`], if set, replaces `<home>/.claude` — the same variable Claude
+//!    Code itself honours. It is ignored when an explicit home is given, so `--home` always
+//!    yields a self-contained tree.
+//!
+//! Empty variables count as unset. Relative paths are resolved against the current directory.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::Path;
+//! use mcp_execution_core::paths::Paths;
+//!
+//! let paths = Paths::new("/sandbox/agent-1");
+//! assert_eq!(paths.servers_dir(), Path::new("/sandbox/agent-1/.claude/servers"));
+//! assert_eq!(paths.skills_dir(), Path::new("/sandbox/agent-1/.claude/skills"));
+//! assert_eq!(paths.user_mcp_config(), Path::new("/sandbox/agent-1/.claude/mcp.json"));
+//! assert_eq!(paths.claude_json(), Path::new("/sandbox/agent-1/.claude.json"));
+//! ```
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the home directory mcp-execution resolves `.claude` against.
+pub const HOME_ENV: &str = "MCP_EXECUTION_HOME";
+
+/// Environment variable overriding the Claude config directory (`~/.claude`).
+pub const CLAUDE_CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
+
+/// Resolved locations of the Claude config directory and the files under it.
+///
+/// See the [module docs](self) for how [`discover`](Self::discover) resolves them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    home: PathBuf,
+    claude_dir: PathBuf,
+    servers_dir: Option<PathBuf>,
+}
+
+impl Paths {
+    /// Paths rooted at `home`, with the config directory at `<home>/.claude`.
+    #[must_use]
+    pub fn new(home: impl Into<PathBuf>) -> Self {
+        let home = home.into();
+        let claude_dir = home.join(".claude");
+        Self {
+            home,
+            claude_dir,
+            servers_dir: None,
+        }
+    }
+
+    /// Replaces the config directory, keeping the home.
+    #[must_use]
+    pub fn with_claude_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.claude_dir = dir.into();
+        self
+    }
+
+    /// Moves generated server files out of the config directory, e.g. into another agent
+    /// host's own directory.
+    #[must_use]
+    pub fn with_servers_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.servers_dir = Some(dir.into());
+        self
+    }
+
+    /// Resolves paths for the current process.
+    ///
+    /// `home` (typically `--home`) takes precedence over the environment. Returns `None` only
+    /// when no home is given, [`HOME_ENV`] is unset, and the user's home directory cannot be
+    /// determined.
+    #[must_use]
+    pub fn discover(home: Option<PathBuf>) -> Option<Self> {
+        resolve(
+            home,
+            std::env::var_os(HOME_ENV),
+            std::env::var_os(CLAUDE_CONFIG_DIR_ENV),
+            dirs::home_dir,
+        )
+    }
+
+    /// Paths rooted at the operating system's home directory, ignoring [`HOME_ENV`] and
+    /// [`CLAUDE_CONFIG_DIR_ENV`]: what a generated runtime bridge resolves on its own through
+    /// `os.homedir()`.
+    #[must_use]
+    pub fn os_default() -> Option<Self> {
+        dirs::home_dir().map(Self::new)
+    }
+
+    /// The home directory.
+    #[must_use]
+    pub fn home(&self) -> &Path {
+        &self.home
+    }
+
+    /// The Claude config directory, `~/.claude` by default.
+    #[must_use]
+    pub fn claude_dir(&self) -> &Path {
+        &self.claude_dir
+    }
+
+    /// Where generated server files go: `<config dir>/servers` unless
+    /// [moved](Self::with_servers_dir).
+    #[must_use]
+    pub fn servers_dir(&self) -> PathBuf {
+        self.servers_dir
+            .clone()
+            .unwrap_or_else(|| self.claude_dir.join("servers"))
+    }
+
+    /// Where generated skills go: `<config dir>/skills`.
+    #[must_use]
+    pub fn skills_dir(&self) -> PathBuf {
+        self.claude_dir.join("skills")
+    }
+
+    /// This project's user-level server config: `<config dir>/mcp.json`.
+    #[must_use]
+    pub fn user_mcp_config(&self) -> PathBuf {
+        self.claude_dir.join("mcp.json")
+    }
+
+    /// Claude Code's own config file.
+    ///
+    /// `<home>/.claude.json` normally; when the config directory has been moved away from
+    /// `<home>/.claude`, Claude Code keeps the file inside it instead.
+    #[must_use]
+    pub fn claude_json(&self) -> PathBuf {
+        if self.claude_dir == self.home.join(".claude") {
+            self.home.join(".claude.json")
+        } else {
+            self.claude_dir.join(".claude.json")
+        }
+    }
+}
+
+/// Returns the root of the project containing `start`, for bindings committed alongside it.
+///
+/// The root is the nearest ancestor of `start` (itself included) holding a `.git` entry —
+/// a directory, or a file in worktrees and submodules. Outside a repository it falls back to
+/// the nearest ancestor holding a project `.mcp.json`, and otherwise returns `None`.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::paths::find_project_root;
+///
+/// let repo = tempfile::tempdir().unwrap();
+/// std::fs::create_dir_all(repo.path().join(".git")).unwrap();
+/// std::fs::create_dir_all(repo.path().join("src/deep")).unwrap();
+///
+/// let root = find_project_root(&repo.path().join("src/deep")).unwrap();
+/// assert_eq!(root, repo.path());
+/// ```
+#[must_use]
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    let nearest_with = |marker: &str| {
+        start
+            .ancestors()
+            .find(|dir| dir.join(marker).exists())
+            .map(Path::to_path_buf)
+    };
+    nearest_with(".git").or_else(|| nearest_with(".mcp.json"))
+}
+
+/// [`Paths::discover`] with the environment passed in, so it can be tested without mutating
+/// process state.
+fn resolve(
+    home: Option<PathBuf>,
+    home_env: Option<OsString>,
+    claude_dir_env: Option<OsString>,
+    default_home: impl FnOnce() -> Option<PathBuf>,
+) -> Option<Paths> {
+    if let Some(home) = home {
+        return Some(Paths::new(absolute(home)));
+    }
+    let home = non_empty(home_env).or_else(default_home)?;
+    let paths = Paths::new(absolute(home));
+    Some(match non_empty(claude_dir_env) {
+        Some(dir) => paths.with_claude_dir(absolute(dir)),
+        None => paths,
+    })
+}
+
+fn non_empty(value: Option<OsString>) -> Option<PathBuf> {
+    value.filter(|value| !value.is_empty()).map(PathBuf::from)
+}
+
+fn absolute(path: PathBuf) -> PathBuf {
+    std::path::absolute(&path).unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_root_prefers_repository_over_nested_mcp_json() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::write(repo.path().join(".git"), "gitdir: elsewhere").unwrap();
+        let nested = repo.path().join("pkg");
+        std::fs::create_dir_all(nested.join("src")).unwrap();
+        std::fs::write(nested.join(".mcp.json"), "{}").unwrap();
+
+        assert_eq!(find_project_root(&nested.join("src")).unwrap(), repo.path());
+    }
+
+    #[test]
+    fn project_root_falls_back_to_mcp_json() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".mcp.json"), "{}").unwrap();
+        std::fs::create_dir_all(dir.path().join("sub")).unwrap();
+
+        assert_eq!(
+            find_project_root(&dir.path().join("sub")).unwrap(),
+            dir.path()
+        );
+    }
+
+    fn no_home() -> Option<PathBuf> {
+        None
+    }
+
+    #[test]
+    fn defaults_to_user_home() {
+        let paths = resolve(None, None, None, || Some(PathBuf::from("/home/u"))).unwrap();
+        assert_eq!(paths, Paths::new("/home/u"));
+        assert_eq!(paths.claude_json(), Path::new("/home/u/.claude.json"));
+    }
+
+    #[test]
+    fn home_env_replaces_user_home() {
+        let paths = resolve(None, Some("/sandbox".into()), None, || {
+            Some(PathBuf::from("/home/u"))
+        })
+        .unwrap();
+        assert_eq!(paths.servers_dir(), Path::new("/sandbox/.claude/servers"));
+    }
+
+    #[test]
+    fn claude_config_dir_replaces_config_dir() {
+        let paths = resolve(None, None, Some("/cfg".into()), || {
+            Some(PathBuf::from("/home/u"))
+        })
+        .unwrap();
+        assert_eq!(paths.home(), Path::new("/home/u"));
+        assert_eq!(paths.skills_dir(), Path::new("/cfg/skills"));
+        assert_eq!(paths.user_mcp_config(), Path::new("/cfg/mcp.json"));
+        assert_eq!(paths.claude_json(), Path::new("/cfg/.claude.json"));
+    }
+
+    #[test]
+    fn explicit_home_ignores_environment() {
+        let paths = resolve(
+            Some("/explicit".into()),
+            Some("/sandbox".into()),
+            Some("/cfg".into()),
+            no_home,
+        )
+        .unwrap();
+        assert_eq!(paths, Paths::new("/explicit"));
+    }
+
+    #[test]
+    fn empty_variables_count_as_unset() {
+        let paths = resolve(None, Some(OsString::new()), Some(OsString::new()), || {
+            Some(PathBuf::from("/home/u"))
+        })
+        .unwrap();
+        assert_eq!(paths, Paths::new("/home/u"));
+    }
+
+    #[test]
+    fn servers_dir_can_move_independently() {
+        let paths = Paths::new("/home/u").with_servers_dir("/home/u/.codex/servers");
+        assert_eq!(paths.servers_dir(), Path::new("/home/u/.codex/servers"));
+        assert_eq!(paths.skills_dir(), Path::new("/home/u/.claude/skills"));
+    }
+
+    #[test]
+    fn no_home_resolves_to_none() {
+        assert!(resolve(None, None, Some("/cfg".into()), no_home).is_none());
+    }
+
+    #[test]
+    fn relative_home_is_made_absolute() {
+        let paths = resolve(Some("sandbox".into()), None, None, no_home).unwrap();
+        assert!(paths.home().is_absolute());
+        assert!(paths.home().ends_with("sandbox"));
+    }
+}
@@ -15,6 +15,11 @@
 //! `generate` today produce byte-identical files?" — collision-disambiguating TypeScript names
 //! and tool categorization both affect generated output without affecting either digest below.
 //!
+//! A [`ToolFilter`] narrowing which tools were generated is recorded alongside the digests
+//! ([`GenerationProvenance::tool_filter`]) rather than inside either of them: [`ToolDigest`]
+//! always covers the server's full discovered tool list, so changing the filter shows up as a
+//! changed filter, never as a changed tool surface.
+//!
 //! # Hashing approach
 //!
 //! Both [`ConfigFingerprint`] and [`ToolDigest`] are SHA-256 digests (hex-encoded, lowercase)
@@ -33,6 +38,7 @@
 
 use crate::redact::{UrlTailKind, split_url};
 use crate::server_config::{ServerConfig, Transport};
+use crate::tool_filter::ToolFilter;
 use crate::untrusted::sanitize_untrusted_inline;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -232,6 +238,10 @@ impl ConfigFingerprint {
     /// value, environment/header value, query-parameter value, or userinfo is ever fed — see
     /// the module docs.
     ///
+    /// The transport hashed is [`ServerConfig::unexpanded_transport`] — the config as written,
+    /// before `${VAR}` expansion — so rotating a secret referenced as `${VAR}` (even one that
+    /// lands in `command` or the URL) never registers as a config change.
+    ///
     /// `ServerConfig::connect_timeout`/`discover_timeout` are deliberately excluded: they bound
     /// how long the client waits for a response, not what the server exposes, so changing one
     /// must not register as a change to the server's identity or tool surface.
@@ -270,12 +280,15 @@ impl ConfigFingerprint {
         let mut pre = Preimage::new();
         pre.str(CONFIG_FINGERPRINT_DOMAIN);
 
-        match config.transport() {
+        match config.unexpanded_transport() {
+            // `env_policy` only changes what the process inherits at spawn time, not which
+            // server the bindings were generated from.
             Transport::Stdio {
                 command,
                 args,
                 env,
                 cwd,
+                env_policy: _,
             } => {
                 pre.str("stdio");
                 pre.str(command);
@@ -653,7 +666,17 @@ pub struct GenerationProvenance {
     /// Fingerprint of the [`ServerConfig`] used to connect to and introspect the server.
     pub config_fingerprint: ConfigFingerprint,
     /// Digest of the discovered tool list at generation time.
+    ///
+    /// Always computed over every tool the server reported, including any `tool_filter`
+    /// excluded from the generated files.
     pub tool_digest: ToolDigest,
+    /// Include/exclude filter that selected which discovered tools were generated.
+    ///
+    /// Omitted from the serialized sidecar when empty, so a sidecar generated without a filter
+    /// is byte-identical to one written before this field existed. A later regeneration reads
+    /// it back (see [`crate::tool_filter::read_persisted_tool_filter`]) to keep honoring it.
+    #[serde(default, skip_serializing_if = "ToolFilter::is_empty")]
+    pub tool_filter: ToolFilter,
 }
 
 impl GenerationProvenance {
@@ -677,8 +700,32 @@ impl GenerationProvenance {
             generated_at: Utc::now(),
             config_fingerprint: ConfigFingerprint::compute(config),
             tool_digest: ToolDigest::compute(tools),
+            tool_filter: ToolFilter::default(),
         }
     }
+
+    /// Records `filter` as the tool filter this generation was run with.
+    ///
+    /// `tools` passed to [`Self::capture`] must still be the *unfiltered* discovered list — see
+    /// [`Self::tool_filter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::provenance::GenerationProvenance;
+    /// use mcp_execution_core::tool_filter::ToolFilter;
+    /// use mcp_execution_core::ServerConfig;
+    ///
+    /// let config = ServerConfig::builder().command("docker".to_string()).build().unwrap();
+    /// let filter = ToolFilter::new(vec!["issue_*".to_string()], vec![]).unwrap();
+    /// let provenance = GenerationProvenance::capture(&config, &[]).with_tool_filter(filter.clone());
+    /// assert_eq!(provenance.tool_filter, filter);
+    /// ```
+    #[must_use]
+    pub fn with_tool_filter(mut self, filter: ToolFilter) -> Self {
+        self.tool_filter = filter;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -843,6 +890,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fingerprint_equal_when_only_interpolated_values_differ() {
+        let build = |secret: &'static str| {
+            ServerConfig::builder()
+                .command("${SERVER_BIN}".to_string())
+                .env("TOKEN".to_string(), "${TOKEN}".to_string())
+                .build_interpolated(move |name| {
+                    Some(if name == "SERVER_BIN" { "npx" } else { secret }.to_string())
+                })
+                .unwrap()
+        };
+        let literal = ServerConfig::builder()
+            .command("npx".to_string())
+            .env("TOKEN".to_string(), "secret-a".to_string())
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            ConfigFingerprint::compute(&build("secret-a")),
+            ConfigFingerprint::compute(&build("secret-b"))
+        );
+        // The reference itself is what is hashed, not the value it expanded to.
+        assert_ne!(
+            ConfigFingerprint::compute(&build("secret-a")),
+            ConfigFingerprint::compute(&literal)
+        );
+    }
+
     // -- Config fingerprint: sensitivity --
 
     #[test]
@@ -1241,6 +1316,34 @@ mod tests {
         assert!(provenance.generated_at >= before && provenance.generated_at <= after);
     }
 
+    #[test]
+    fn generation_provenance_omits_empty_tool_filter() {
+        let provenance = GenerationProvenance::capture(&stdio_config("docker"), &[]);
+        let json = serde_json::to_value(&provenance).unwrap();
+        assert!(json.get("tool_filter").is_none());
+
+        let back: GenerationProvenance = serde_json::from_value(json).unwrap();
+        assert!(back.tool_filter.is_empty());
+    }
+
+    /// A changed filter is recorded as a changed filter, not as a changed tool surface: the
+    /// digest is unaffected because callers always pass the unfiltered tool list.
+    #[test]
+    fn generation_provenance_records_tool_filter_without_touching_digest() {
+        let config = stdio_config("docker");
+        let filter = ToolFilter::new(vec![], vec!["*_admin".to_string()]).unwrap();
+
+        let plain = GenerationProvenance::capture(&config, &[]);
+        let filtered = GenerationProvenance::capture(&config, &[]).with_tool_filter(filter.clone());
+
+        assert_eq!(plain.tool_digest, filtered.tool_digest);
+        assert_ne!(plain.tool_filter, filtered.tool_filter);
+
+        let json = serde_json::to_string(&filtered).unwrap();
+        let back: GenerationProvenance = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.tool_filter, filter);
+    }
+
     #[test]
     fn provenance_types_are_send_sync() {
         fn assert_send<T: Send>() {}
@@ -38,7 +38,8 @@ pub const REDACTED_PLACEHOLDER: &str = "<redacted>";
 /// Intended for `env`/`headers`-style maps: the key (e.g. `"Authorization"`
 /// or `"GITHUB_PERSONAL_ACCESS_TOKEN"`) is a caller-chosen identifier and
 /// useful for debugging, but the value routinely holds a bearer token or API
-/// key and must never be echoed.
+/// key and must never be echoed. Keys are listed in sorted order, so the output
+/// is stable across runs despite `HashMap`'s iteration order.
 ///
 /// # Examples
 ///
@@ -58,8 +59,10 @@ pub struct RedactedMapValues<'a>(pub &'a HashMap<String, String>);
 
 impl fmt::Debug for RedactedMapValues<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<&String> = self.0.keys().collect();
+        keys.sort_unstable();
         f.debug_map()
-            .entries(self.0.keys().map(|key| (key, REDACTED_PLACEHOLDER)))
+            .entries(keys.into_iter().map(|key| (key, REDACTED_PLACEHOLDER)))
             .finish()
     }
 }
@@ -497,10 +500,57 @@ pub fn redact_urls_in_text(text: &str) -> String {
     out
 }
 
+/// Values shorter than this are never redacted by [`redact_secrets_in_text`]: they cannot be
+/// meaningful secrets, and replacing every `1` or `yes` would shred unrelated text.
+const MIN_REDACTED_SECRET_LEN: usize = 4;
+
+/// Replaces every occurrence of each of `secrets` in `text` with [`REDACTED_PLACEHOLDER`],
+/// then redacts URLs the way [`redact_urls_in_text`] does.
+///
+/// For free-form output a server produced while holding known secrets — e.g. a stdio server's
+/// stderr, which may echo back the `env` values it was given. Longer secrets are replaced
+/// first, so one that contains another is not left half-redacted.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::redact_secrets_in_text;
+///
+/// let line = "auth failed for token ghp_abc123 (see https://api.example.com/?key=ghp_abc123)";
+/// let redacted = redact_secrets_in_text(line, ["ghp_abc123", "1"]);
+/// assert!(!redacted.contains("ghp_abc123"));
+/// assert!(redacted.starts_with("auth failed for token <redacted> (see"));
+/// ```
+#[must_use]
+pub fn redact_secrets_in_text<'a>(
+    text: &str,
+    secrets: impl IntoIterator<Item = &'a str>,
+) -> String {
+    let mut secrets: Vec<&str> = secrets
+        .into_iter()
+        .filter(|secret| secret.len() >= MIN_REDACTED_SECRET_LEN)
+        .collect();
+    secrets.sort_unstable_by_key(|secret| std::cmp::Reverse(secret.len()));
+
+    let mut redacted = text.to_string();
+    for secret in secrets {
+        if redacted.contains(secret) {
+            redacted = redacted.replace(secret, REDACTED_PLACEHOLDER);
+        }
+    }
+    redact_urls_in_text(&redacted)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn redact_secrets_in_text_skips_short_values_and_prefers_longer_ones() {
+        let redacted = redact_secrets_in_text("key=abcd1234 flag=on", ["abcd", "abcd1234", "on"]);
+        assert_eq!(redacted, "key=<redacted> flag=on");
+    }
+
     #[test]
     fn redacted_map_values_keeps_keys_hides_values() {
         let mut map = HashMap::new();
@@ -511,6 +561,19 @@ mod tests {
         assert!(!debug_output.contains("Bearer secret"));
     }
 
+    #[test]
+    fn redacted_map_values_sorts_keys() {
+        let map: HashMap<String, String> = ["zeta", "alpha", "mid"]
+            .into_iter()
+            .map(|key| (key.to_string(), "v".to_string()))
+            .collect();
+
+        assert_eq!(
+            format!("{:?}", RedactedMapValues(&map)),
+            r#"{"alpha": "<redacted>", "mid": "<redacted>", "zeta": "<redacted>"}"#
+        );
+    }
+
     #[test]
     fn redacted_items_hides_every_entry() {
         let items = vec!["KEY=VALUE".to_string(), "just-a-secret".to_string()];
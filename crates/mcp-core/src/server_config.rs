@@ -49,7 +49,7 @@
 //! ```
 
 use crate::path::sanitize_path_for_error;
-use crate::redact::{RedactedItems, RedactedMapValues, RedactedUrl};
+use crate::redact::{RedactedItems, RedactedMapValues, RedactedUrl, UrlTailKind};
 use serde::de::Error as _;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -131,6 +131,11 @@ pub enum Transport {
         /// If `None`, inherits the parent process working directory.
         #[serde(default)]
         cwd: Option<PathBuf>,
+
+        /// Which of the parent process's environment variables the subprocess sees, before
+        /// `env` is applied on top.
+        #[serde(default, skip_serializing_if = "EnvPolicy::is_inherit")]
+        env_policy: EnvPolicy,
     },
     /// HTTP transport: communication via HTTP/HTTPS API.
     Http {
@@ -170,6 +175,111 @@ pub enum Transport {
     },
 }
 
+/// Names every non-[`Inherit`](EnvPolicy::Inherit) policy passes through from the parent
+/// environment: enough for a server to find executables, its home directory, and the user's
+/// locale and terminal. `USERPROFILE` and `SYSTEMROOT` are Windows' equivalents of `HOME` and
+/// the system directory most programs need to start at all.
+const MINIMAL_ENV_NAMES: &[&str] = &["PATH", "HOME", "USERPROFILE", "SYSTEMROOT", "LANG", "TERM"];
+
+/// Returns the parent environment variables [`EnvPolicy::Minimal`] passes through.
+///
+/// Exposed so the generated TypeScript runtime bridge can render the same list instead of
+/// hand-copying it.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::minimal_env_names;
+///
+/// assert!(minimal_env_names().contains(&"PATH"));
+/// ```
+#[must_use]
+pub const fn minimal_env_names() -> &'static [&'static str] {
+    MINIMAL_ENV_NAMES
+}
+
+/// Which parent environment variables a spawned stdio server inherits.
+///
+/// Without a policy a server sees every variable in the user's shell, secrets included. The
+/// configured `env` is always applied on top, whatever the policy. In `mcp.json` this is the
+/// `envPolicy` key: `"inherit"`, `"minimal"`, or `{"allowlist": ["AWS_PROFILE"]}`.
+///
+/// Names are compared ASCII-case-insensitively, as Windows does.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::EnvPolicy;
+///
+/// let policy: EnvPolicy = serde_json::from_str(r#"{"allowlist": ["AWS_PROFILE"]}"#).unwrap();
+/// assert!(policy.allows("PATH"));
+/// assert!(policy.allows("aws_profile"));
+/// assert!(!policy.allows("GITHUB_TOKEN"));
+/// assert!(EnvPolicy::Inherit.allows("GITHUB_TOKEN"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvPolicy {
+    /// The whole parent environment (the default).
+    #[default]
+    Inherit,
+    /// Only [`minimal_env_names`].
+    Minimal,
+    /// [`minimal_env_names`] plus these names.
+    Allowlist(Vec<String>),
+}
+
+impl EnvPolicy {
+    /// Whether this is [`EnvPolicy::Inherit`].
+    #[must_use]
+    pub const fn is_inherit(&self) -> bool {
+        matches!(self, Self::Inherit)
+    }
+
+    /// Whether the parent's variable `name` is passed through to the server.
+    #[must_use]
+    pub fn allows(&self, name: &str) -> bool {
+        let minimal = MINIMAL_ENV_NAMES
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(name));
+        match self {
+            Self::Inherit => true,
+            Self::Minimal => minimal,
+            Self::Allowlist(names) => {
+                minimal
+                    || names
+                        .iter()
+                        .any(|allowed| allowed.eq_ignore_ascii_case(name))
+            }
+        }
+    }
+
+    /// The variables of `parent` this policy passes through.
+    ///
+    /// Names that are not valid UTF-8 are only passed by [`EnvPolicy::Inherit`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::EnvPolicy;
+    ///
+    /// let parent = [("PATH", "/bin"), ("GITHUB_TOKEN", "ghp_x")];
+    /// let kept: Vec<_> = EnvPolicy::Minimal.filter(parent).collect();
+    /// assert_eq!(kept, [("PATH", "/bin")]);
+    /// ```
+    pub fn filter<K, V>(
+        &self,
+        parent: impl IntoIterator<Item = (K, V)>,
+    ) -> impl Iterator<Item = (K, V)>
+    where
+        K: AsRef<std::ffi::OsStr>,
+    {
+        parent.into_iter().filter(move |(name, _)| {
+            self.is_inherit() || name.as_ref().to_str().is_some_and(|name| self.allows(name))
+        })
+    }
+}
+
 impl fmt::Debug for Transport {
     /// Redacts the same fields, the same way, as [`ServerConfig`]'s own hand-written impl —
     /// see the "Security" section on that type's doc comment for the full rationale. Kept as a
@@ -199,12 +309,14 @@ impl fmt::Debug for Transport {
                 args,
                 env,
                 cwd,
+                env_policy,
             } => f
                 .debug_struct("Stdio")
                 .field("command", &sanitize_path_for_error(Path::new(command)))
                 .field("args", &RedactedItems(args))
                 .field("env", &RedactedMapValues(env))
                 .field("cwd", &cwd.as_deref().map(sanitize_path_for_error))
+                .field("env_policy", env_policy)
                 .finish(),
             Self::Http { url, headers } => f
                 .debug_struct("Http")
@@ -397,6 +509,64 @@ pub struct ServerConfig {
     /// `list_all_tools` to respond. Defaults to 30 seconds.
     #[serde(default = "default_discover_timeout")]
     discover_timeout: Duration,
+
+    /// The transport as written in the source config, before `${VAR}` expansion.
+    ///
+    /// Only set by [`ServerConfigBuilder::build_interpolated`], and only when expansion
+    /// changed something; `None` means `transport` is already the as-written form. Never
+    /// serialized: it exists so [`ConfigFingerprint`](crate::provenance::ConfigFingerprint)
+    /// can ignore the values behind `${VAR}` references.
+    #[serde(skip)]
+    unexpanded: Option<Box<Transport>>,
+}
+
+/// Placeholder [`ServerConfig::redacted`] substitutes for secret-shaped values.
+///
+/// Plain text rather than [`crate::REDACTED_PLACEHOLDER`], so a redacted config still passes
+/// [`crate::validate_server_config`] and can be read back.
+pub const REDACTED_CONFIG_VALUE: &str = "REDACTED";
+
+/// Rewrites `url` to keep what [`ConfigFingerprint`](crate::provenance::ConfigFingerprint)
+/// hashes — scheme, authority, path, query-parameter names, and whether userinfo is present —
+/// with every other part replaced by [`REDACTED_CONFIG_VALUE`] or dropped.
+fn redact_url_values(url: &str) -> String {
+    let Some(parts) = crate::redact::split_url(url) else {
+        return REDACTED_CONFIG_VALUE.to_string();
+    };
+    let mut redacted = format!("{}://", parts.scheme);
+    if parts.userinfo_present {
+        redacted.push_str(REDACTED_CONFIG_VALUE);
+        redacted.push('@');
+    }
+    redacted.push_str(parts.authority);
+    redacted.push_str(parts.path);
+    if let Some((UrlTailKind::Query, query)) = parts.tail {
+        let query = query.find('#').map_or(query, |pos| &query[..pos]);
+        let segments: Vec<String> = query
+            .split('&')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| match segment.split_once('=') {
+                Some((name, _)) => format!("{name}={REDACTED_CONFIG_VALUE}"),
+                None => REDACTED_CONFIG_VALUE.to_string(),
+            })
+            .collect();
+        if !segments.is_empty() {
+            redacted.push('?');
+            redacted.push_str(&segments.join("&"));
+        }
+    }
+    redacted
+}
+
+/// The serialized shape of a [`ServerConfig`], before validation.
+#[derive(Deserialize)]
+struct RawServerConfig {
+    #[serde(flatten)]
+    transport: Transport,
+    #[serde(default = "default_connect_timeout")]
+    connect_timeout: Duration,
+    #[serde(default = "default_discover_timeout")]
+    discover_timeout: Duration,
 }
 
 impl<'de> Deserialize<'de> for ServerConfig {
@@ -408,21 +578,12 @@ impl<'de> Deserialize<'de> for ServerConfig {
     where
         D: serde::Deserializer<'de>,
     {
-        #[derive(Deserialize)]
-        struct Raw {
-            #[serde(flatten)]
-            transport: Transport,
-            #[serde(default = "default_connect_timeout")]
-            connect_timeout: Duration,
-            #[serde(default = "default_discover_timeout")]
-            discover_timeout: Duration,
-        }
-
-        let raw = Raw::deserialize(deserializer)?;
+        let raw = RawServerConfig::deserialize(deserializer)?;
         let config = Self {
             transport: raw.transport,
             connect_timeout: raw.connect_timeout,
             discover_timeout: raw.discover_timeout,
+            unexpanded: None,
         };
         crate::validate_server_config(&config).map_err(D::Error::custom)?;
         Ok(config)
@@ -438,12 +599,14 @@ impl fmt::Debug for ServerConfig {
                 args,
                 env,
                 cwd,
+                env_policy,
             } => {
                 s.field("transport", &"stdio")
                     .field("command", &sanitize_path_for_error(Path::new(command)))
                     .field("args", &RedactedItems(args))
                     .field("env", &RedactedMapValues(env))
-                    .field("cwd", &cwd.as_deref().map(sanitize_path_for_error));
+                    .field("cwd", &cwd.as_deref().map(sanitize_path_for_error))
+                    .field("env_policy", env_policy);
             }
             Transport::Http { url, headers } => {
                 s.field("transport", &"http")
@@ -458,6 +621,7 @@ impl fmt::Debug for ServerConfig {
         }
         s.field("connect_timeout", &self.connect_timeout)
             .field("discover_timeout", &self.discover_timeout)
+            .field("interpolated", &self.unexpanded.is_some())
             .finish()
     }
 }
@@ -497,6 +661,144 @@ impl ServerConfig {
         &self.transport
     }
 
+    /// Returns the transport as written in the source config, before `${VAR}` expansion.
+    ///
+    /// Identical to [`Self::transport`] unless the config was built with
+    /// [`ServerConfigBuilder::build_interpolated`] and at least one `${...}` reference was
+    /// expanded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::{ServerConfig, Transport};
+    ///
+    /// let config = ServerConfig::builder()
+    ///     .command("npx".to_string())
+    ///     .env("TOKEN".to_string(), "${TOKEN}".to_string())
+    ///     .build_interpolated(|_| Some("secret".to_string()))
+    ///     .unwrap();
+    ///
+    /// let Transport::Stdio { env, .. } = config.unexpanded_transport() else { unreachable!() };
+    /// assert_eq!(env["TOKEN"], "${TOKEN}");
+    /// assert_eq!(config.env()["TOKEN"], "secret");
+    /// ```
+    #[must_use]
+    pub fn unexpanded_transport(&self) -> &Transport {
+        self.unexpanded.as_deref().unwrap_or(&self.transport)
+    }
+
+    /// Returns a copy with every secret-shaped value replaced by [`REDACTED_CONFIG_VALUE`],
+    /// safe to write to disk.
+    ///
+    /// Starts from [`Self::unexpanded_transport`], so `${VAR}` references stay references.
+    /// Every `args` entry and every `env`/`headers` value is replaced, and a URL keeps only its
+    /// scheme, authority, path, and query-parameter names, with any userinfo replaced. What is
+    /// kept is exactly what [`ConfigFingerprint`](crate::provenance::ConfigFingerprint) hashes,
+    /// so the copy fingerprints the same as `self`. It cannot be used to connect.
+    ///
+    /// A `${VAR}` reference left in `command` fails the shell-metacharacter check
+    /// [`Deserialize`] runs, so read a serialized copy back with
+    /// [`Self::deserialize_redacted`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::provenance::ConfigFingerprint;
+    /// use mcp_execution_core::ServerConfig;
+    ///
+    /// let config = ServerConfig::builder()
+    ///     .http_transport("https://api.example.com/mcp?token=sk-secret".to_string())
+    ///     .header("Authorization".to_string(), "Bearer sk-secret".to_string())
+    ///     .build()
+    ///     .unwrap();
+    /// let redacted = config.redacted();
+    ///
+    /// assert!(!serde_json::to_string(&redacted).unwrap().contains("sk-secret"));
+    /// assert_eq!(redacted.url(), Some("https://api.example.com/mcp?token=REDACTED"));
+    /// assert_eq!(ConfigFingerprint::compute(&redacted), ConfigFingerprint::compute(&config));
+    /// ```
+    #[must_use]
+    pub fn redacted(&self) -> Self {
+        let redact_values = |map: &HashMap<String, String>| {
+            map.keys()
+                .map(|key| (key.clone(), REDACTED_CONFIG_VALUE.to_string()))
+                .collect()
+        };
+        let transport = match self.unexpanded_transport() {
+            Transport::Stdio {
+                command,
+                args,
+                env,
+                cwd,
+                env_policy,
+            } => Transport::Stdio {
+                command: command.clone(),
+                args: vec![REDACTED_CONFIG_VALUE.to_string(); args.len()],
+                env: redact_values(env),
+                cwd: cwd.clone(),
+                env_policy: env_policy.clone(),
+            },
+            Transport::Http { url, headers } => Transport::Http {
+                url: redact_url_values(url),
+                headers: redact_values(headers),
+            },
+            Transport::Sse { url, headers } => Transport::Sse {
+                url: redact_url_values(url),
+                headers: redact_values(headers),
+            },
+        };
+        Self {
+            transport,
+            connect_timeout: self.connect_timeout,
+            discover_timeout: self.discover_timeout,
+            unexpanded: None,
+        }
+    }
+
+    /// Deserializes a config serialized from [`Self::redacted`], for use with
+    /// `#[serde(deserialize_with = "ServerConfig::deserialize_redacted")]`.
+    ///
+    /// Unlike [`Deserialize`], it skips [`crate::validate_server_config`]: a redacted copy keeps
+    /// `${VAR}` references as written and may name a command that no longer exists, neither of
+    /// which matters for a config that is only fingerprinted. It fingerprints the same as the
+    /// config it was redacted from. Never spawn it: the introspector re-validates every config
+    /// it connects with and rejects one like this.
+    ///
+    /// # Errors
+    ///
+    /// Returns a deserialization error if the input is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::provenance::ConfigFingerprint;
+    /// use mcp_execution_core::ServerConfig;
+    ///
+    /// let config = ServerConfig::builder()
+    ///     .command("${SRV_BIN}".to_string())
+    ///     .build_interpolated(|_| Some("srv".to_string()))
+    ///     .unwrap();
+    /// let json = serde_json::to_string(&config.redacted()).unwrap();
+    ///
+    /// assert!(serde_json::from_str::<ServerConfig>(&json).is_err());
+    /// let restored =
+    ///     ServerConfig::deserialize_redacted(&mut serde_json::Deserializer::from_str(&json))
+    ///         .unwrap();
+    /// assert_eq!(ConfigFingerprint::compute(&restored), ConfigFingerprint::compute(&config));
+    /// ```
+    pub fn deserialize_redacted<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = RawServerConfig::deserialize(deserializer)?;
+        Ok(Self {
+            transport: raw.transport,
+            connect_timeout: raw.connect_timeout,
+            discover_timeout: raw.discover_timeout,
+            unexpanded: None,
+        })
+    }
+
     /// Returns the command as a string slice, or `None` for a config that isn't
     /// [`Transport::Stdio`].
     ///
@@ -598,6 +900,31 @@ impl ServerConfig {
         }
     }
 
+    /// Returns which parent environment variables the server process inherits. Always
+    /// [`EnvPolicy::Inherit`] for a config that isn't [`Transport::Stdio`], which spawns
+    /// nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::{EnvPolicy, ServerConfig};
+    ///
+    /// let config = ServerConfig::builder()
+    ///     .command("server".to_string())
+    ///     .env_policy(EnvPolicy::Minimal)
+    ///     .build().unwrap();
+    ///
+    /// assert_eq!(config.env_policy(), &EnvPolicy::Minimal);
+    /// ```
+    #[must_use]
+    pub const fn env_policy(&self) -> &EnvPolicy {
+        const INHERIT: &EnvPolicy = &EnvPolicy::Inherit;
+        match &self.transport {
+            Transport::Stdio { env_policy, .. } => env_policy,
+            Transport::Http { .. } | Transport::Sse { .. } => INHERIT,
+        }
+    }
+
     /// Returns the URL for HTTP/SSE transport, if set. Always `None` for
     /// [`Transport::Stdio`].
     ///
@@ -739,6 +1066,7 @@ pub struct ServerConfigBuilder {
     args: Vec<String>,
     env: HashMap<String, String>,
     cwd: Option<PathBuf>,
+    env_policy: EnvPolicy,
     url: Option<String>,
     headers: HashMap<String, String>,
     connect_timeout: Duration,
@@ -759,6 +1087,7 @@ impl fmt::Debug for ServerConfigBuilder {
             .field("args", &RedactedItems(&self.args))
             .field("env", &RedactedMapValues(&self.env))
             .field("cwd", &self.cwd.as_deref().map(sanitize_path_for_error))
+            .field("env_policy", &self.env_policy)
             .field("url", &self.url.as_deref().map(RedactedUrl))
             .field("headers", &RedactedMapValues(&self.headers))
             .field("connect_timeout", &self.connect_timeout)
@@ -775,6 +1104,7 @@ impl Default for ServerConfigBuilder {
             args: Vec::new(),
             env: HashMap::new(),
             cwd: None,
+            env_policy: EnvPolicy::Inherit,
             url: None,
             headers: HashMap::new(),
             connect_timeout: DEFAULT_CONNECT_TIMEOUT,
@@ -897,6 +1227,25 @@ impl ServerConfigBuilder {
         self
     }
 
+    /// Sets which parent environment variables the server process inherits (stdio only).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::{EnvPolicy, ServerConfig};
+    ///
+    /// let config = ServerConfig::builder()
+    ///     .command("mcp-server".to_string())
+    ///     .env_policy(EnvPolicy::Allowlist(vec!["AWS_PROFILE".to_string()]))
+    ///     .build().unwrap();
+    /// assert!(config.env_policy().allows("AWS_PROFILE"));
+    /// ```
+    #[must_use]
+    pub fn env_policy(mut self, env_policy: EnvPolicy) -> Self {
+        self.env_policy = env_policy;
+        self
+    }
+
     /// Configures HTTP transport with the given URL.
     ///
     /// This sets the transport type to HTTP and configures the endpoint URL.
@@ -1085,6 +1434,57 @@ impl ServerConfigBuilder {
         Ok(config)
     }
 
+    /// Builds and validates the `ServerConfig` after expanding `${VAR}` and
+    /// `${VAR:-default}` references.
+    ///
+    /// Every string-valued transport field (`command`, `args`, `env` values, `cwd`, `url`,
+    /// `headers` values) is expanded with `lookup` — see [`crate::interpolation`] for the
+    /// grammar — and the result then goes through exactly the validation [`Self::build`]
+    /// performs. The as-written transport is kept and exposed through
+    /// [`ServerConfig::unexpanded_transport`].
+    ///
+    /// Pass [`crate::interpolation::process_env`] to resolve against the current process
+    /// environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ValidationError` naming the field (and, for an unset variable, the
+    /// variable — never its value) if a reference is malformed or names an unset variable
+    /// without a default, plus every error [`Self::build`] can return.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::ServerConfig;
+    ///
+    /// let config = ServerConfig::builder()
+    ///     .http_transport("https://${HOST:-api.example.com}/mcp".to_string())
+    ///     .header("Authorization".to_string(), "Bearer ${API_KEY}".to_string())
+    ///     .build_interpolated(|name| (name == "API_KEY").then(|| "sk-123".to_string()))
+    ///     .unwrap();
+    /// assert_eq!(config.url(), Some("https://api.example.com/mcp"));
+    ///
+    /// let err = ServerConfig::builder()
+    ///     .command("npx".to_string())
+    ///     .env("GITHUB_TOKEN".to_string(), "${GITHUB_TOKEN}".to_string())
+    ///     .build_interpolated(|_| None)
+    ///     .unwrap_err();
+    /// assert!(err.to_string().contains("GITHUB_TOKEN"));
+    /// ```
+    pub fn build_interpolated<F>(self, lookup: F) -> crate::Result<ServerConfig>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut config = self.build_structural()?;
+        let expanded = crate::interpolation::expand_transport(&config.transport, lookup)?;
+        if expanded != config.transport {
+            let unexpanded = std::mem::replace(&mut config.transport, expanded);
+            config.unexpanded = Some(Box::new(unexpanded));
+        }
+        crate::validate_server_config(&config)?;
+        Ok(config)
+    }
+
     /// Checks structural completeness (command/url presence) and assembles
     /// the `ServerConfig`, without running security validation.
     ///
@@ -1112,6 +1512,7 @@ impl ServerConfigBuilder {
                     args: self.args,
                     env: self.env,
                     cwd: self.cwd,
+                    env_policy: self.env_policy,
                 }
             }
             TransportKind::Http => {
@@ -1142,6 +1543,7 @@ impl ServerConfigBuilder {
             transport,
             connect_timeout: self.connect_timeout,
             discover_timeout: self.discover_timeout,
+            unexpanded: None,
         })
     }
 }
@@ -1318,6 +1720,60 @@ mod tests {
         assert_eq!(config, deserialized);
     }
 
+    #[test]
+    fn test_env_policy_serialization() {
+        let inherit = ServerConfig::builder()
+            .command("mcp-server".to_string())
+            .build()
+            .unwrap();
+        assert!(
+            !serde_json::to_string(&inherit)
+                .unwrap()
+                .contains("env_policy")
+        );
+
+        let allowlist = ServerConfig::builder()
+            .command("mcp-server".to_string())
+            .env_policy(EnvPolicy::Allowlist(vec!["AWS_PROFILE".to_string()]))
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&allowlist).unwrap();
+        assert_eq!(
+            json["env_policy"],
+            serde_json::json!({"allowlist": ["AWS_PROFILE"]})
+        );
+        assert_eq!(
+            serde_json::from_value::<ServerConfig>(json).unwrap(),
+            allowlist
+        );
+
+        let minimal: EnvPolicy = serde_json::from_str(r#""minimal""#).unwrap();
+        assert_eq!(minimal, EnvPolicy::Minimal);
+        assert!(serde_json::from_str::<EnvPolicy>(r#""none""#).is_err());
+    }
+
+    #[test]
+    fn test_env_policy_filter() {
+        let parent = [
+            ("Path", "/bin"),
+            ("TERM", "xterm"),
+            ("AWS_PROFILE", "dev"),
+            ("TOKEN", "x"),
+        ];
+        let names = |policy: &EnvPolicy| {
+            policy
+                .filter(parent)
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&EnvPolicy::Inherit).len(), 4);
+        assert_eq!(names(&EnvPolicy::Minimal), ["Path", "TERM"]);
+        assert_eq!(
+            names(&EnvPolicy::Allowlist(vec!["aws_profile".to_string()])),
+            ["Path", "TERM", "AWS_PROFILE"]
+        );
+    }
+
     #[test]
     fn test_server_config_clone() {
         let config = ServerConfig::builder()
@@ -1767,6 +2223,7 @@ mod tests {
                 secret_env.to_string(),
             )]),
             cwd: None,
+            env_policy: EnvPolicy::default(),
         };
 
         let debug_str = format!("{transport:?}");
@@ -1805,4 +2262,50 @@ mod tests {
         assert!(debug_str.contains("Authorization"));
         assert!(!debug_str.contains(secret));
     }
+
+    #[test]
+    fn test_redacted_stdio_keeps_fingerprint_and_round_trips() {
+        use crate::provenance::ConfigFingerprint;
+
+        let config = ServerConfig::builder()
+            .command("npx".to_string())
+            .args(vec!["--api-key".to_string(), "sk-arg-secret".to_string()])
+            .env("TOKEN".to_string(), "${TOKEN}".to_string())
+            .env("MODE".to_string(), "plain-secret".to_string())
+            .build_interpolated(|_| Some("sk-env-secret".to_string()))
+            .unwrap();
+        let redacted = config.redacted();
+        let json = serde_json::to_string(&redacted).unwrap();
+
+        for secret in ["sk-arg-secret", "sk-env-secret", "plain-secret"] {
+            assert!(!json.contains(secret), "{secret} persisted");
+        }
+        assert_eq!(redacted.args().len(), 2);
+        assert_eq!(
+            ConfigFingerprint::compute(&redacted),
+            ConfigFingerprint::compute(&config)
+        );
+        let read_back: ServerConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            ConfigFingerprint::compute(&read_back),
+            ConfigFingerprint::compute(&config)
+        );
+    }
+
+    #[test]
+    fn test_redacted_url_keeps_only_fingerprinted_parts() {
+        for (url, expected) in [
+            (
+                "https://user:pw@api.example.com/mcp?a=1&b&a=2#frag",
+                "https://REDACTED@api.example.com/mcp?a=REDACTED&REDACTED&a=REDACTED",
+            ),
+            (
+                "https://api.example.com/mcp#frag",
+                "https://api.example.com/mcp",
+            ),
+            ("not a url", REDACTED_CONFIG_VALUE),
+        ] {
+            assert_eq!(redact_url_values(url), expected);
+        }
+    }
 }
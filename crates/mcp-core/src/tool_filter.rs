@@ -0,0 +1,534 @@
+//! Include/exclude glob filters selecting which of a server's tools get generated.
+//!
+//! Some MCP servers expose well over a hundred tools when a caller only wants a handful of
+//! them. A [`ToolFilter`] narrows the discovered tool list before code generation, so an
+//! excluded tool is absent from every generated artifact: its `.ts` file, `index.ts`, the
+//! `_meta.json` sidecar, and therefore any `SKILL.md` built from that sidecar.
+//!
+//! The filter is recorded in [`GenerationProvenance`](crate::provenance::GenerationProvenance)
+//! rather than folded into its tool digest: the digest still covers the server's *full*
+//! discovered tool list, so narrowing or widening the filter registers as a change to how the
+//! bindings were generated, not as a change to what the server exposes. The same record is what
+//! makes the filter persistent — a later regeneration reads it back out of the existing
+//! `_meta.json` (see [`read_persisted_tool_filter`]) instead of silently reverting to every tool.
+//!
+//! # Pattern syntax
+//!
+//! Patterns are matched against a tool's raw MCP name, case-sensitively, over the whole name:
+//! `*` matches any run of characters (including none) and `?` matches exactly one character.
+//! Every other character matches itself, so a pattern without either metacharacter is an exact
+//! tool name.
+//!
+//! # Examples
+//!
+//! ```
+//! use mcp_execution_core::tool_filter::ToolFilter;
+//!
+//! let filter = ToolFilter::new(
+//!     vec!["issue_*".to_string()],
+//!     vec!["*_admin_*".to_string()],
+//! )
+//! .unwrap();
+//!
+//! assert!(filter.matches("issue_create"));
+//! assert!(!filter.matches("issue_admin_delete"));
+//! assert!(!filter.matches("repo_create"));
+//! ```
+
+use crate::metadata::{MAX_PERSISTED_METADATA_SIZE, METADATA_FILE_NAME};
+use crate::path::sanitize_path_for_error;
+use crate::untrusted::sanitize_untrusted_inline;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error as ThisError;
+
+/// Maximum number of patterns accepted in each of a [`ToolFilter`]'s `include` and `exclude`
+/// lists.
+///
+/// Matches the 500-tool ceiling `mcp-execution-skill` enforces per server (`MAX_TOOL_FILES`):
+/// an `exclude` list naming every tool one by one — the shape `save_categorized_tools`'s
+/// `exclude` parameter takes — never needs more entries than that.
+pub const MAX_TOOL_FILTER_PATTERNS: usize = 500;
+
+/// Maximum length, in bytes, of a single [`ToolFilter`] pattern.
+///
+/// Mirrors the 128-byte cap `mcp-execution-server` applies to a categorized tool's `name`, so
+/// any tool name that can be categorized can also be excluded by its exact name.
+pub const MAX_TOOL_FILTER_PATTERN_LEN: usize = 128;
+
+/// A validated include/exclude glob filter over a server's tool names.
+///
+/// A tool is kept when it matches at least one `include` pattern (or `include` is empty) and
+/// matches no `exclude` pattern — `exclude` always wins. The default filter is empty and keeps
+/// every tool.
+///
+/// Both lists are sorted and deduplicated on construction, so two filters built from the same
+/// patterns in a different order compare (and serialize) identically. [`Deserialize`] is routed
+/// through the same validation as [`ToolFilter::new`], so a hand-edited `_meta.json` cannot
+/// produce a filter violating its invariants.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::tool_filter::ToolFilter;
+///
+/// let a = ToolFilter::new(vec!["b_*".to_string(), "a_*".to_string()], vec![]).unwrap();
+/// let b = ToolFilter::new(vec!["a_*".to_string(), "b_*".to_string()], vec![]).unwrap();
+/// assert_eq!(a, b);
+///
+/// assert!(ToolFilter::default().is_empty());
+/// assert!(ToolFilter::default().matches("anything"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawToolFilter")]
+pub struct ToolFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exclude: Vec<String>,
+}
+
+/// Unvalidated serde landing zone for [`ToolFilter`]'s `Deserialize`.
+#[derive(Deserialize)]
+struct RawToolFilter {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+impl TryFrom<RawToolFilter> for ToolFilter {
+    type Error = Error;
+
+    fn try_from(raw: RawToolFilter) -> Result<Self> {
+        Self::new(raw.include, raw.exclude)
+    }
+}
+
+impl ToolFilter {
+    /// Builds a filter from `include` and `exclude` glob patterns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ValidationError`] (naming `include` or `exclude` as the field) if either
+    /// list has more than [`MAX_TOOL_FILTER_PATTERNS`] entries, or any pattern is empty, longer
+    /// than [`MAX_TOOL_FILTER_PATTERN_LEN`] bytes, or contains a whitespace or control
+    /// character — neither of which can ever appear in a valid
+    /// [`ToolName`](crate::ToolName), so such a pattern could never match anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::tool_filter::ToolFilter;
+    ///
+    /// assert!(ToolFilter::new(vec!["".to_string()], vec![]).is_err());
+    /// assert!(ToolFilter::new(vec![], vec!["delete_*".to_string()]).is_ok());
+    /// ```
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Result<Self> {
+        Ok(Self {
+            include: normalize_patterns("include", include)?,
+            exclude: normalize_patterns("exclude", exclude)?,
+        })
+    }
+
+    /// Returns the sorted, deduplicated `include` patterns.
+    #[must_use]
+    pub fn include(&self) -> &[String] {
+        &self.include
+    }
+
+    /// Returns the sorted, deduplicated `exclude` patterns.
+    #[must_use]
+    pub fn exclude(&self) -> &[String] {
+        &self.exclude
+    }
+
+    /// Returns `true` if this filter has no patterns at all and therefore keeps every tool.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Returns `true` if the tool named `tool_name` is kept by this filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_core::tool_filter::ToolFilter;
+    ///
+    /// let filter = ToolFilter::new(vec![], vec!["delete_?".to_string()]).unwrap();
+    /// assert!(!filter.matches("delete_a"));
+    /// assert!(filter.matches("delete_all"));
+    /// ```
+    #[must_use]
+    pub fn matches(&self, tool_name: &str) -> bool {
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| glob_matches(pattern, tool_name));
+        included
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| glob_matches(pattern, tool_name))
+    }
+}
+
+/// Validates every pattern in `patterns`, then sorts and deduplicates the list.
+///
+/// Rejected patterns are echoed back through [`sanitize_untrusted_inline`]: a filter may come
+/// from a hand-edited `_meta.json` or an MCP client's tool call, neither of which is trusted.
+fn normalize_patterns(field: &str, mut patterns: Vec<String>) -> Result<Vec<String>> {
+    if patterns.len() > MAX_TOOL_FILTER_PATTERNS {
+        return Err(Error::ValidationError {
+            field: field.to_string(),
+            reason: format!(
+                "{} patterns exceeds the limit of {MAX_TOOL_FILTER_PATTERNS}",
+                patterns.len()
+            ),
+        });
+    }
+
+    for pattern in &patterns {
+        let reason = if pattern.is_empty() {
+            Some("pattern must not be empty".to_string())
+        } else if pattern.len() > MAX_TOOL_FILTER_PATTERN_LEN {
+            Some(format!(
+                "pattern is {} bytes, exceeding the {MAX_TOOL_FILTER_PATTERN_LEN} byte limit",
+                pattern.len()
+            ))
+        } else if pattern.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            Some(format!(
+                "pattern '{}' contains whitespace or a control character",
+                sanitize_untrusted_inline(pattern)
+            ))
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(Error::ValidationError {
+                field: field.to_string(),
+                reason,
+            });
+        }
+    }
+
+    patterns.sort_unstable();
+    patterns.dedup();
+    Ok(patterns)
+}
+
+/// Matches `name` against a `*`/`?` glob `pattern` over the whole string, using the
+/// [pattern syntax](self#pattern-syntax) of tool filters.
+///
+/// Iterative with single-star backtracking rather than recursive, so a pattern made of many
+/// `*`s costs `O(pattern × name)` at worst instead of exponential time.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::tool_filter::glob_matches;
+///
+/// assert!(glob_matches("@modelcontextprotocol/*", "@modelcontextprotocol/server-github"));
+/// assert!(!glob_matches("issue_?", "issue_12"));
+/// ```
+#[must_use]
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the most recent `*` in `pattern`, and the `name` position it is currently
+    // assumed to have consumed up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, consumed)) => {
+                    p = star + 1;
+                    n = consumed + 1;
+                    backtrack = Some((star, consumed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Minimal probe of a `_meta.json` sidecar, reading only the persisted tool filter.
+///
+/// Deliberately not [`crate::metadata::ServerMetadata`]: a sidecar from an older schema
+/// version, or one this build would otherwise reject, still carries a usable filter (or none),
+/// and failing a regeneration over an unrelated field would defeat the point of reading it.
+#[derive(Deserialize)]
+struct PersistedFilterProbe {
+    #[serde(default)]
+    provenance: Option<PersistedProvenanceProbe>,
+}
+
+#[derive(Deserialize)]
+struct PersistedProvenanceProbe {
+    #[serde(default)]
+    tool_filter: ToolFilter,
+}
+
+/// Error returned by [`read_persisted_tool_filter`].
+#[derive(Debug, ThisError)]
+pub enum PersistedFilterError {
+    /// The sidecar exists but could not be read.
+    #[error("failed to read {path}: {source}")]
+    Io {
+        /// Sanitized path of the sidecar (see [`crate::sanitize_path_for_error`]).
+        path: String,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// The sidecar is larger than any real generator output could be.
+    #[error("{path} is {size} bytes, exceeding the {limit} byte limit")]
+    TooLarge {
+        /// Sanitized path of the sidecar.
+        path: String,
+        /// Observed size in bytes.
+        size: u64,
+        /// Maximum accepted size in bytes.
+        limit: u64,
+    },
+    /// The sidecar is not valid JSON, or its recorded filter fails [`ToolFilter::new`]'s
+    /// validation.
+    #[error("failed to parse the tool filter recorded in {path}: {source}")]
+    Parse {
+        /// Sanitized path of the sidecar.
+        path: String,
+        /// Underlying parse error.
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// Reads the tool filter recorded by a previous generation into `server_dir`'s `_meta.json`.
+///
+/// Returns `Ok(None)` when there is no sidecar yet (a first-time generation) or the sidecar
+/// records no filter, so callers can fall back to generating every tool.
+///
+/// # Errors
+///
+/// Returns [`PersistedFilterError`] if the sidecar exists but cannot be read, is implausibly
+/// large, or is not valid JSON / records an invalid filter.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_core::tool_filter::read_persisted_tool_filter;
+///
+/// let dir = tempfile::tempdir().unwrap();
+/// assert_eq!(read_persisted_tool_filter(dir.path()).unwrap(), None);
+/// ```
+pub fn read_persisted_tool_filter(
+    server_dir: &Path,
+) -> std::result::Result<Option<ToolFilter>, PersistedFilterError> {
+    let meta_path = server_dir.join(METADATA_FILE_NAME);
+    let io_error = |source| PersistedFilterError::Io {
+        path: sanitize_path_for_error(&meta_path),
+        source,
+    };
+
+    let file_metadata = match std::fs::metadata(&meta_path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(io_error(err)),
+    };
+    if file_metadata.len() > MAX_PERSISTED_METADATA_SIZE {
+        return Err(PersistedFilterError::TooLarge {
+            path: sanitize_path_for_error(&meta_path),
+            size: file_metadata.len(),
+            limit: MAX_PERSISTED_METADATA_SIZE,
+        });
+    }
+
+    let content = std::fs::read_to_string(&meta_path).map_err(io_error)?;
+    let probe: PersistedFilterProbe =
+        serde_json::from_str(&content).map_err(|source| PersistedFilterError::Parse {
+            path: sanitize_path_for_error(&meta_path),
+            source,
+        })?;
+
+    Ok(probe
+        .provenance
+        .map(|provenance| provenance.tool_filter)
+        .filter(|filter| !filter.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(include: &[&str], exclude: &[&str]) -> ToolFilter {
+        ToolFilter::new(
+            include.iter().map(ToString::to_string).collect(),
+            exclude.iter().map(ToString::to_string).collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn glob_star_matches_any_run_including_empty() {
+        assert!(glob_matches("issue_*", "issue_"));
+        assert!(glob_matches("issue_*", "issue_create"));
+        assert!(glob_matches("*_admin_*", "repo_admin_delete"));
+        assert!(!glob_matches("*_admin_*", "repo_administer"));
+        assert!(glob_matches("*", ""));
+    }
+
+    #[test]
+    fn glob_question_mark_matches_exactly_one_char() {
+        assert!(glob_matches("get_?", "get_a"));
+        assert!(!glob_matches("get_?", "get_"));
+        assert!(!glob_matches("get_?", "get_ab"));
+    }
+
+    #[test]
+    fn glob_without_metacharacters_is_exact_match() {
+        assert!(glob_matches("create_issue", "create_issue"));
+        assert!(!glob_matches("create_issue", "create_issues"));
+        assert!(!glob_matches("create_issue", "Create_issue"));
+    }
+
+    #[test]
+    fn glob_backtracks_across_multiple_stars() {
+        assert!(glob_matches("a*b*c", "axxbyyc"));
+        assert!(glob_matches("a*b*c", "abbbc"));
+        assert!(!glob_matches("a*b*c", "axxbyy"));
+    }
+
+    #[test]
+    fn glob_pathological_pattern_terminates_quickly() {
+        let pattern = "*a".repeat(60);
+        let name = "a".repeat(59);
+        assert!(!glob_matches(&pattern, &name));
+    }
+
+    #[test]
+    fn empty_filter_keeps_every_tool() {
+        let filter = ToolFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.matches("anything"));
+    }
+
+    #[test]
+    fn include_only_keeps_matching_tools() {
+        let filter = filter(&["issue_*", "pr_*"], &[]);
+        assert!(filter.matches("issue_create"));
+        assert!(filter.matches("pr_merge"));
+        assert!(!filter.matches("repo_create"));
+    }
+
+    #[test]
+    fn exclude_wins_over_include() {
+        let filter = filter(&["issue_*"], &["*_admin_*"]);
+        assert!(filter.matches("issue_create"));
+        assert!(!filter.matches("issue_admin_delete"));
+    }
+
+    #[test]
+    fn exclude_only_keeps_everything_else() {
+        let filter = filter(&[], &["delete_repo"]);
+        assert!(filter.matches("create_repo"));
+        assert!(!filter.matches("delete_repo"));
+    }
+
+    #[test]
+    fn patterns_are_sorted_and_deduplicated() {
+        let filter = filter(&["b", "a", "b"], &["z", "y"]);
+        assert_eq!(filter.include(), ["a", "b"]);
+        assert_eq!(filter.exclude(), ["y", "z"]);
+    }
+
+    #[test]
+    fn new_rejects_empty_pattern() {
+        let err = ToolFilter::new(vec![String::new()], vec![]).unwrap_err();
+        assert!(matches!(err, Error::ValidationError { ref field, .. } if field == "include"));
+    }
+
+    #[test]
+    fn new_rejects_oversized_pattern() {
+        let long = "a".repeat(MAX_TOOL_FILTER_PATTERN_LEN + 1);
+        let err = ToolFilter::new(vec![], vec![long]).unwrap_err();
+        assert!(matches!(err, Error::ValidationError { ref field, .. } if field == "exclude"));
+    }
+
+    #[test]
+    fn new_rejects_too_many_patterns() {
+        let patterns = (0..=MAX_TOOL_FILTER_PATTERNS)
+            .map(|i| format!("tool_{i}"))
+            .collect();
+        assert!(ToolFilter::new(patterns, vec![]).is_err());
+    }
+
+    #[test]
+    fn new_rejects_control_characters_without_echoing_them() {
+        let err = ToolFilter::new(vec!["evil\u{1b}[2J".to_string()], vec![]).unwrap_err();
+        assert!(!err.to_string().contains('\u{1b}'));
+    }
+
+    #[test]
+    fn serde_omits_empty_lists_and_round_trips() {
+        let filter = filter(&[], &["delete_*"]);
+        let json = serde_json::to_string(&filter).unwrap();
+        assert_eq!(json, r#"{"exclude":["delete_*"]}"#);
+        let back: ToolFilter = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, filter);
+    }
+
+    #[test]
+    fn deserialize_validates_patterns() {
+        assert!(serde_json::from_str::<ToolFilter>(r#"{"include":[""]}"#).is_err());
+    }
+
+    #[test]
+    fn read_persisted_tool_filter_reads_provenance_field() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(METADATA_FILE_NAME),
+            r#"{"schema_version":2,"provenance":{"tool_filter":{"include":["issue_*"]}}}"#,
+        )
+        .unwrap();
+
+        let persisted = read_persisted_tool_filter(dir.path()).unwrap();
+        assert_eq!(persisted, Some(filter(&["issue_*"], &[])));
+    }
+
+    #[test]
+    fn read_persisted_tool_filter_none_without_recorded_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(METADATA_FILE_NAME),
+            r#"{"schema_version":2,"provenance":{}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(read_persisted_tool_filter(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn read_persisted_tool_filter_rejects_malformed_json() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(METADATA_FILE_NAME), "not json").unwrap();
+
+        let err = read_persisted_tool_filter(dir.path()).unwrap_err();
+        assert!(matches!(err, PersistedFilterError::Parse { .. }));
+    }
+}
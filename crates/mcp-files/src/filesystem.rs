@@ -18,6 +18,18 @@
 //! mid-export leaves the previous export (or nothing, on a first export)
 //! untouched at the target path — never a partially written tree.
 //!
+//! # Incremental export
+//!
+//! With [`ExportOptions::with_incremental`], an export into an existing
+//! directory compares each file's content hash with the file already on
+//! disk and only replaces the ones that differ, each by a single rename;
+//! entries the `FileSystem` no longer contains are removed, except the
+//! top-level [`NPM_INSTALL_ARTIFACTS`] an `npm install` in the directory
+//! created. Every file it
+//! replaces or removes is first moved into a `.stale-*` sibling, so a failure
+//! part-way through restores the directory as it was, just like the
+//! whole-directory swap.
+//!
 //! # Performance Optimizations
 //!
 //! 1. **Directory Pre-creation**: Creates all directories first in single pass
@@ -61,7 +73,9 @@
 //! ```
 
 use crate::types::{FileEntry, FilePath, FilesError, FilesResourceKind, Result};
+use mcp_execution_codegen::progressive::generator::NPM_INSTALL_ARTIFACTS;
 use mcp_execution_core::open_confined_write;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Write;
@@ -458,6 +472,14 @@ impl FileSystem {
     /// left exactly as it was — either untouched or, if it did not exist yet,
     /// still absent. See the [module-level docs](self) for details.
     ///
+    /// With [`ExportOptions::with_incremental`], an existing `base_path`
+    /// directory is updated in place instead: only files whose content
+    /// differs are rewritten and stale entries are removed, with the same
+    /// all-or-nothing outcome if this call returns an error. `base_path` is
+    /// replaced wholesale as usual when it does not exist yet, or when an
+    /// existing entry is a directory where this `FileSystem` has a file (or
+    /// the reverse).
+    ///
     /// Concurrent exports of the *same* `base_path` from different processes
     /// are not locked against each other and can still race on the final
     /// swap — one export's result may be silently overwritten by another's
@@ -509,6 +531,16 @@ impl FileSystem {
         let (staging, canonical_staging) =
             self.stage_export(target, options.confine_to.as_deref())?;
 
+        if options.incremental
+            && fs::symlink_metadata(target).is_ok_and(|metadata| metadata.is_dir())
+            && let Some(plan) = self.plan_incremental_export(target)?
+        {
+            // Only the changed files are staged; `staging` is dropped (and
+            // removed) once they have been moved into `target`.
+            self.write_changed_files(&canonical_staging, &plan, options)?;
+            return Self::apply_incremental_export(target, &canonical_staging, &plan);
+        }
+
         // Phase 3: Write all files into the staging directory. If this fails,
         // `staging` is dropped here and its `Drop` impl removes the partial
         // tree — `target` is never touched.
@@ -779,6 +811,224 @@ impl FileSystem {
         Ok(())
     }
 
+    /// Compares this `FileSystem` with the existing directory `target` and
+    /// lists what an incremental export has to change.
+    ///
+    /// Returns `None` if `target`'s shape conflicts with this `FileSystem`
+    /// (a directory where a file belongs, or a non-directory where a
+    /// directory belongs), which only a whole-directory swap can resolve.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a VFS path cannot be mapped to a disk path, or
+    /// `target` or a file under it cannot be read.
+    fn plan_incremental_export(&self, target: &Path) -> Result<Option<IncrementalPlan>> {
+        let mut expected_files = HashMap::with_capacity(self.file_count());
+        for (vfs_path, file) in self.files() {
+            let relative = Self::vfs_to_disk_path(vfs_path.as_str(), Path::new(""))?;
+            expected_files.insert(relative, file.content());
+        }
+        let expected_dirs: HashSet<&Path> = expected_files
+            .keys()
+            .flat_map(|relative| relative.ancestors().skip(1))
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .collect();
+
+        let mut plan = IncrementalPlan::default();
+        let mut existing_files = HashSet::new();
+        let mut pending_dirs = vec![PathBuf::new()];
+        while let Some(dir) = pending_dirs.pop() {
+            let disk_dir = target.join(&dir);
+            let entries = fs::read_dir(&disk_dir).map_err(|e| FilesError::IoError {
+                path: disk_dir.display().to_string(),
+                source: e,
+            })?;
+            for entry in entries {
+                let entry = entry.map_err(|e| FilesError::IoError {
+                    path: disk_dir.display().to_string(),
+                    source: e,
+                })?;
+                let relative = dir.join(entry.file_name());
+                let file_type = entry.file_type().map_err(|e| FilesError::IoError {
+                    path: entry.path().display().to_string(),
+                    source: e,
+                })?;
+
+                if let Some(content) = expected_files.get(&relative) {
+                    if file_type.is_dir() {
+                        return Ok(None);
+                    }
+                    // A symlink is replaced even if it points at matching
+                    // content, since a whole-directory export never leaves one.
+                    if !file_type.is_file() || !file_matches(&entry.path(), content)? {
+                        plan.changed.push(relative.clone());
+                    }
+                    existing_files.insert(relative);
+                } else if expected_dirs.contains(relative.as_path()) {
+                    if !file_type.is_dir() {
+                        return Ok(None);
+                    }
+                    pending_dirs.push(relative);
+                } else if !(dir.as_os_str().is_empty()
+                    && NPM_INSTALL_ARTIFACTS
+                        .iter()
+                        .any(|name| entry.file_name() == *name))
+                {
+                    plan.stale.push(relative);
+                }
+            }
+        }
+
+        plan.changed.extend(
+            expected_files
+                .into_keys()
+                .filter(|relative| !existing_files.contains(relative)),
+        );
+        Ok(Some(plan))
+    }
+
+    /// Writes the files `plan` lists as changed into the staging directory.
+    fn write_changed_files(
+        &self,
+        staging_base: &Path,
+        plan: &IncrementalPlan,
+        options: &ExportOptions,
+    ) -> Result<()> {
+        let changed: HashSet<&Path> = plan.changed.iter().map(PathBuf::as_path).collect();
+        for (vfs_path, file) in self.files() {
+            let relative = Self::vfs_to_disk_path(vfs_path.as_str(), Path::new(""))?;
+            if changed.contains(relative.as_path()) {
+                write_file_atomic(
+                    &staging_base.join(&relative),
+                    file.content(),
+                    options.atomic,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the staged files `plan` lists as changed into `target` and
+    /// removes its stale entries.
+    ///
+    /// Each replaced or removed entry is first moved into a `.stale-*`
+    /// sibling of `target` (see [`Self::unique_sibling_name`]). If any step
+    /// fails, every completed step is undone before the error is returned, so
+    /// `target` is left as it was. As with [`Self::swap_into_place`], this
+    /// does not extend to a process killed part-way through: `target` is then
+    /// left partly updated and the displaced originals sit in the `.stale-*`
+    /// sibling until [`Self::sweep_stale_artifacts`] reclaims it.
+    ///
+    /// Does nothing, and creates no sibling, when `plan` is empty.
+    fn apply_incremental_export(
+        target: &Path,
+        staging_base: &Path,
+        plan: &IncrementalPlan,
+    ) -> Result<()> {
+        if plan.is_empty() {
+            return Ok(());
+        }
+
+        let parent = target.parent().ok_or_else(|| FilesError::InvalidPath {
+            path: format!("Target path has no parent directory: {}", target.display()),
+        })?;
+        let displaced = parent.join(Self::unique_sibling_name(target));
+        fs::create_dir(&displaced).map_err(|e| FilesError::IoError {
+            path: displaced.display().to_string(),
+            source: e,
+        })?;
+
+        let mut applied = Vec::new();
+        let result =
+            Self::apply_incremental_steps(target, staging_base, &displaced, plan, &mut applied);
+        if result.is_err() {
+            Self::roll_back_incremental_steps(target, &displaced, applied);
+        }
+
+        Self::remove_artifact_best_effort(&displaced);
+        result
+    }
+
+    /// Performs [`Self::apply_incremental_export`]'s moves, recording each
+    /// completed one in `applied` so it can be rolled back.
+    fn apply_incremental_steps(
+        target: &Path,
+        staging_base: &Path,
+        displaced: &Path,
+        plan: &IncrementalPlan,
+        applied: &mut Vec<IncrementalStep>,
+    ) -> Result<()> {
+        for relative in &plan.changed {
+            let destination = target.join(relative);
+            if fs::symlink_metadata(&destination).is_ok() {
+                Self::move_aside(target, displaced, relative)?;
+                applied.push(IncrementalStep::MovedAside(relative.clone()));
+            } else if let Some(dir) = destination.parent() {
+                Self::create_dir_recorded(target, dir, applied)?;
+            }
+
+            rename_with_context(&staging_base.join(relative), &destination)?;
+            applied.push(IncrementalStep::Placed(relative.clone()));
+        }
+
+        for relative in &plan.stale {
+            Self::move_aside(target, displaced, relative)?;
+            applied.push(IncrementalStep::MovedAside(relative.clone()));
+        }
+
+        Ok(())
+    }
+
+    /// Moves `target/relative` to the same relative path under `displaced`.
+    fn move_aside(target: &Path, displaced: &Path, relative: &Path) -> Result<()> {
+        let backup = displaced.join(relative);
+        if let Some(dir) = backup.parent() {
+            fs::create_dir_all(dir).map_err(|e| FilesError::IoError {
+                path: dir.display().to_string(),
+                source: e,
+            })?;
+        }
+        rename_with_context(&target.join(relative), &backup)
+    }
+
+    /// Creates `dir` and any missing ancestors below `target`, recording each
+    /// created directory in `applied`.
+    fn create_dir_recorded(
+        target: &Path,
+        dir: &Path,
+        applied: &mut Vec<IncrementalStep>,
+    ) -> Result<()> {
+        let missing: Vec<&Path> = dir
+            .ancestors()
+            .take_while(|ancestor| *ancestor != target && !ancestor.exists())
+            .collect();
+        for ancestor in missing.into_iter().rev() {
+            fs::create_dir(ancestor).map_err(|e| FilesError::IoError {
+                path: ancestor.display().to_string(),
+                source: e,
+            })?;
+            applied.push(IncrementalStep::CreatedDir(ancestor.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    /// Undoes `applied` in reverse order.
+    ///
+    /// Best-effort: a step that cannot be undone is skipped so the remaining
+    /// ones still get their chance, and the originals of any that could not
+    /// be moved back stay in `displaced`.
+    fn roll_back_incremental_steps(target: &Path, displaced: &Path, applied: Vec<IncrementalStep>) {
+        for step in applied.into_iter().rev() {
+            let _ = match step {
+                IncrementalStep::Placed(relative) => fs::remove_file(target.join(relative)),
+                IncrementalStep::MovedAside(relative) => {
+                    fs::rename(displaced.join(&relative), target.join(&relative))
+                }
+                IncrementalStep::CreatedDir(dir) => fs::remove_dir(dir),
+            };
+        }
+    }
+
     /// Publishes a fully staged export by atomically swapping it into `target`.
     ///
     /// If the swap itself fails, the staging directory is removed so no
@@ -1050,6 +1300,37 @@ impl Default for FileSystem {
     }
 }
 
+/// Changes an incremental export makes to an existing target directory.
+///
+/// Paths are relative to the target.
+#[derive(Debug, Default)]
+struct IncrementalPlan {
+    /// Files that are new or whose content differs from what is on disk.
+    changed: Vec<PathBuf>,
+    /// Entries on disk that the `FileSystem` no longer contains, other than
+    /// [`NPM_INSTALL_ARTIFACTS`].
+    stale: Vec<PathBuf>,
+}
+
+impl IncrementalPlan {
+    /// Whether the target already matches the `FileSystem`.
+    const fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.stale.is_empty()
+    }
+}
+
+/// A completed step of an incremental export, kept so it can be rolled back.
+#[derive(Debug)]
+enum IncrementalStep {
+    /// The entry at this target-relative path was moved into the `.stale-*`
+    /// sibling.
+    MovedAside(PathBuf),
+    /// A staged file was moved to this target-relative path.
+    Placed(PathBuf),
+    /// This directory was created inside the target.
+    CreatedDir(PathBuf),
+}
+
 /// Options for filesystem export operations.
 ///
 /// # Examples
@@ -1065,6 +1346,8 @@ pub struct ExportOptions {
     pub atomic: bool,
     /// Optional confinement base directory; see [`Self::with_confine_to`].
     confine_to: Option<PathBuf>,
+    /// Whether to update an existing target in place; see [`Self::with_incremental`].
+    incremental: bool,
 }
 
 impl ExportOptions {
@@ -1073,11 +1356,13 @@ impl ExportOptions {
     /// Defaults:
     /// - atomic: true (safer)
     /// - `confine_to`: unset (no confinement check)
+    /// - incremental: false (the target is replaced wholesale)
     #[must_use]
     pub const fn new() -> Self {
         Self {
             atomic: true,
             confine_to: None,
+            incremental: false,
         }
     }
 
@@ -1116,6 +1401,41 @@ impl ExportOptions {
         self.confine_to = Some(base_dir.into());
         self
     }
+
+    /// Sets whether to update an existing target directory in place.
+    ///
+    /// When enabled, a file whose SHA-256 content hash matches the file
+    /// already on disk is left untouched, so its modification time and inode
+    /// stay the same and file watchers see only the files that really
+    /// changed. Changed files are still replaced atomically, one rename each,
+    /// and entries no longer present are removed, apart from
+    /// [`NPM_INSTALL_ARTIFACTS`]. See the
+    /// [module-level docs](crate::filesystem#incremental-export).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_files::{ExportOptions, FilesBuilder};
+    /// # use tempfile::TempDir;
+    ///
+    /// # let temp = TempDir::new().unwrap();
+    /// # let target = temp.path().join("github");
+    /// let vfs = FilesBuilder::new()
+    ///     .add_file("/index.ts", "export {}")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let options = ExportOptions::new().with_incremental(true);
+    /// vfs.export_to_filesystem_with_options(&target, &options).unwrap();
+    /// // Nothing changed, so nothing is rewritten.
+    /// vfs.export_to_filesystem_with_options(&target, &options).unwrap();
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub const fn with_incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
 }
 
 impl Default for ExportOptions {
@@ -1124,6 +1444,31 @@ impl Default for ExportOptions {
     }
 }
 
+/// Whether the regular file at `path` already holds exactly `content`.
+///
+/// Compares SHA-256 content hashes, after a cheap length check that settles
+/// most changed files without reading them.
+fn file_matches(path: &Path, content: &str) -> Result<bool> {
+    let io_error = |e: std::io::Error| FilesError::IoError {
+        path: path.display().to_string(),
+        source: e,
+    };
+    let metadata = fs::metadata(path).map_err(io_error)?;
+    if metadata.len() != content.len() as u64 {
+        return Ok(false);
+    }
+    let existing = fs::read(path).map_err(io_error)?;
+    Ok(Sha256::digest(&existing) == Sha256::digest(content.as_bytes()))
+}
+
+/// Renames `from` to `to`, reporting `to` on failure.
+fn rename_with_context(from: &Path, to: &Path) -> Result<()> {
+    fs::rename(from, to).map_err(|e| FilesError::IoError {
+        path: to.display().to_string(),
+        source: e,
+    })
+}
+
 /// Writes file content to disk.
 ///
 /// If `atomic` is `true`, writes to a temp file, `fsync`s it for durability,
@@ -1843,6 +2188,197 @@ mod tests {
         assert!(!staging_path.exists());
     }
 
+    // ── Incremental export ───────────────────────────────────────────────────
+
+    /// Lists the entries next to `target` that an export left behind.
+    fn sibling_artifacts(target: &Path) -> Vec<String> {
+        fs::read_dir(target.parent().unwrap())
+            .unwrap()
+            .filter_map(|entry| entry.unwrap().file_name().into_string().ok())
+            .filter(|name| name.contains(".staging-") || name.contains(".stale-"))
+            .collect()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_incremental_export_rewrites_only_changed_files() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("server");
+        let options = ExportOptions::new().with_incremental(true);
+        FilesBuilder::new()
+            .add_file("/index.ts", "index v1")
+            .add_file("/tools/kept.ts", "kept")
+            .add_file("/tools/edited.ts", "edited v1")
+            .build()
+            .unwrap()
+            .export_to_filesystem_with_options(&target, &options)
+            .unwrap();
+        let inode = |relative: &str| fs::metadata(target.join(relative)).unwrap().ino();
+        let kept_inode = inode("tools/kept.ts");
+        let edited_inode = inode("tools/edited.ts");
+
+        FilesBuilder::new()
+            .add_file("/index.ts", "index v1")
+            .add_file("/tools/kept.ts", "kept")
+            .add_file("/tools/edited.ts", "edited v2")
+            .add_file("/tools/nested/added.ts", "added")
+            .build()
+            .unwrap()
+            .export_to_filesystem_with_options(&target, &options)
+            .unwrap();
+
+        assert_eq!(inode("tools/kept.ts"), kept_inode);
+        assert_ne!(inode("tools/edited.ts"), edited_inode);
+        assert_eq!(
+            fs::read_to_string(target.join("tools/edited.ts")).unwrap(),
+            "edited v2"
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("tools/nested/added.ts")).unwrap(),
+            "added"
+        );
+        assert!(sibling_artifacts(&target).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_export_removes_stale_entries() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("server");
+        fs::create_dir_all(target.join("tools/removed-group")).unwrap();
+        fs::write(target.join("index.ts"), "index").unwrap();
+        fs::write(target.join("tools/removed.ts"), "gone").unwrap();
+        fs::write(target.join("tools/removed-group/a.ts"), "gone").unwrap();
+        fs::write(target.join("tools/kept.ts"), "kept").unwrap();
+
+        FilesBuilder::new()
+            .add_file("/index.ts", "index")
+            .add_file("/tools/kept.ts", "kept")
+            .build()
+            .unwrap()
+            .export_to_filesystem_with_options(
+                &target,
+                &ExportOptions::new().with_incremental(true),
+            )
+            .unwrap();
+
+        assert!(target.join("tools/kept.ts").exists());
+        assert!(!target.join("tools/removed.ts").exists());
+        assert!(!target.join("tools/removed-group").exists());
+        assert!(sibling_artifacts(&target).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_export_keeps_npm_install_artifacts() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("server");
+        fs::create_dir_all(target.join("node_modules/x")).unwrap();
+        fs::write(target.join("node_modules/x/index.js"), "installed").unwrap();
+        fs::write(target.join("package-lock.json"), "{}").unwrap();
+        fs::write(target.join("index.ts"), "old").unwrap();
+        fs::write(target.join("removed.ts"), "gone").unwrap();
+
+        FilesBuilder::new()
+            .add_file("/index.ts", "new")
+            .build()
+            .unwrap()
+            .export_to_filesystem_with_options(
+                &target,
+                &ExportOptions::new().with_incremental(true),
+            )
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(target.join("node_modules/x/index.js")).unwrap(),
+            "installed"
+        );
+        assert!(target.join("package-lock.json").exists());
+        assert!(!target.join("removed.ts").exists());
+        assert!(sibling_artifacts(&target).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_export_into_missing_target_publishes_whole_tree() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("server");
+
+        FilesBuilder::new()
+            .add_file("/tools/a.ts", "a")
+            .build()
+            .unwrap()
+            .export_to_filesystem_with_options(
+                &target,
+                &ExportOptions::new().with_incremental(true),
+            )
+            .unwrap();
+
+        assert_eq!(fs::read_to_string(target.join("tools/a.ts")).unwrap(), "a");
+        assert!(sibling_artifacts(&target).is_empty());
+    }
+
+    #[test]
+    fn test_incremental_export_swaps_whole_tree_on_shape_conflict() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("server");
+        // A directory sits where the new export has a file.
+        fs::create_dir_all(target.join("tools.ts")).unwrap();
+        fs::write(target.join("tools.ts/inner.ts"), "inner").unwrap();
+
+        let vfs = FilesBuilder::new()
+            .add_file("/tools.ts", "file")
+            .build()
+            .unwrap();
+        assert!(vfs.plan_incremental_export(&target).unwrap().is_none());
+
+        vfs.export_to_filesystem_with_options(
+            &target,
+            &ExportOptions::new().with_incremental(true),
+        )
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(target.join("tools.ts")).unwrap(), "file");
+    }
+
+    #[test]
+    fn test_apply_incremental_export_rolls_back_on_failure() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("server");
+        let staging = temp.path().join("staging");
+        fs::create_dir_all(target.join("tools")).unwrap();
+        fs::write(target.join("tools/edited.ts"), "original").unwrap();
+        fs::write(target.join("tools/stale.ts"), "stale").unwrap();
+        fs::create_dir_all(staging.join("tools")).unwrap();
+        fs::create_dir_all(staging.join("new")).unwrap();
+        fs::write(staging.join("tools/edited.ts"), "updated").unwrap();
+        fs::write(staging.join("new/added.ts"), "added").unwrap();
+
+        // The last changed file was never staged, so moving it into place fails
+        // after the earlier steps have already been applied.
+        let plan = IncrementalPlan {
+            changed: vec![
+                PathBuf::from("tools/edited.ts"),
+                PathBuf::from("new/added.ts"),
+                PathBuf::from("tools/missing.ts"),
+            ],
+            stale: vec![PathBuf::from("tools/stale.ts")],
+        };
+
+        let result = FileSystem::apply_incremental_export(&target, &staging, &plan);
+        assert!(result.is_err());
+
+        assert_eq!(
+            fs::read_to_string(target.join("tools/edited.ts")).unwrap(),
+            "original"
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("tools/stale.ts")).unwrap(),
+            "stale"
+        );
+        assert!(!target.join("new").exists());
+        assert!(sibling_artifacts(&target).is_empty());
+    }
+
     #[test]
     fn test_export_many_files() {
         let temp = TempDir::new().unwrap();
@@ -1937,13 +2473,17 @@ mod tests {
     fn test_export_options_default() {
         let options = ExportOptions::default();
         assert!(options.atomic);
+        assert!(!options.incremental);
     }
 
     #[test]
     fn test_export_options_builder() {
-        let options = ExportOptions::new().with_atomic_writes(false);
+        let options = ExportOptions::new()
+            .with_atomic_writes(false)
+            .with_incremental(true);
 
         assert!(!options.atomic);
+        assert!(options.incremental);
     }
 
     // ── Resource-exhaustion bounds (issue #198) ──────────────────────────────
@@ -12,6 +12,10 @@
 //! capabilities. Discovered information is stored locally for subsequent
 //! code generation phases.
 //!
+//! Discovery disconnects once the tool list is read. To follow a server's
+//! tool list over time, [`ToolListSubscription`] keeps a session open and
+//! reports each `notifications/tools/list_changed` the server sends.
+//!
 //! # Examples
 //!
 //! ```no_run
@@ -47,23 +51,27 @@
 use futures_util::StreamExt;
 use futures_util::stream::{self, Stream};
 use http::{HeaderName, HeaderValue};
+use mcp_execution_core::untrusted::sanitize_untrusted_text;
 use mcp_execution_core::{
     Error, ResourceKind, Result, ServerConfig, ServerId, ToolName, Transport,
-    validate_server_config,
+    redact_secrets_in_text, validate_server_config,
 };
 use rmcp::RoleClient;
 use rmcp::ServiceExt;
-use rmcp::service::{RxJsonRpcMessage, TxJsonRpcMessage};
+use rmcp::service::{NotificationContext, RunningService, RxJsonRpcMessage, TxJsonRpcMessage};
 use rmcp::transport::StreamableHttpClientTransport;
 use rmcp::transport::async_rw::{JsonRpcMessageCodec, JsonRpcMessageCodecError};
 use rmcp::transport::streamable_http_client::StreamableHttpClientTransportConfig;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::future::Future;
 use std::process::Stdio;
+use std::sync::{Arc, Mutex};
 use std::task::Poll;
-use tokio::io::AsyncRead;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Child;
+use tokio::sync::mpsc;
 use tokio_util::bytes::BytesMut;
 use tokio_util::codec::{Decoder, FramedRead, FramedWrite};
 
@@ -167,6 +175,23 @@ const MAX_RESPONSE_LINE_SIZE: usize = 4 * 1024 * 1024;
 /// potentially-breaking decision out of scope here.
 const HTTP_MAX_SSE_EVENT_SIZE: usize = 16 * 1024 * 1024;
 
+/// Maximum number of bytes of a stdio server's stderr kept for error reports: only the last
+/// this many bytes survive, however much the server writes.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_introspector::MAX_STDERR_TAIL_BYTES;
+///
+/// assert!(MAX_STDERR_TAIL_BYTES > 0);
+/// ```
+pub const MAX_STDERR_TAIL_BYTES: usize = 8 * 1024;
+
+/// How long a failed stdio discovery waits, after killing the server, for the rest of its
+/// stderr to arrive. Bounded because a grandchild (e.g. the `node` process `npx` starts) can
+/// keep the pipe open after the server itself is gone.
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_millis(200);
+
 /// Information about an MCP server.
 ///
 /// Contains metadata about the server including its name, version,
@@ -583,6 +608,151 @@ impl Default for Introspector {
     }
 }
 
+/// How often [`ToolListSubscription::changed`] checks whether its session has closed while no
+/// notification is pending.
+const SUBSCRIPTION_CLOSE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A live session with an MCP server that reports when the server's tool list changes.
+///
+/// [`Introspector::discover_server`] disconnects as soon as it has read the tool list; this
+/// instead keeps a connection open (for a stdio server, a dedicated server process) and listens
+/// for `notifications/tools/list_changed`. It only signals *that* the list changed — call
+/// [`Introspector::discover_server`] again to read the new one. Dropping the subscription closes
+/// the session and, for stdio, kills its process.
+///
+/// # Examples
+///
+/// ```no_run
+/// use mcp_execution_introspector::ToolListSubscription;
+/// use mcp_execution_core::{ServerId, ServerConfig};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let server_id = ServerId::new("github").unwrap();
+/// let config = ServerConfig::builder()
+///     .command("github-server".to_string())
+///     .build()?;
+///
+/// let mut subscription = ToolListSubscription::connect(&server_id, &config).await?;
+/// while subscription.changed().await {
+///     println!("tool list changed");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ToolListSubscription {
+    /// Receives one `()` per coalesced burst of notifications.
+    notifications: mpsc::Receiver<()>,
+    /// The running rmcp client; dropping it cancels the session.
+    client: RunningService<RoleClient, ToolListChangedForwarder>,
+    /// The stdio server process backing the session, `None` for HTTP/SSE. Spawned with
+    /// `kill_on_drop`, so it is terminated when the subscription is dropped.
+    _child: Option<Child>,
+}
+
+impl std::fmt::Debug for ToolListSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolListSubscription")
+            .field("closed", &self.client.is_closed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl ToolListSubscription {
+    /// Connects to the server described by `config` and starts listening for tool list
+    /// changes.
+    ///
+    /// Re-validates `config` the same way [`Introspector::discover_server`] does, and bounds
+    /// the handshake by [`ServerConfig::connect_timeout`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `config` fails [`validate_server_config`], the server process cannot
+    /// be spawned, or the handshake fails or times out.
+    #[tracing::instrument(skip_all, fields(server_id = %server_id))]
+    pub async fn connect(server_id: &ServerId, config: &ServerConfig) -> Result<Self> {
+        validate_server_config(config)?;
+
+        let (sender, notifications) = mpsc::channel(1);
+        let handler = ToolListChangedForwarder { sender };
+
+        let (client, child) = match config.transport() {
+            Transport::Stdio { command, .. } => {
+                let mut child = spawn_introspection_child(server_id, command, config)?;
+                let stderr = StderrTail::capture(server_id, &mut child, config);
+                let (stdout, stdin) = take_child_stdio(server_id, &mut child)?;
+                let transport = bounded_stdio_transport(stdout, stdin);
+                match connect_bounded(server_id, config, handler.serve(transport)).await {
+                    Ok(client) => (client, Some(child)),
+                    Err(error) => {
+                        drop(child.kill().await);
+                        return Err(stderr.attach(error).await);
+                    }
+                }
+            }
+            Transport::Http { .. } | Transport::Sse { .. } => {
+                let transport = http_transport(server_id, config)?;
+                let client = connect_bounded(server_id, config, handler.serve(transport)).await?;
+                (client, None)
+            }
+        };
+
+        Ok(Self {
+            notifications,
+            client,
+            _child: child,
+        })
+    }
+
+    /// Returns whether the server advertised `tools.listChanged` in its handshake.
+    ///
+    /// A server that does not is unlikely to ever send the notification, though
+    /// [`changed`](Self::changed) still reports one if it does.
+    #[must_use]
+    pub fn server_advertises_list_changed(&self) -> bool {
+        self.client
+            .peer_info()
+            .and_then(|info| info.capabilities.tools.as_ref()?.list_changed)
+            .unwrap_or(false)
+    }
+
+    /// Waits for the next `notifications/tools/list_changed` from the server.
+    ///
+    /// Notifications that arrive while no one is waiting are coalesced into one. Returns
+    /// `false` once the session has closed (the server exited or dropped the connection);
+    /// no further notifications arrive after that.
+    ///
+    /// Cancel-safe: dropping the returned future loses no pending notification.
+    pub async fn changed(&mut self) -> bool {
+        loop {
+            tokio::select! {
+                received = self.notifications.recv() => return received.is_some(),
+                () = tokio::time::sleep(SUBSCRIPTION_CLOSE_POLL_INTERVAL) => {
+                    if self.client.is_closed() {
+                        return false;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// rmcp client handler backing a [`ToolListSubscription`].
+///
+/// Feeds a capacity-1 channel with `try_send`, so a burst of notifications arriving before the
+/// subscriber wakes collapses into the single one already pending.
+#[derive(Debug)]
+struct ToolListChangedForwarder {
+    sender: mpsc::Sender<()>,
+}
+
+impl rmcp::ClientHandler for ToolListChangedForwarder {
+    async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        // `Full` means a notification is already pending, which covers this one; `Closed`
+        // means the subscription was dropped.
+        let _ = self.sender.try_send(());
+    }
+}
+
 /// Server name, version, and feature-support flags extracted from an MCP
 /// handshake result.
 ///
@@ -624,6 +794,10 @@ struct DiscoveryResult {
 /// introspection completes; this crate does not keep the process alive for
 /// later tool invocation.
 ///
+/// The child inherits the parent environment filtered by the config's
+/// [`EnvPolicy`](mcp_execution_core::EnvPolicy), with its own `env` applied on
+/// top - the same rule the generated bridge's `createConnection` follows.
+///
 /// `kill_on_drop(true)` is set as a backstop for the case where the
 /// `discover_via_stdio_process` future itself is dropped before it reaches
 /// its own explicit [`Child::kill`] call - e.g. a caller racing discovery
@@ -648,12 +822,13 @@ fn spawn_introspection_child(
     // fallback (and its empty-string sentinel) needed.
     let mut command = tokio::process::Command::new(command);
     command.args(config.args());
-    command.envs(config.env());
+    apply_child_env(&mut command, config, std::env::vars_os());
     if let Some(cwd) = config.cwd() {
         command.current_dir(cwd);
     }
     command.stdin(Stdio::piped());
     command.stdout(Stdio::piped());
+    command.stderr(Stdio::piped());
     command.kill_on_drop(true);
 
     command.spawn().map_err(|e| Error::ConnectionFailed {
@@ -662,6 +837,177 @@ fn spawn_introspection_child(
     })
 }
 
+/// Sets `command`'s environment: `parent` filtered by the config's
+/// [`EnvPolicy`](mcp_execution_core::EnvPolicy), then the config's own `env`.
+fn apply_child_env(
+    command: &mut tokio::process::Command,
+    config: &ServerConfig,
+    parent: impl IntoIterator<Item = (std::ffi::OsString, std::ffi::OsString)>,
+) {
+    let env_policy = config.env_policy();
+    if !env_policy.is_inherit() {
+        command.env_clear();
+        command.envs(env_policy.filter(parent));
+    }
+    command.envs(config.env());
+}
+
+/// The last [`MAX_STDERR_TAIL_BYTES`] of a stdio server's stderr, collected by a background
+/// task that also logs each line at debug level. Both the tail and the log have the config's
+/// [`stderr_secrets`] redacted.
+///
+/// Piping stderr (rather than inheriting it) keeps a server's diagnostics out of the terminal
+/// and out of an `mcp-execution-server` client's stdio stream, while still letting a failure
+/// report them through [`Error::ServerStderr`].
+struct StderrTail {
+    /// Raw bytes, trimmed from the front as new ones arrive.
+    buffer: Arc<Mutex<VecDeque<u8>>>,
+    /// The reader task; finishes once the pipe closes. Dropping the handle detaches rather
+    /// than cancels it, so a live subscription's server keeps being logged.
+    reader: Option<tokio::task::JoinHandle<()>>,
+    /// The config values redacted from the tail and the log.
+    secrets: Arc<[String]>,
+}
+
+impl StderrTail {
+    /// Starts collecting `child`'s piped stderr, redacting `config`'s [`stderr_secrets`]. A
+    /// child without one yields an empty tail.
+    fn capture(server_id: &ServerId, child: &mut Child, config: &ServerConfig) -> Self {
+        let buffer = Arc::new(Mutex::new(VecDeque::new()));
+        let secrets: Arc<[String]> = stderr_secrets(config).into();
+        let reader = child.stderr.take().map(|stderr| {
+            tokio::spawn(pump_stderr(
+                server_id.to_string(),
+                stderr,
+                Arc::clone(&buffer),
+                Arc::clone(&secrets),
+            ))
+        });
+        Self {
+            buffer,
+            reader,
+            secrets,
+        }
+    }
+
+    /// Wraps `error` in [`Error::ServerStderr`] when the server wrote anything to stderr.
+    ///
+    /// Waits up to [`STDERR_DRAIN_TIMEOUT`] for the pipe to close first, so output the server
+    /// wrote just before exiting is included.
+    async fn attach(mut self, error: Error) -> Error {
+        if let Some(reader) = self.reader.as_mut()
+            && tokio::time::timeout(STDERR_DRAIN_TIMEOUT, reader)
+                .await
+                .is_err()
+        {
+            tracing::debug!("server stderr still open after {STDERR_DRAIN_TIMEOUT:?}");
+        }
+        match self.snapshot() {
+            Some(stderr) => Error::ServerStderr {
+                stderr,
+                source: Box::new(error),
+            },
+            None => error,
+        }
+    }
+
+    /// Returns the collected tail with the config's secrets and URL credentials redacted and
+    /// each line sanitized, or `None` if nothing but whitespace was written.
+    fn snapshot(&self) -> Option<String> {
+        let bytes: Vec<u8> = self
+            .buffer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .copied()
+            .collect();
+        sanitize_stderr(&bytes, &self.secrets)
+    }
+}
+
+impl std::fmt::Debug for StderrTail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StderrTail").finish_non_exhaustive()
+    }
+}
+
+/// Reads `stderr` to the end, keeping the last [`MAX_STDERR_TAIL_BYTES`] in `buffer` and
+/// logging each line at debug level with `secrets` redacted.
+async fn pump_stderr(
+    server_id: String,
+    mut stderr: tokio::process::ChildStderr,
+    buffer: Arc<Mutex<VecDeque<u8>>>,
+    secrets: Arc<[String]>,
+) {
+    let mut chunk = [0u8; 1024];
+    let mut line = Vec::new();
+    loop {
+        let read = match stderr.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => read,
+        };
+        {
+            let mut buffer = buffer
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            buffer.extend(&chunk[..read]);
+            let excess = buffer.len().saturating_sub(MAX_STDERR_TAIL_BYTES);
+            buffer.drain(..excess);
+        }
+        for &byte in &chunk[..read] {
+            if byte == b'\n' {
+                log_stderr_line(&server_id, &line, &secrets);
+                line.clear();
+            } else if line.len() < MAX_STDERR_TAIL_BYTES {
+                line.push(byte);
+            }
+        }
+    }
+    log_stderr_line(&server_id, &line, &secrets);
+}
+
+/// Logs one stderr line at debug level, with `secrets` and URL credentials redacted and control
+/// characters stripped like every other server-supplied text this crate logs.
+fn log_stderr_line(server_id: &str, line: &[u8], secrets: &[String]) {
+    let text = String::from_utf8_lossy(line);
+    if text.trim().is_empty() {
+        return;
+    }
+    tracing::debug!(
+        server = server_id,
+        "stderr: {}",
+        sanitize_untrusted_text(
+            &redact_secrets_in_text(&text, secrets.iter().map(String::as_str)),
+            MAX_STDERR_TAIL_BYTES
+        )
+    );
+}
+
+/// The values of `config` a server might echo to stderr: every `args` entry and every `env`
+/// and `headers` value, the same ones [`ServerConfig::redacted`] replaces.
+fn stderr_secrets(config: &ServerConfig) -> Vec<String> {
+    config
+        .args()
+        .iter()
+        .chain(config.env().values())
+        .chain(config.headers().values())
+        .cloned()
+        .collect()
+}
+
+/// Turns raw stderr bytes into report text: `secrets` and URL credentials are redacted, each
+/// line is sanitized, and blank lines are dropped. Returns `None` if nothing is left.
+fn sanitize_stderr(bytes: &[u8], secrets: &[String]) -> Option<String> {
+    let text = String::from_utf8_lossy(bytes);
+    let redacted = redact_secrets_in_text(&text, secrets.iter().map(String::as_str));
+    let lines: Vec<String> = redacted
+        .lines()
+        .map(|line| sanitize_untrusted_text(line, MAX_STDERR_TAIL_BYTES))
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
 /// Spawns the stdio introspection child, drives discovery over it, and tears
 /// it down afterward.
 ///
@@ -675,16 +1021,10 @@ async fn discover_via_stdio_process(
     config: &ServerConfig,
 ) -> Result<DiscoveryResult> {
     let mut child = spawn_introspection_child(server_id, command, config)?;
-    let stdout = child.stdout.take().ok_or_else(|| Error::ConnectionFailed {
-        server: server_id.to_string(),
-        source: Box::new(std::io::Error::other("child stdout was not captured")),
-    })?;
-    let stdin = child.stdin.take().ok_or_else(|| Error::ConnectionFailed {
-        server: server_id.to_string(),
-        source: Box::new(std::io::Error::other("child stdin was not captured")),
-    })?;
+    let stderr = StderrTail::capture(server_id, &mut child, config);
+    let stdio = take_child_stdio(server_id, &mut child)?;
 
-    let discovery = discover_via_stdio(server_id, config, (stdout, stdin)).await;
+    let discovery = discover_via_stdio(server_id, config, stdio).await;
 
     // The child process is spawned solely for this discovery round-trip, so it
     // must be reaped here regardless of outcome. We deliberately kill it
@@ -698,7 +1038,30 @@ async fn discover_via_stdio_process(
         );
     }
 
-    discovery
+    match discovery {
+        Ok(discovery) => Ok(discovery),
+        Err(error) => Err(stderr.attach(error).await),
+    }
+}
+
+/// Takes the piped stdout/stdin handles off a child spawned by [`spawn_introspection_child`].
+///
+/// # Errors
+///
+/// Returns [`Error::ConnectionFailed`] if either pipe was not captured.
+fn take_child_stdio(
+    server_id: &ServerId,
+    child: &mut Child,
+) -> Result<(tokio::process::ChildStdout, tokio::process::ChildStdin)> {
+    let stdout = child.stdout.take().ok_or_else(|| Error::ConnectionFailed {
+        server: server_id.to_string(),
+        source: Box::new(std::io::Error::other("child stdout was not captured")),
+    })?;
+    let stdin = child.stdin.take().ok_or_else(|| Error::ConnectionFailed {
+        server: server_id.to_string(),
+        source: Box::new(std::io::Error::other("child stdin was not captured")),
+    })?;
+    Ok((stdout, stdin))
 }
 
 /// Outcome of a single [`list_tools_bounded`] page fetch that isn't a plain success.
@@ -1012,20 +1375,10 @@ async fn connect_and_list_tools<F, T>(
     connect: F,
 ) -> Result<DiscoveryResult>
 where
-    F: Future<Output = std::result::Result<rmcp::service::RunningService<RoleClient, ()>, T>>,
+    F: Future<Output = std::result::Result<RunningService<RoleClient, ()>, T>>,
     T: std::error::Error + Send + Sync + 'static,
 {
-    // Bounded by the connect timeout.
-    let client = tokio::time::timeout(config.connect_timeout(), connect)
-        .await
-        .map_err(|_elapsed| Error::Timeout {
-            operation: format!("connect to {server_id}"),
-            duration_secs: config.connect_timeout().as_secs(),
-        })?
-        .map_err(|e| Error::ConnectionFailed {
-            server: server_id.to_string(),
-            source: Box::new(e),
-        })?;
+    let client = Box::pin(connect_bounded(server_id, config, connect)).await?;
 
     // List tools page by page, bounded by the discover timeout overall and bailing out early
     // if the accumulated count exceeds MAX_TOOL_COUNT (see `list_tools_bounded`'s docs).
@@ -1046,6 +1399,59 @@ where
     })
 }
 
+/// Awaits an rmcp client handshake (`connect`), bounded by `config.connect_timeout()`.
+///
+/// Generic over the client handler `S` so [`connect_and_list_tools`] (which uses `()`) and
+/// [`ToolListSubscription::connect`] (which forwards notifications) share one timeout and
+/// error mapping.
+///
+/// # Errors
+///
+/// Returns [`Error::Timeout`] if the handshake exceeds the connect timeout, or
+/// [`Error::ConnectionFailed`] if it fails.
+async fn connect_bounded<F, S, T>(
+    server_id: &ServerId,
+    config: &ServerConfig,
+    connect: F,
+) -> Result<RunningService<RoleClient, S>>
+where
+    F: Future<Output = std::result::Result<RunningService<RoleClient, S>, T>>,
+    S: rmcp::Service<RoleClient>,
+    T: std::error::Error + Send + Sync + 'static,
+{
+    tokio::time::timeout(config.connect_timeout(), connect)
+        .await
+        .map_err(|_elapsed| Error::Timeout {
+            operation: format!("connect to {server_id}"),
+            duration_secs: config.connect_timeout().as_secs(),
+        })?
+        .map_err(|e| Error::ConnectionFailed {
+            server: server_id.to_string(),
+            source: Box::new(e),
+        })
+}
+
+/// Builds the client transport over a spawned server's stdio pipes.
+///
+/// The default `(ChildStdout, ChildStdin)` transport (`AsyncRwTransport`) reads
+/// lines via an unbounded `read_until`, bypassing `JsonRpcMessageCodec`'s
+/// `max_length` entirely (issue #225). Building the sink/stream pair explicitly
+/// routes stdout through `bounded_response_stream` instead.
+fn bounded_stdio_transport(
+    stdout: tokio::process::ChildStdout,
+    stdin: tokio::process::ChildStdin,
+) -> (
+    FramedWrite<tokio::process::ChildStdin, JsonRpcMessageCodec<TxJsonRpcMessage<RoleClient>>>,
+    impl Stream<Item = RxJsonRpcMessage<RoleClient>> + Send + Unpin + 'static,
+) {
+    let sink = FramedWrite::new(
+        stdin,
+        JsonRpcMessageCodec::<TxJsonRpcMessage<RoleClient>>::new(),
+    );
+    let stream = bounded_response_stream(stdout, MAX_RESPONSE_LINE_SIZE);
+    (sink, stream)
+}
+
 /// Connects to an already-spawned MCP server over `transport` and lists its
 /// tools, with each step bounded by `config`'s configured timeouts.
 ///
@@ -1063,18 +1469,10 @@ async fn discover_via_stdio(
     config: &ServerConfig,
     transport: (tokio::process::ChildStdout, tokio::process::ChildStdin),
 ) -> Result<DiscoveryResult> {
-    // The default `(ChildStdout, ChildStdin)` transport (`AsyncRwTransport`) reads
-    // lines via an unbounded `read_until`, bypassing `JsonRpcMessageCodec`'s
-    // `max_length` entirely (issue #225). Building the sink/stream pair explicitly
-    // routes stdout through `bounded_response_stream` instead.
     let (stdout, stdin) = transport;
-    let sink = FramedWrite::new(
-        stdin,
-        JsonRpcMessageCodec::<TxJsonRpcMessage<RoleClient>>::new(),
-    );
-    let stream = bounded_response_stream(stdout, MAX_RESPONSE_LINE_SIZE);
+    let transport = bounded_stdio_transport(stdout, stdin);
 
-    connect_and_list_tools(server_id, config, ().serve((sink, stream))).await
+    connect_and_list_tools(server_id, config, ().serve(transport)).await
 }
 
 /// Connects to an MCP server over Streamable HTTP and lists its tools, with
@@ -1103,6 +1501,27 @@ async fn discover_via_stdio(
 /// or [`Error::ResourceLimitExceeded`] if the accumulated tool count exceeds
 /// [`MAX_TOOL_COUNT`].
 async fn discover_via_http(server_id: &ServerId, config: &ServerConfig) -> Result<DiscoveryResult> {
+    let transport = http_transport(server_id, config)?;
+
+    // The client `connect_and_list_tools` builds internally is dropped when it returns, which
+    // triggers `WorkerTransport`'s drop guard, cancelling the worker task; a cancelled worker
+    // cannot itself issue a session-DELETE request, so no explicit disconnect happens on this path —
+    // the server-side session simply expires on its own timeout instead.
+    connect_and_list_tools(server_id, config, ().serve(transport)).await
+}
+
+/// Builds the Streamable HTTP client transport for an [`Transport::Http`] or
+/// [`Transport::Sse`] config, with its custom headers and the
+/// [`HTTP_MAX_SSE_EVENT_SIZE`] cap applied.
+///
+/// # Errors
+///
+/// Returns [`Error::ConnectionFailed`] if a configured header name or value is not a valid
+/// HTTP header.
+fn http_transport(
+    server_id: &ServerId,
+    config: &ServerConfig,
+) -> Result<impl rmcp::transport::Transport<RoleClient> + 'static> {
     // `ServerConfigBuilder::build` already guarantees `url` is `Some` for
     // Http/Sse transports — no `ServerConfig` can exist otherwise.
     let url = config
@@ -1128,17 +1547,11 @@ async fn discover_via_http(server_id: &ServerId, config: &ServerConfig) -> Resul
     // `from_config` is an inherent method defined only on that one specialization of
     // `StreamableHttpClientTransport<C>`, so this crate does not need to depend on `reqwest`
     // directly or name `reqwest::Client` to select it.
-    let transport = StreamableHttpClientTransport::from_config(
+    Ok(StreamableHttpClientTransport::from_config(
         StreamableHttpClientTransportConfig::with_uri(url)
             .custom_headers(custom_headers)
             .max_sse_event_size(HTTP_MAX_SSE_EVENT_SIZE),
-    );
-
-    // The client `connect_and_list_tools` builds internally is dropped when it returns, which
-    // triggers `WorkerTransport`'s drop guard, cancelling the worker task; a cancelled worker
-    // cannot itself issue a session-DELETE request, so no explicit disconnect happens on this path —
-    // the server-side session simply expires on its own timeout instead.
-    connect_and_list_tools(server_id, config, ().serve(transport)).await
+    ))
 }
 
 /// Assembles a [`ServerInfo`] from the raw tool list and handshake metadata
@@ -1350,6 +1763,93 @@ fn fallback_server_name(config: &ServerConfig) -> String {
 mod tests {
     use super::*;
 
+    // ── apply_child_env ──────────────────────────────────────────────────────
+
+    fn child_env(config: &ServerConfig) -> Vec<(String, Option<String>)> {
+        let parent = [
+            ("PATH", "/bin"),
+            ("GITHUB_TOKEN", "ghp_x"),
+            ("AWS_PROFILE", "dev"),
+        ]
+        .map(|(name, value)| (name.into(), value.into()));
+        let mut command = tokio::process::Command::new("server");
+        apply_child_env(&mut command, config, parent);
+        let mut env: Vec<_> = command
+            .as_std()
+            .get_envs()
+            .map(|(name, value)| {
+                (
+                    name.to_string_lossy().into_owned(),
+                    value.map(|value| value.to_string_lossy().into_owned()),
+                )
+            })
+            .collect();
+        env.sort();
+        env
+    }
+
+    #[test]
+    fn test_apply_child_env_inherit_only_adds_configured_env() {
+        let config = ServerConfig::builder()
+            .command("server".to_string())
+            .env("DEBUG".to_string(), "1".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            child_env(&config),
+            [("DEBUG".to_string(), Some("1".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_apply_child_env_allowlist_drops_other_parent_vars() {
+        let config = ServerConfig::builder()
+            .command("server".to_string())
+            .env("DEBUG".to_string(), "1".to_string())
+            .env_policy(mcp_execution_core::EnvPolicy::Allowlist(vec![
+                "AWS_PROFILE".to_string(),
+            ]))
+            .build()
+            .unwrap();
+        let names: Vec<_> = child_env(&config)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["AWS_PROFILE", "DEBUG", "PATH"]);
+    }
+
+    #[test]
+    fn test_sanitize_stderr_redacts_config_values_and_drops_blank_lines() {
+        let config = ServerConfig::builder()
+            .command("server".to_string())
+            .arg("--token=ghp-arg-456".to_string())
+            .env("API_KEY".to_string(), "sk-live-123".to_string())
+            .build()
+            .unwrap();
+        let secrets = stderr_secrets(&config);
+        let raw =
+            b"\n  \nauth failed: sk-live-123\nbad flag --token=ghp-arg-456\n\x1b[31mfatal\x1b[0m\n";
+
+        assert_eq!(
+            sanitize_stderr(raw, &secrets).as_deref(),
+            Some("auth failed: <redacted>\nbad flag <redacted>\n [31mfatal [0m")
+        );
+        assert_eq!(sanitize_stderr(b" \n\t\n", &secrets), None);
+
+        let http = ServerConfig::builder()
+            .http_transport("https://api.example.com/mcp".to_string())
+            .header(
+                "Authorization".to_string(),
+                "Bearer sk-header-789".to_string(),
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            sanitize_stderr(b"rejected Bearer sk-header-789", &stderr_secrets(&http)).as_deref(),
+            Some("rejected <redacted>")
+        );
+    }
+
     // ── bounded_response_stream (issue #225) ─────────────────────────────────
 
     mod bounded_response_stream_tests {
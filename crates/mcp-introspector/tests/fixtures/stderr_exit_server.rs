@@ -0,0 +1,13 @@
+//! Test fixture: an executable that explains on stderr why it cannot start, then exits
+//! without answering the `initialize` handshake — the way a real server reports a missing
+//! token.
+//!
+//! Used by `tests/stderr_capture_test.rs`. Echoes `FIXTURE_TOKEN` back so the test can check
+//! that configured `env` values are redacted from the captured tail.
+
+fn main() {
+    let token = std::env::var("FIXTURE_TOKEN").unwrap_or_default();
+    eprintln!("starting fixture server");
+    eprintln!("error: missing GITHUB_TOKEN (got FIXTURE_TOKEN={token})");
+    std::process::exit(1);
+}
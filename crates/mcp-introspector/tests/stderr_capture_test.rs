@@ -0,0 +1,48 @@
+//! Integration test proving a stdio server's stderr is captured and attached to the
+//! introspection error when the server fails before completing the handshake.
+//!
+//! Requires the `test-fixtures` feature (implied by `--all-features`) so that
+//! `fixture-stderr-exit-server` is built.
+
+#![cfg(feature = "test-fixtures")]
+
+use mcp_execution_core::{Error, ServerConfig, ServerId};
+use mcp_execution_introspector::Introspector;
+use std::time::Duration;
+
+/// Absolute path to the `fixture-stderr-exit-server` binary built alongside this test target.
+const FIXTURE_BIN: &str = env!("CARGO_BIN_EXE_fixture-stderr-exit-server");
+
+#[tokio::test]
+async fn test_discover_server_failure_carries_redacted_stderr_tail() {
+    let mut introspector = Introspector::new();
+    let server_id = ServerId::new("stdio-stderr-exit").unwrap();
+
+    let config = ServerConfig::builder()
+        .command(FIXTURE_BIN.to_string())
+        .env("FIXTURE_TOKEN".to_string(), "sk-fixture-secret".to_string())
+        .connect_timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+
+    let result = introspector.discover_server(server_id, &config).await;
+
+    match result {
+        Err(Error::ServerStderr { stderr, source }) => {
+            assert!(
+                stderr.contains("error: missing GITHUB_TOKEN"),
+                "stderr tail should explain the failure, got: {stderr}"
+            );
+            assert!(stderr.contains("starting fixture server\n"));
+            assert!(
+                !stderr.contains("sk-fixture-secret"),
+                "configured env values must be redacted, got: {stderr}"
+            );
+            assert!(
+                matches!(*source, Error::ConnectionFailed { .. }),
+                "expected the handshake failure underneath, got {source:?}"
+            );
+        }
+        other => panic!("expected Error::ServerStderr, got {other:?}"),
+    }
+}
@@ -0,0 +1,135 @@
+//! Integration tests for `ToolListSubscription`: connects to a real in-process rmcp Streamable
+//! HTTP server that sends `notifications/tools/list_changed`, and proves the notification
+//! reaches `ToolListSubscription::changed` and the advertised capability is reported.
+//!
+//! Reuses the in-process Streamable HTTP fixture pattern from `tests/http_transport_test.rs`.
+//!
+//! Requires the `test-fixtures` feature (implied by `--all-features`, which is how CI and the
+//! project's preferred `cargo nextest` invocation run tests) so that
+//! `rmcp/transport-streamable-http-server` is enabled.
+
+#![cfg(feature = "test-fixtures")]
+
+use axum::Router;
+use mcp_execution_core::{ServerConfig, ServerId};
+use mcp_execution_introspector::ToolListSubscription;
+use rmcp::model::{Implementation, InitializeResult, ServerCapabilities};
+use rmcp::service::NotificationContext;
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use rmcp::{RoleServer, ServerHandler};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Interval at which [`NotifyingFixtureHandler`] re-sends the notification once a client has
+/// initialized.
+///
+/// Sent repeatedly rather than once: the client opens its server-to-client SSE stream only
+/// after the handshake, so a single notification sent right away could race ahead of it.
+const NOTIFY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A fixture MCP server advertising `tools.listChanged` that, once a client has initialized,
+/// keeps announcing a tool list change until the session goes away.
+#[derive(Clone)]
+struct NotifyingFixtureHandler;
+
+impl ServerHandler for NotifyingFixtureHandler {
+    fn get_info(&self) -> InitializeResult {
+        InitializeResult::new(
+            ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .build(),
+        )
+        .with_server_info(Implementation::new("notifying-fixture-server", "1.0.0"))
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        let peer = context.peer;
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(NOTIFY_INTERVAL).await;
+                if peer.notify_tool_list_changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+/// Spawns the fixture server on a loopback TCP port, returning its URL and the token that
+/// shuts it down.
+async fn spawn_fixture_server() -> (String, CancellationToken) {
+    let ct = CancellationToken::new();
+    let server_config =
+        StreamableHttpServerConfig::default().with_cancellation_token(ct.child_token());
+
+    let service: StreamableHttpService<NotifyingFixtureHandler, LocalSessionManager> =
+        StreamableHttpService::new(
+            || Ok(NotifyingFixtureHandler),
+            Arc::new(LocalSessionManager::default()),
+            server_config,
+        );
+    let router: Router = Router::new().nest_service("/mcp", service);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind fixture listener");
+    let addr = listener
+        .local_addr()
+        .expect("fixture listener has a local addr");
+
+    let shutdown_ct = ct.clone();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, router)
+            .with_graceful_shutdown(async move { shutdown_ct.cancelled_owned().await })
+            .await;
+    });
+
+    (format!("http://{addr}/mcp"), ct)
+}
+
+#[tokio::test]
+async fn test_subscription_receives_tool_list_changed() {
+    let (url, ct) = spawn_fixture_server().await;
+    let config = ServerConfig::builder()
+        .http_transport(url)
+        .connect_timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+
+    let mut subscription =
+        ToolListSubscription::connect(&ServerId::new("notifying").unwrap(), &config)
+            .await
+            .expect("subscription should connect to the HTTP fixture");
+    assert!(subscription.server_advertises_list_changed());
+
+    let changed = tokio::time::timeout(Duration::from_secs(10), subscription.changed()).await;
+
+    ct.cancel();
+
+    assert_eq!(
+        changed.ok(),
+        Some(true),
+        "the fixture's tools/list_changed notification should reach the subscription"
+    );
+}
+
+#[tokio::test]
+async fn test_subscription_connect_fails_without_server() {
+    // Bind and immediately drop a listener so the port is known to be closed.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let config = ServerConfig::builder()
+        .http_transport(format!("http://{addr}/mcp"))
+        .connect_timeout(Duration::from_secs(5))
+        .build()
+        .unwrap();
+
+    let result = ToolListSubscription::connect(&ServerId::new("absent").unwrap(), &config).await;
+    assert!(result.is_err());
+}
@@ -12,6 +12,15 @@
 //! 2. **`save_categorized_tools`** - Generate TypeScript files with Claude's categorization
 //! 3. **`list_generated_servers`** - List all servers with generated files
 //!
+//! `get_session_tools` pages through a large server's tools, and `list_sessions`,
+//! `describe_session`, and `discard_session` inspect and clean up the pending sessions between
+//! the first two. `search_tools` finds tools across every generated server (see [`search`]).
+//!
+//! The generated files and saved skills are also served as MCP resources, with
+//! `resources/subscribe` notifications on regeneration (see [`resources`]).
+//! The `onboard_server`, `refresh_server`, and `write_skill` prompts expand to the whole
+//! workflow (see [`prompts`]).
+//!
 //! # Workflow
 //!
 //! 1. User asks Claude to generate progressive loading for an MCP server
@@ -52,18 +61,25 @@
 
 pub mod clock;
 mod output_dir;
+pub mod policy;
+pub mod prompts;
+pub mod resources;
+pub mod search;
 pub mod service;
+pub mod session_store;
 pub mod state;
 pub mod types;
 
 pub use clock::{Clock, SystemClock};
 pub use output_dir::{OutputDirError, relative_subpath, resolve_output_dir};
+pub use policy::{CommandPolicy, PolicyError};
 pub use service::GeneratorService;
 pub use state::StateManager;
 pub use types::{
     CategorizedTool, GeneratedServerInfo, IntrospectServerParams, IntrospectServerResult,
     IntrospectedToolSummary, ListGeneratedServersParams, ListGeneratedServersResult,
-    PendingGeneration, SaveCategorizedToolsParams, SaveCategorizedToolsResult, ToolGenerationError,
+    PendingGeneration, SaveCategorizedToolsParams, SaveCategorizedToolsResult, SearchToolsParams,
+    SearchToolsResult, ToolGenerationError, ToolSearchHit,
 };
 
 // Re-export skill types from mcp-skill crate
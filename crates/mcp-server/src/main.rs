@@ -24,14 +24,19 @@
 //! }
 //! ```
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use clap::builder::{PossibleValuesParser, TypedValueParser as _};
 use futures_util::StreamExt;
 use futures_util::stream::{self, Stream};
 use mcp_execution_core::cli::{LOG_FORMAT_ENV_VAR, LogFormat};
 use mcp_execution_core::untrusted::{MAX_UNTRUSTED_FIELD_LEN, sanitize_untrusted_text};
+use mcp_execution_core::{redact_urls_in_text, sanitize_path_for_error};
+use mcp_execution_server::policy::CommandPolicy;
 use mcp_execution_server::service::GeneratorService;
+use mcp_execution_server::session_store::SessionStore;
+use mcp_execution_skill::HostProfile;
+use mcp_execution_skill::host::{DEFAULT_HOST, HOST_NAMES, host_profile};
 use rmcp::RoleServer;
 use rmcp::ServiceExt;
 use rmcp::model::{GetExtensions, JsonRpcMessage};
@@ -40,6 +45,8 @@ use rmcp::transport::async_rw::{JsonRpcMessageCodec, JsonRpcMessageCodecError};
 use rmcp::transport::stdio;
 use std::collections::VecDeque;
 use std::fmt;
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::task::Poll;
@@ -78,6 +85,93 @@ struct ServerArgs {
             .map(|s| LogFormat::from_str(&s).expect("possible values are LogFormat variants"))
     )]
     log_format: Option<LogFormat>,
+
+    /// Directory generated servers are written under and read from.
+    ///
+    /// Defaults to `servers/` in the Claude config directory: `~/.claude`, moved by the
+    /// `MCP_EXECUTION_HOME` and `CLAUDE_CONFIG_DIR` environment variables.
+    #[arg(long = "servers-dir", value_name = "DIR")]
+    servers_dir: Option<PathBuf>,
+
+    /// Directory `save_skill` writes skills under.
+    ///
+    /// Defaults to `skills/` in the Claude config directory, like `--servers-dir`.
+    #[arg(long = "skills-dir", value_name = "DIR")]
+    skills_dir: Option<PathBuf>,
+
+    /// Agent host to generate for: `claude` (default), `codex`, `cursor`, or `generic`.
+    ///
+    /// Selects the bindings directory, the config file generated bridges read, and the
+    /// instructions `save_skill` writes (`SKILL.md`, a `.cursor/rules/*.mdc` rule, or an
+    /// `AGENTS.md` section). `--servers-dir`/`--skills-dir` still override the directories.
+    #[arg(
+        long = "host",
+        default_value = DEFAULT_HOST,
+        value_parser = PossibleValuesParser::new(HOST_NAMES)
+            .map(|s| host_profile(&s).expect("possible values are host profile names"))
+    )]
+    host: &'static dyn HostProfile,
+
+    /// JSON file allowlisting the commands `introspect_server` may spawn.
+    ///
+    /// Lists commands by exact path or basename, optionally with one glob per argument, and/or
+    /// sets `"allowConfigured": true` to accept command lines already in the user's MCP config.
+    /// Without it, any command is allowed.
+    #[arg(long = "policy", value_name = "FILE")]
+    policy: Option<PathBuf>,
+
+    /// Directory pending `introspect_server` sessions are kept in, so they survive a restart.
+    ///
+    /// Created owner-only if missing. Stored sessions hold server configs with argument, env,
+    /// header, and URL query values replaced by `REDACTED`. Without it, sessions live in memory
+    /// only.
+    #[arg(long = "session-dir", value_name = "DIR")]
+    session_dir: Option<PathBuf>,
+}
+
+/// Redacts credential-bearing URLs from each formatted log line before writing it to the inner
+/// sink, like `mcp-execution-cli`'s writer of the same name.
+///
+/// `tracing-subscriber`'s fmt layer issues exactly one `write_all` per event, so `write` always
+/// sees a whole line for [`redact_urls_in_text`] to scan.
+struct RedactingWriter<W>(W);
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(redact_urls_in_text(&text).as_bytes())?;
+        // All of `buf` was consumed, however many bytes redaction forwarded.
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Builds the service `main` serves, applying `--host`, `--servers-dir`, `--skills-dir`,
+/// `--policy`, and `--session-dir`.
+fn build_service(args: &ServerArgs) -> Result<GeneratorService> {
+    let mut service = GeneratorService::new().with_host(args.host);
+    if let Some(dir) = &args.servers_dir {
+        service = service.with_servers_base_dir(dir.clone());
+    }
+    if let Some(dir) = &args.skills_dir {
+        service = service.with_skills_base_dir(dir.clone());
+    }
+    if let Some(path) = &args.policy {
+        service = service.with_command_policy(CommandPolicy::load(path)?);
+    }
+    if let Some(dir) = &args.session_dir {
+        let store = SessionStore::open(dir.clone()).with_context(|| {
+            format!(
+                "failed to open session directory {}",
+                sanitize_path_for_error(dir)
+            )
+        })?;
+        service = service.with_session_store(store);
+    }
+    Ok(service)
 }
 
 /// Resolves the effective log format from the parsed `--log-format` flag and the
@@ -600,7 +694,7 @@ async fn main() -> Result<()> {
 
     // Initialize logging to stderr (stdout is for MCP protocol)
     let fmt_layer = tracing_subscriber::fmt::layer()
-        .with_writer(std::io::stderr)
+        .with_writer(|| RedactingWriter(std::io::stderr()))
         .with_target(true);
     let layer = match log_format {
         LogFormat::Json => fmt_layer.json().boxed(),
@@ -613,18 +707,14 @@ async fn main() -> Result<()> {
                 .unwrap_or_else(|_| EnvFilter::new("info,mcp_execution_server=debug")),
         ))
         .with(
-            // Not wrapped in `mcp-execution-cli::runner`'s URL-redacting writer (see #353):
-            // this process only ever builds a stdio server config from `IntrospectServerParams`
-            // (see `service::build_stdio_server_config`), which has no `url` field, and no other
-            // path here constructs an http/sse config -- so there is no `reqwest`/`rmcp` transport
-            // error whose `Display` could embed a secret-bearing URL for this writer to reach. The
-            // #209 regression test (`service.rs`) guards that invariant; if a future change adds
-            // an http/sse client path to this crate, wire the same writer in at that point.
+            // Wrapped in `RedactingWriter` (see #353): `introspect_server`'s `from_config` can
+            // connect to an http/sse server from the user's MCP config, and a `reqwest`/`rmcp`
+            // transport error's `Display` embeds that server's URL, query string included.
             //
             // `MCP_EXECUTION_LOG_FORMAT` (see `resolve_log_format`) is a second,
-            // narrower untrusted-text-into-log path this process does have, but the warning
-            // logged below never echoes its rejected raw value -- only a fixed diagnostic string
-            // naming the environment variable is logged -- so it does not need this writer either.
+            // narrower untrusted-text-into-log path this process has, but the warning logged
+            // below never echoes its rejected raw value -- only a fixed diagnostic string naming
+            // the environment variable is logged.
             layer,
         )
         .init();
@@ -646,7 +736,7 @@ async fn main() -> Result<()> {
         Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS)),
     );
 
-    let service = GeneratorService::new().serve((sink, stream)).await?;
+    let service = build_service(&args)?.serve((sink, stream)).await?;
     service.waiting().await?;
 
     tracing::info!("Server shutdown complete");
@@ -658,9 +748,10 @@ mod tests {
     use super::{
         AsyncRead, EnvFilter, GetExtensions, JsonRpcMessage, LOG_FORMAT_ENV_VAR, LogFormat,
         MAX_CONCURRENT_REQUESTS, MAX_REQUEST_LINE_SIZE, MAX_UNTRUSTED_FIELD_LEN,
-        OwnedSemaphorePermit, RoleServer, RxJsonRpcMessage, SanitizedCodecError, Semaphore,
-        ServerArgs, Stream, StreamExt, bounded_request_stream, cap_rmcp_log_level,
-        log_format_env_is_invalid, resolve_log_format, warn_on_rejected_log_format,
+        OwnedSemaphorePermit, RedactingWriter, RoleServer, RxJsonRpcMessage, SanitizedCodecError,
+        Semaphore, ServerArgs, Stream, StreamExt, bounded_request_stream, build_service,
+        cap_rmcp_log_level, log_format_env_is_invalid, resolve_log_format,
+        warn_on_rejected_log_format,
     };
     use clap::Parser as _;
     use mcp_execution_server::service::GeneratorService;
@@ -669,6 +760,7 @@ mod tests {
     use rmcp::service::TxJsonRpcMessage;
     use rmcp::transport::async_rw::{JsonRpcMessageCodec, JsonRpcMessageCodecError};
     use std::io;
+    use std::path::PathBuf;
     use std::pin::Pin;
     use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -1567,6 +1659,64 @@ mod tests {
         assert_eq!(args.log_format, Some(LogFormat::Json));
     }
 
+    #[test]
+    fn test_server_args_base_dirs() {
+        let args = ServerArgs::parse_from(["mcp-execution"]);
+        assert_eq!(args.servers_dir, None);
+        assert_eq!(args.skills_dir, None);
+
+        let args = ServerArgs::parse_from([
+            "mcp-execution",
+            "--servers-dir",
+            "/sandbox/servers",
+            "--skills-dir",
+            "/sandbox/skills",
+        ]);
+        assert_eq!(args.servers_dir, Some(PathBuf::from("/sandbox/servers")));
+        assert_eq!(args.skills_dir, Some(PathBuf::from("/sandbox/skills")));
+    }
+
+    #[test]
+    fn test_server_args_host() {
+        let args = ServerArgs::parse_from(["mcp-execution"]);
+        assert_eq!(args.host.name(), "claude");
+
+        let args = ServerArgs::parse_from(["mcp-execution", "--host", "codex"]);
+        assert_eq!(args.host.name(), "codex");
+
+        assert!(ServerArgs::try_parse_from(["mcp-execution", "--host", "emacs"]).is_err());
+    }
+
+    #[test]
+    fn test_build_service_loads_policy() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("policy.json");
+        std::fs::write(&path, r#"{"allowConfigured": true}"#).unwrap();
+        let args = ServerArgs::parse_from(["mcp-execution", "--policy", path.to_str().unwrap()]);
+        assert!(build_service(&args).is_ok());
+
+        std::fs::write(&path, r#"{"allow": []}"#).unwrap();
+        let err = build_service(&args).unwrap_err();
+        assert!(err.to_string().contains("policy.json"), "{err}");
+    }
+
+    #[test]
+    fn test_build_service_opens_session_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sessions = dir.path().join("sessions");
+        let args =
+            ServerArgs::parse_from(["mcp-execution", "--session-dir", sessions.to_str().unwrap()]);
+        assert!(build_service(&args).is_ok());
+        assert!(sessions.is_dir());
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        let args =
+            ServerArgs::parse_from(["mcp-execution", "--session-dir", file.to_str().unwrap()]);
+        let err = build_service(&args).unwrap_err();
+        assert!(err.to_string().contains("session directory"), "{err}");
+    }
+
     #[test]
     fn test_server_args_log_format_invalid_rejected_by_clap() {
         let result = ServerArgs::try_parse_from(["mcp-execution", "--log-format", "xml"]);
@@ -1615,6 +1765,11 @@ mod tests {
 
         let args = ServerArgs {
             log_format: Some(LogFormat::Json),
+            servers_dir: None,
+            skills_dir: None,
+            host: &mcp_execution_skill::host::ClaudeCode,
+            policy: None,
+            session_dir: None,
         };
         let format = resolve_log_format(&args);
         let is_invalid = log_format_env_is_invalid(&args);
@@ -1634,6 +1789,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn redacting_writer_redacts_url_secret_before_forwarding() {
+        use std::io::Write as _;
+
+        let mut sink = Vec::new();
+        let line = "ERROR rmcp::transport::worker: error sending request for url (https://api.example.invalid/mcp?token=hunter2secret)\n";
+        let n = RedactingWriter(&mut sink).write(line.as_bytes()).unwrap();
+        assert_eq!(n, line.len());
+        let written = String::from_utf8(sink).unwrap();
+        assert!(!written.contains("hunter2secret"));
+        assert!(written.contains("https://api.example.invalid/mcp?<redacted>"));
+    }
+
     /// Proves the environment variable is actually consulted end to end -- not just that
     /// `LogFormat::resolve` itself works in isolation: a `resolve_log_format` that forgot to
     /// call `std::env::var(LOG_FORMAT_ENV_VAR)` would still pass every test that only exercises
@@ -1648,7 +1816,14 @@ mod tests {
             std::env::set_var(LOG_FORMAT_ENV_VAR, "json");
         }
 
-        let args = ServerArgs { log_format: None };
+        let args = ServerArgs {
+            log_format: None,
+            servers_dir: None,
+            skills_dir: None,
+            host: &mcp_execution_skill::host::ClaudeCode,
+            policy: None,
+            session_dir: None,
+        };
         let format = resolve_log_format(&args);
 
         // SAFETY: see above.
@@ -1672,7 +1847,14 @@ mod tests {
             std::env::set_var(LOG_FORMAT_ENV_VAR, "xml");
         }
 
-        let args = ServerArgs { log_format: None };
+        let args = ServerArgs {
+            log_format: None,
+            servers_dir: None,
+            skills_dir: None,
+            host: &mcp_execution_skill::host::ClaudeCode,
+            policy: None,
+            session_dir: None,
+        };
         let format = resolve_log_format(&args);
 
         // SAFETY: see above.
@@ -1699,7 +1881,14 @@ mod tests {
             std::env::set_var(LOG_FORMAT_ENV_VAR, "xml");
         }
 
-        let args = ServerArgs { log_format: None };
+        let args = ServerArgs {
+            log_format: None,
+            servers_dir: None,
+            skills_dir: None,
+            host: &mcp_execution_skill::host::ClaudeCode,
+            policy: None,
+            session_dir: None,
+        };
         let is_invalid = log_format_env_is_invalid(&args);
 
         // SAFETY: see above.
@@ -1723,7 +1912,14 @@ mod tests {
             std::env::set_var(LOG_FORMAT_ENV_VAR, "json");
         }
 
-        let args = ServerArgs { log_format: None };
+        let args = ServerArgs {
+            log_format: None,
+            servers_dir: None,
+            skills_dir: None,
+            host: &mcp_execution_skill::host::ClaudeCode,
+            policy: None,
+            session_dir: None,
+        };
         let is_invalid = log_format_env_is_invalid(&args);
 
         // SAFETY: see above.
@@ -1747,7 +1943,14 @@ mod tests {
             std::env::remove_var(LOG_FORMAT_ENV_VAR);
         }
 
-        let args = ServerArgs { log_format: None };
+        let args = ServerArgs {
+            log_format: None,
+            servers_dir: None,
+            skills_dir: None,
+            host: &mcp_execution_skill::host::ClaudeCode,
+            policy: None,
+            session_dir: None,
+        };
         let is_invalid = log_format_env_is_invalid(&args);
 
         // SAFETY: see above.
@@ -0,0 +1,441 @@
+//! Allowlist of the commands `introspect_server` may spawn.
+//!
+//! `introspect_server` starts whatever stdio `command` its caller names, and the caller is a
+//! model. The forbidden-environment-variable checks in `mcp-execution-core` stop a handful of
+//! known injection vectors, but they are not a sandbox boundary. A [`CommandPolicy`], loaded
+//! from the file passed to `mcp-execution --policy`, narrows what may be spawned at all:
+//!
+//! ```json
+//! {
+//!   "commands": [
+//!     { "command": "npx", "args": ["-y", "@modelcontextprotocol/server-*"] },
+//!     { "command": "/opt/mcp/bin/github-server", "env": ["GITHUB_*"] }
+//!   ],
+//!   "allowConfigured": true
+//! }
+//! ```
+//!
+//! A rule's `command` containing a path separator must equal the requested command exactly;
+//! otherwise it is a basename, matched against the requested command's file name, so `npx`
+//! allows both `npx` and `/usr/local/bin/npx`. `args`, when present, holds one
+//! [glob](mcp_execution_core::tool_filter::glob_matches) per argument and requires exactly that
+//! many arguments; without it any arguments are accepted. `env` holds globs of the environment
+//! variable names the caller may set; without it, a request that sets any is rejected, since
+//! a variable like `npm_config_registry` changes what an allowed command runs.
+//! `allowConfigured` additionally accepts a command whose command line and `env` equal those of
+//! an entry already in the user's MCP config, which includes every `from_config` entry.
+//!
+//! The policy only governs spawning: remote (`http`/`sse`) servers, which `introspect_server`
+//! can reach only through `from_config`, are not affected by it.
+
+use mcp_execution_core::sanitize_path_for_error;
+use mcp_execution_core::tool_filter::glob_matches;
+use mcp_execution_core::untrusted::sanitize_untrusted_inline;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use thiserror::Error;
+
+/// Maximum number of rules accepted in a policy's `commands` list.
+pub const MAX_POLICY_RULES: usize = 256;
+
+/// Upper bound on the size of a policy file; real policies are a few hundred bytes.
+const MAX_POLICY_FILE_SIZE: u64 = 1024 * 1024;
+
+/// The `path` [`CommandPolicy::from_json`] errors carry, replaced by the file's path in
+/// [`CommandPolicy::load`].
+const INLINE_SOURCE: &str = "(inline)";
+
+/// Errors from loading a [`CommandPolicy`].
+#[derive(Debug, Error)]
+pub enum PolicyError {
+    /// The policy file could not be read.
+    #[error("failed to read command policy {path}: {source}")]
+    Io {
+        /// Sanitized path of the file.
+        path: String,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// The policy file is larger than any real policy could be.
+    #[error("command policy {path} exceeds the {MAX_POLICY_FILE_SIZE} byte limit")]
+    TooLarge {
+        /// Sanitized path of the file.
+        path: String,
+    },
+    /// The policy file is not valid JSON or has unknown fields.
+    #[error("failed to parse command policy {path}: {source}")]
+    Parse {
+        /// Sanitized path of the file.
+        path: String,
+        /// Underlying parse error.
+        #[source]
+        source: serde_json::Error,
+    },
+    /// The policy parsed but a rule is unusable.
+    #[error("invalid command policy {path}: {reason}")]
+    Invalid {
+        /// Sanitized path of the file.
+        path: String,
+        /// What is wrong with it.
+        reason: String,
+    },
+}
+
+/// One entry of a policy's `commands` list.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommandRule {
+    command: String,
+    #[serde(default)]
+    args: Option<Vec<String>>,
+    #[serde(default)]
+    env: Vec<String>,
+}
+
+impl CommandRule {
+    fn is_path(&self) -> bool {
+        self.command.contains(['/', '\\'])
+    }
+
+    fn matches(&self, command: &str, args: &[String], env: &HashMap<String, String>) -> bool {
+        let command_matches = if self.is_path() {
+            command == self.command
+        } else {
+            Path::new(command)
+                .file_name()
+                .is_some_and(|name| name == self.command.as_str())
+        };
+        command_matches
+            && self.args.as_ref().is_none_or(|patterns| {
+                patterns.len() == args.len()
+                    && patterns
+                        .iter()
+                        .zip(args)
+                        .all(|(pattern, arg)| glob_matches(pattern, arg))
+            })
+            && env
+                .keys()
+                .all(|name| self.env.iter().any(|pattern| glob_matches(pattern, name)))
+    }
+
+    /// Why this rule cannot be used, if it cannot.
+    fn problem(&self) -> Option<&'static str> {
+        let has_bad_char = |text: &str| text.chars().any(char::is_control);
+        if self.command.is_empty() {
+            Some("a rule's command is empty")
+        } else if has_bad_char(&self.command) {
+            Some("a rule's command contains a control character")
+        } else if self.args.as_ref().is_some_and(|args| {
+            args.len() > mcp_execution_core::MAX_ARG_COUNT
+                || args.iter().any(|arg| has_bad_char(arg))
+        }) {
+            Some("a rule's args has too many patterns or a control character")
+        } else if self.env.len() > mcp_execution_core::MAX_ENV_COUNT
+            || self.env.iter().any(|name| has_bad_char(name))
+        {
+            Some("a rule's env has too many patterns or a control character")
+        } else {
+            None
+        }
+    }
+}
+
+/// A validated allowlist of commands `introspect_server` may spawn.
+///
+/// See the [module documentation](self) for the file format and matching rules.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::policy::CommandPolicy;
+/// use std::collections::HashMap;
+///
+/// let policy = CommandPolicy::from_json(
+///     r#"{"commands": [{"command": "npx", "args": ["-y", "@modelcontextprotocol/*"]}]}"#,
+/// )
+/// .unwrap();
+///
+/// let args = |list: &[&str]| list.iter().map(ToString::to_string).collect::<Vec<_>>();
+/// let no_env = HashMap::new();
+/// assert!(policy.allows("npx", &args(&["-y", "@modelcontextprotocol/server-github"]), &no_env));
+/// assert!(!policy.allows("npx", &args(&["-y", "evil-package"]), &no_env));
+/// assert!(!policy.allows_configured());
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct CommandPolicy {
+    #[serde(default)]
+    commands: Vec<CommandRule>,
+    #[serde(default)]
+    allow_configured: bool,
+}
+
+impl CommandPolicy {
+    /// Reads and validates the policy file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PolicyError`] if the file cannot be read, is implausibly large, is not a valid
+    /// policy, or has more than [`MAX_POLICY_RULES`] rules or an empty command.
+    pub fn load(path: &Path) -> Result<Self, PolicyError> {
+        let display = || sanitize_path_for_error(path);
+        let io_error = |source| PolicyError::Io {
+            path: display(),
+            source,
+        };
+        if std::fs::metadata(path).map_err(io_error)?.len() > MAX_POLICY_FILE_SIZE {
+            return Err(PolicyError::TooLarge { path: display() });
+        }
+        let text = std::fs::read_to_string(path).map_err(io_error)?;
+        Self::from_json(&text).map_err(|err| match err {
+            PolicyError::Parse { source, .. } => PolicyError::Parse {
+                path: display(),
+                source,
+            },
+            PolicyError::Invalid { reason, .. } => PolicyError::Invalid {
+                path: display(),
+                reason,
+            },
+            other => other,
+        })
+    }
+
+    /// Parses and validates a policy from its JSON text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PolicyError::Parse`] or [`PolicyError::Invalid`], naming the source `(inline)`.
+    pub fn from_json(text: &str) -> Result<Self, PolicyError> {
+        let policy: Self = serde_json::from_str(text).map_err(|source| PolicyError::Parse {
+            path: INLINE_SOURCE.to_string(),
+            source,
+        })?;
+        let invalid = |reason: String| PolicyError::Invalid {
+            path: INLINE_SOURCE.to_string(),
+            reason,
+        };
+        if policy.commands.len() > MAX_POLICY_RULES {
+            return Err(invalid(format!(
+                "{} rules exceeds the limit of {MAX_POLICY_RULES}",
+                policy.commands.len()
+            )));
+        }
+        if let Some(problem) = policy.commands.iter().find_map(CommandRule::problem) {
+            return Err(invalid(problem.to_string()));
+        }
+        Ok(policy)
+    }
+
+    /// Whether a rule in `commands` allows spawning `command` with `args` and the environment
+    /// variables `env` sets.
+    #[must_use]
+    pub fn allows(&self, command: &str, args: &[String], env: &HashMap<String, String>) -> bool {
+        self.commands
+            .iter()
+            .any(|rule| rule.matches(command, args, env))
+    }
+
+    /// Whether command lines already in the user's MCP config, with that entry's `env`, are
+    /// allowed too.
+    #[must_use]
+    pub const fn allows_configured(&self) -> bool {
+        self.allow_configured
+    }
+
+    /// A one-sentence description of what the policy allows, for tool descriptions and
+    /// rejection messages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_server::policy::CommandPolicy;
+    ///
+    /// let policy = CommandPolicy::from_json(r#"{"allowConfigured": true}"#).unwrap();
+    /// assert_eq!(
+    ///     policy.summary(),
+    ///     "Only servers already in the user's MCP config may be spawned."
+    /// );
+    /// ```
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if self.commands.is_empty() {
+            return if self.allow_configured {
+                "Only servers already in the user's MCP config may be spawned.".to_string()
+            } else {
+                "No stdio server may be spawned; only remote servers from the user's MCP \
+                 config can be introspected."
+                    .to_string()
+            };
+        }
+
+        let mut summary = "Only these commands may be spawned: ".to_string();
+        for (i, rule) in self.commands.iter().enumerate() {
+            if i > 0 {
+                summary.push_str(", ");
+            }
+            let _ = write!(summary, "`{}", sanitize_untrusted_inline(&rule.command));
+            match &rule.args {
+                Some(args) if args.is_empty() => summary.push_str("` (no arguments)"),
+                Some(args) => {
+                    for arg in args {
+                        let _ = write!(summary, " {}", sanitize_untrusted_inline(arg));
+                    }
+                    summary.push('`');
+                }
+                None => summary.push_str("` (any arguments)"),
+            }
+            if !rule.env.is_empty() {
+                let names: Vec<String> = rule
+                    .env
+                    .iter()
+                    .map(|name| sanitize_untrusted_inline(name))
+                    .collect();
+                let _ = write!(summary, " with env {}", names.join(" "));
+            }
+        }
+        if self.allow_configured {
+            summary.push_str(", and servers already in the user's MCP config");
+        }
+        summary.push_str(". Env variables a rule does not list are rejected.");
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(json: &str) -> CommandPolicy {
+        CommandPolicy::from_json(json).unwrap()
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    fn env(names: &[&str]) -> HashMap<String, String> {
+        names
+            .iter()
+            .map(|name| ((*name).to_string(), "value".to_string()))
+            .collect()
+    }
+
+    fn no_env() -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    #[test]
+    fn basename_rule_matches_any_path_with_that_file_name() {
+        let policy = policy(r#"{"commands": [{"command": "uvx"}]}"#);
+        assert!(policy.allows("uvx", &args(&["mcp-server-git"]), &no_env()));
+        assert!(policy.allows("/usr/local/bin/uvx", &[], &no_env()));
+        assert!(!policy.allows("uvx-evil", &[], &no_env()));
+        assert!(!policy.allows("/usr/bin/sh", &args(&["uvx"]), &no_env()));
+    }
+
+    #[test]
+    fn path_rule_requires_exact_path() {
+        let policy = policy(r#"{"commands": [{"command": "/opt/mcp/server"}]}"#);
+        assert!(policy.allows("/opt/mcp/server", &[], &no_env()));
+        assert!(!policy.allows("server", &[], &no_env()));
+        assert!(!policy.allows("/tmp/opt/mcp/server", &[], &no_env()));
+    }
+
+    #[test]
+    fn args_patterns_match_positionally_and_by_count() {
+        let policy = policy(
+            r#"{"commands": [{"command": "npx", "args": ["-y", "@modelcontextprotocol/*"]}]}"#,
+        );
+        assert!(policy.allows(
+            "npx",
+            &args(&["-y", "@modelcontextprotocol/server-git"]),
+            &no_env()
+        ));
+        assert!(!policy.allows(
+            "npx",
+            &args(&["@modelcontextprotocol/server-git", "-y"]),
+            &no_env()
+        ));
+        assert!(!policy.allows("npx", &args(&["-y"]), &no_env()));
+        assert!(!policy.allows(
+            "npx",
+            &args(&["-y", "@modelcontextprotocol/server-git", "--evil"]),
+            &no_env()
+        ));
+    }
+
+    #[test]
+    fn env_names_must_match_a_rule_pattern() {
+        let policy = policy(
+            r#"{"commands": [
+                {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-*"]},
+                {"command": "github-server", "env": ["GITHUB_*"]}
+            ]}"#,
+        );
+        let npx_args = args(&["-y", "@modelcontextprotocol/server-git"]);
+        assert!(!policy.allows("npx", &npx_args, &env(&["npm_config_registry"])));
+        assert!(policy.allows("github-server", &[], &env(&["GITHUB_TOKEN"])));
+        assert!(!policy.allows(
+            "github-server",
+            &[],
+            &env(&["GITHUB_TOKEN", "NODE_EXTRA_CA_CERTS"])
+        ));
+    }
+
+    #[test]
+    fn empty_policy_allows_nothing() {
+        let policy = policy("{}");
+        assert!(!policy.allows("echo", &[], &no_env()));
+        assert!(!policy.allows_configured());
+    }
+
+    #[test]
+    fn from_json_rejects_bad_policies() {
+        assert!(matches!(
+            CommandPolicy::from_json(r#"{"comands": []}"#),
+            Err(PolicyError::Parse { .. })
+        ));
+        assert!(matches!(
+            CommandPolicy::from_json(r#"{"commands": [{"command": ""}]}"#),
+            Err(PolicyError::Invalid { .. })
+        ));
+        let too_many = format!(
+            r#"{{"commands": [{}]}}"#,
+            vec![r#"{"command": "x"}"#; MAX_POLICY_RULES + 1].join(",")
+        );
+        assert!(matches!(
+            CommandPolicy::from_json(&too_many),
+            Err(PolicyError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn load_names_the_file_in_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("policy.json");
+        std::fs::write(&path, "not json").unwrap();
+        let err = CommandPolicy::load(&path).unwrap_err();
+        assert!(err.to_string().contains("policy.json"), "{err}");
+
+        let missing = CommandPolicy::load(&dir.path().join("missing.json")).unwrap_err();
+        assert!(matches!(missing, PolicyError::Io { .. }));
+    }
+
+    #[test]
+    fn summary_lists_rules() {
+        let policy = policy(
+            r#"{"commands": [{"command": "npx", "args": ["-y", "pkg-*"]},
+                             {"command": "srv", "env": ["SRV_*"]}],
+                "allowConfigured": true}"#,
+        );
+        assert_eq!(
+            policy.summary(),
+            "Only these commands may be spawned: `npx -y pkg-*`, `srv` (any arguments) with env \
+             SRV_*, and servers already in the user's MCP config. Env variables a rule does not \
+             list are rejected."
+        );
+    }
+}
@@ -0,0 +1,540 @@
+//! MCP resources over the generated bindings, backing `resources/list`, `resources/read`, and
+//! `resources/subscribe`.
+//!
+//! Every generated server directory directly under the servers directory is served as
+//! `mcp-execution://servers/<server_id>/<file>`, where `<file>` is one of its top-level `.ts`
+//! files (each tool's file and `index.ts`) or its `_meta.json` sidecar. When the host keeps its
+//! instructions as standalone `SKILL.md` files, each saved skill is also served as
+//! `mcp-execution://skills/<server_id>/SKILL.md`. A host that cannot run `ls`/`cat` against
+//! `~/.claude` can then load a server's tools one file at a time through the protocol alone.
+//!
+//! Reads are confined the same way writes are: a server ID must be a valid slug, a file name a
+//! single path component, and neither the server's directory nor the file may be a symlink
+//! (the file is opened with [`open_confined_read`]).
+//!
+//! # Examples
+//!
+//! ```
+//! use mcp_execution_server::resources::ResourceUri;
+//!
+//! let uri = ResourceUri::parse("mcp-execution://servers/github/createIssue.ts").unwrap();
+//! assert_eq!(uri.server_id(), "github");
+//! assert_eq!(uri.mime_type(), "text/typescript");
+//! assert_eq!(uri.to_string(), "mcp-execution://servers/github/createIssue.ts");
+//!
+//! assert!(ResourceUri::parse("mcp-execution://servers/github/../secrets.ts").is_err());
+//! ```
+
+use mcp_execution_core::metadata::METADATA_FILE_NAME;
+use mcp_execution_core::{open_confined_read, validate_server_id_slug};
+use rmcp::model::{Resource, ResourceTemplate};
+use rmcp::service::{Peer, RoleServer};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Scheme and authority-less prefix shared by every resource URI.
+pub const RESOURCE_URI_PREFIX: &str = "mcp-execution://";
+
+/// Largest file `resources/read` serves, in bytes (1 MB).
+pub const MAX_RESOURCE_SIZE: u64 = 1024 * 1024;
+
+/// Most resource URIs one service tracks subscriptions for.
+pub const MAX_RESOURCE_SUBSCRIPTIONS: usize = 1000;
+
+const SERVERS_SEGMENT: &str = "servers";
+const SKILLS_SEGMENT: &str = "skills";
+const SKILL_FILE_NAME: &str = "SKILL.md";
+
+/// A parsed resource URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    /// A top-level file of a generated server: a `.ts` file or `_meta.json`.
+    ServerFile {
+        /// The generated server's ID.
+        server_id: String,
+        /// The file's name within the server's directory.
+        file_name: String,
+    },
+    /// The `SKILL.md` saved for a server.
+    Skill {
+        /// The server the skill was saved for.
+        server_id: String,
+    },
+}
+
+impl ResourceUri {
+    /// Parses `uri`, rejecting anything that does not name a servable file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ResourceError::InvalidUri`] if `uri` has another scheme or shape, its server ID
+    /// is not a valid slug, or its file name is not a single `.ts` or `_meta.json` component.
+    pub fn parse(uri: &str) -> Result<Self, ResourceError> {
+        let invalid = || ResourceError::InvalidUri {
+            uri: uri.to_string(),
+        };
+        let rest = uri.strip_prefix(RESOURCE_URI_PREFIX).ok_or_else(invalid)?;
+        let mut segments = rest.split('/');
+        let (Some(kind), Some(server_id), Some(file_name), None) = (
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+        ) else {
+            return Err(invalid());
+        };
+        validate_server_id_slug(server_id).map_err(|_| invalid())?;
+        let server_id = server_id.to_string();
+
+        match kind {
+            SERVERS_SEGMENT if is_servable_file_name(file_name) => Ok(Self::ServerFile {
+                server_id,
+                file_name: file_name.to_string(),
+            }),
+            SKILLS_SEGMENT if file_name == SKILL_FILE_NAME => Ok(Self::Skill { server_id }),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Returns the ID of the server this resource belongs to.
+    #[must_use]
+    pub fn server_id(&self) -> &str {
+        match self {
+            Self::ServerFile { server_id, .. } | Self::Skill { server_id } => server_id,
+        }
+    }
+
+    /// Returns the resource's MIME type.
+    #[must_use]
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::ServerFile { file_name, .. } if file_name == METADATA_FILE_NAME => {
+                "application/json"
+            }
+            Self::ServerFile { .. } => "text/typescript",
+            Self::Skill { .. } => "text/markdown",
+        }
+    }
+
+    /// Returns the directory holding the resource and the resource's path within it.
+    fn locate(&self, servers_dir: &Path, skills_dir: Option<&Path>) -> Option<(PathBuf, PathBuf)> {
+        match self {
+            Self::ServerFile {
+                server_id,
+                file_name,
+            } => {
+                let dir = servers_dir.join(server_id);
+                let path = dir.join(file_name);
+                Some((dir, path))
+            }
+            Self::Skill { server_id } => {
+                let dir = skills_dir?.join(server_id);
+                let path = dir.join(SKILL_FILE_NAME);
+                Some((dir, path))
+            }
+        }
+    }
+
+    fn to_resource(&self, size: u64) -> Resource {
+        let name = match self {
+            Self::ServerFile {
+                server_id,
+                file_name,
+            } => format!("{server_id}/{file_name}"),
+            Self::Skill { server_id } => format!("{server_id}/{SKILL_FILE_NAME}"),
+        };
+        Resource::new(self.to_string(), name)
+            .with_mime_type(self.mime_type())
+            .with_size(size)
+    }
+}
+
+impl fmt::Display for ResourceUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ServerFile {
+                server_id,
+                file_name,
+            } => write!(
+                f,
+                "{RESOURCE_URI_PREFIX}{SERVERS_SEGMENT}/{server_id}/{file_name}"
+            ),
+            Self::Skill { server_id } => write!(
+                f,
+                "{RESOURCE_URI_PREFIX}{SKILLS_SEGMENT}/{server_id}/{SKILL_FILE_NAME}"
+            ),
+        }
+    }
+}
+
+/// Errors from parsing or reading a resource.
+#[derive(Debug, Error)]
+pub enum ResourceError {
+    /// The URI does not name a servable file.
+    #[error("not an mcp-execution resource URI: {uri}")]
+    InvalidUri {
+        /// The rejected URI.
+        uri: String,
+    },
+
+    /// Nothing is served at the URI.
+    #[error("resource not found: {uri}")]
+    NotFound {
+        /// The URI that was read.
+        uri: String,
+    },
+
+    /// The file exceeds [`MAX_RESOURCE_SIZE`].
+    #[error("resource {uri} is {size} bytes, exceeding the {MAX_RESOURCE_SIZE} byte limit")]
+    TooLarge {
+        /// The URI that was read.
+        uri: String,
+        /// The file's size in bytes.
+        size: u64,
+    },
+
+    /// The file exists but could not be read.
+    #[error("failed to read resource {uri}: {source}")]
+    Io {
+        /// The URI that was read.
+        uri: String,
+        /// The underlying error.
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Returns whether `file_name` is a top-level server file `resources/read` serves.
+fn is_servable_file_name(file_name: &str) -> bool {
+    if file_name == METADATA_FILE_NAME {
+        return true;
+    }
+    file_name
+        .strip_suffix(".ts")
+        .is_some_and(|stem| !stem.is_empty() && !stem.starts_with('.'))
+        && !file_name.contains(['/', '\\', '\0'])
+}
+
+/// Returns whether `path` is a directory that is not itself a symlink.
+fn is_real_dir(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|meta| meta.is_dir())
+}
+
+/// Returns the size of `path` if it is a regular file that is not a symlink.
+fn regular_file_size(path: &Path) -> Option<u64> {
+    std::fs::symlink_metadata(path)
+        .ok()
+        .filter(std::fs::Metadata::is_file)
+        .map(|meta| meta.len())
+}
+
+/// Lists every resource under `servers_dir` and, if given, `skills_dir`, sorted by URI.
+///
+/// Entries whose names are not valid server IDs or servable file names, and symlinks, are
+/// skipped rather than reported. A missing directory lists nothing.
+#[must_use]
+pub fn list_resources(servers_dir: &Path, skills_dir: Option<&Path>) -> Vec<Resource> {
+    let mut found: Vec<(ResourceUri, u64)> = Vec::new();
+
+    for (server_id, server_dir) in server_dirs(servers_dir) {
+        let Ok(entries) = std::fs::read_dir(&server_dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if !is_servable_file_name(&file_name) {
+                continue;
+            }
+            if let Some(size) = regular_file_size(&entry.path()) {
+                found.push((
+                    ResourceUri::ServerFile {
+                        server_id: server_id.clone(),
+                        file_name,
+                    },
+                    size,
+                ));
+            }
+        }
+    }
+
+    if let Some(skills_dir) = skills_dir {
+        for (server_id, skill_dir) in server_dirs(skills_dir) {
+            if let Some(size) = regular_file_size(&skill_dir.join(SKILL_FILE_NAME)) {
+                found.push((ResourceUri::Skill { server_id }, size));
+            }
+        }
+    }
+
+    let mut resources: Vec<Resource> = found
+        .iter()
+        .map(|(uri, size)| uri.to_resource(*size))
+        .collect();
+    resources.sort_by(|a, b| a.uri.cmp(&b.uri));
+    resources
+}
+
+/// Returns the subdirectories of `base_dir` named by a valid server ID, skipping symlinks.
+fn server_dirs(base_dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(base_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let server_id = entry.file_name().into_string().ok()?;
+            validate_server_id_slug(&server_id).ok()?;
+            let path = entry.path();
+            is_real_dir(&path).then_some((server_id, path))
+        })
+        .collect()
+}
+
+/// Returns the URI templates [`list_resources`]' URIs follow.
+#[must_use]
+pub fn resource_templates(serve_skills: bool) -> Vec<ResourceTemplate> {
+    let mut templates = vec![
+        ResourceTemplate::new(
+            format!("{RESOURCE_URI_PREFIX}{SERVERS_SEGMENT}/{{server_id}}/{{file}}"),
+            "generated-server-file",
+        )
+        .with_description(
+            "A generated server's TypeScript file (a tool's file or index.ts) or its \
+             _meta.json sidecar",
+        ),
+    ];
+    if serve_skills {
+        templates.push(
+            ResourceTemplate::new(
+                format!("{RESOURCE_URI_PREFIX}{SKILLS_SEGMENT}/{{server_id}}/{SKILL_FILE_NAME}"),
+                "skill",
+            )
+            .with_description("The SKILL.md saved for a generated server")
+            .with_mime_type("text/markdown"),
+        );
+    }
+    templates
+}
+
+/// Reads the resource at `uri` as text.
+///
+/// `skills_dir` is `None` when the host does not keep standalone `SKILL.md` files, in which case
+/// skill URIs are not found.
+///
+/// # Errors
+///
+/// Returns [`ResourceError::NotFound`] if nothing is served at `uri` (including when the file or
+/// its server's directory is a symlink), [`ResourceError::TooLarge`] if the file exceeds
+/// [`MAX_RESOURCE_SIZE`], and [`ResourceError::Io`] if it cannot be read as UTF-8.
+pub fn read_resource(
+    uri: &ResourceUri,
+    servers_dir: &Path,
+    skills_dir: Option<&Path>,
+) -> Result<String, ResourceError> {
+    let not_found = || ResourceError::NotFound {
+        uri: uri.to_string(),
+    };
+    let (dir, path) = uri.locate(servers_dir, skills_dir).ok_or_else(not_found)?;
+    if !is_real_dir(&dir) {
+        return Err(not_found());
+    }
+
+    let file = match open_confined_read(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Err(not_found()),
+        // A symlink at the file itself surfaces as a platform-specific open error; the lstat
+        // tells it apart from a genuine I/O failure.
+        Err(_) if regular_file_size(&path).is_none() => return Err(not_found()),
+        Err(source) => {
+            return Err(ResourceError::Io {
+                uri: uri.to_string(),
+                source,
+            });
+        }
+    };
+    let io_error = |source| ResourceError::Io {
+        uri: uri.to_string(),
+        source,
+    };
+    let metadata = file.metadata().map_err(io_error)?;
+    if !metadata.is_file() {
+        return Err(not_found());
+    }
+    if metadata.len() > MAX_RESOURCE_SIZE {
+        return Err(ResourceError::TooLarge {
+            uri: uri.to_string(),
+            size: metadata.len(),
+        });
+    }
+
+    let mut content = String::new();
+    file.take(MAX_RESOURCE_SIZE)
+        .read_to_string(&mut content)
+        .map_err(io_error)?;
+    Ok(content)
+}
+
+/// The peers subscribed to each resource URI.
+///
+/// Subscriptions are kept per URI, not per file, so a client can subscribe to a server that has
+/// not been generated yet and hear about its first generation. A peer whose notification fails
+/// is dropped.
+#[derive(Debug, Default)]
+pub struct ResourceSubscriptions {
+    peers: std::sync::Mutex<HashMap<String, Peer<RoleServer>>>,
+}
+
+impl ResourceSubscriptions {
+    /// Subscribes `peer` to `uri`, replacing any earlier subscriber.
+    ///
+    /// # Errors
+    ///
+    /// Returns the number of tracked URIs if `uri` is new and [`MAX_RESOURCE_SUBSCRIPTIONS`]
+    /// are already tracked.
+    pub fn subscribe(&self, uri: &ResourceUri, peer: Peer<RoleServer>) -> Result<(), usize> {
+        let uri = uri.to_string();
+        let mut peers = self.lock();
+        if !peers.contains_key(&uri) && peers.len() >= MAX_RESOURCE_SUBSCRIPTIONS {
+            return Err(peers.len());
+        }
+        peers.insert(uri, peer);
+        drop(peers);
+        Ok(())
+    }
+
+    /// Removes the subscription to `uri`, if any.
+    pub fn unsubscribe(&self, uri: &ResourceUri) {
+        self.lock().remove(&uri.to_string());
+    }
+
+    /// Notifies the subscribers of every URI of `server_id` accepted by `affected`.
+    pub async fn notify(&self, server_id: &str, affected: impl Fn(&ResourceUri) -> bool) {
+        let targets: Vec<(String, Peer<RoleServer>)> = self
+            .lock()
+            .iter()
+            .filter(|(uri, _)| {
+                ResourceUri::parse(uri)
+                    .is_ok_and(|uri| uri.server_id() == server_id && affected(&uri))
+            })
+            .map(|(uri, peer)| (uri.clone(), peer.clone()))
+            .collect();
+
+        for (uri, peer) in targets {
+            let params = rmcp::model::ResourceUpdatedNotificationParam::new(uri.clone());
+            if let Err(err) = peer.notify_resource_updated(params).await {
+                tracing::debug!(%uri, "dropping resource subscription: {err}");
+                self.lock().remove(&uri);
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Peer<RoleServer>>> {
+        self.peers
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_rejects_uris_outside_the_served_files() {
+        for uri in [
+            "file:///etc/passwd",
+            "mcp-execution://servers/github",
+            "mcp-execution://servers/github/../x.ts",
+            "mcp-execution://servers/../github/x.ts",
+            "mcp-execution://servers/github/_runtime/mcp-bridge.ts",
+            "mcp-execution://servers/github/notes.md",
+            "mcp-execution://servers/github/.ts",
+            "mcp-execution://servers/GitHub!/x.ts",
+            "mcp-execution://skills/github/README.md",
+            "mcp-execution://other/github/x.ts",
+        ] {
+            assert!(ResourceUri::parse(uri).is_err(), "{uri} should be rejected");
+        }
+
+        for uri in [
+            "mcp-execution://servers/github/createIssue.ts",
+            "mcp-execution://servers/github/_meta.json",
+            "mcp-execution://skills/github/SKILL.md",
+        ] {
+            assert_eq!(ResourceUri::parse(uri).unwrap().to_string(), uri);
+        }
+    }
+
+    #[test]
+    fn test_list_and_read_serve_only_confined_files() {
+        let servers = TempDir::new().unwrap();
+        let skills = TempDir::new().unwrap();
+        let github = servers.path().join("github");
+        std::fs::create_dir_all(github.join("_runtime")).unwrap();
+        std::fs::write(github.join("createIssue.ts"), "export {};").unwrap();
+        std::fs::write(github.join("_meta.json"), "{}").unwrap();
+        std::fs::write(github.join("README.md"), "skipped").unwrap();
+        std::fs::write(github.join("_runtime").join("mcp-bridge.ts"), "skipped").unwrap();
+        std::fs::create_dir_all(skills.path().join("github")).unwrap();
+        std::fs::write(skills.path().join("github").join("SKILL.md"), "# GitHub").unwrap();
+
+        let uris: Vec<String> = list_resources(servers.path(), Some(skills.path()))
+            .into_iter()
+            .map(|resource| resource.uri)
+            .collect();
+        assert_eq!(
+            uris,
+            vec![
+                "mcp-execution://servers/github/_meta.json",
+                "mcp-execution://servers/github/createIssue.ts",
+                "mcp-execution://skills/github/SKILL.md",
+            ]
+        );
+        assert_eq!(list_resources(servers.path(), None).len(), 2);
+
+        let tool = ResourceUri::parse("mcp-execution://servers/github/createIssue.ts").unwrap();
+        assert_eq!(
+            read_resource(&tool, servers.path(), None).unwrap(),
+            "export {};"
+        );
+        let skill = ResourceUri::parse("mcp-execution://skills/github/SKILL.md").unwrap();
+        assert_eq!(
+            read_resource(&skill, servers.path(), Some(skills.path())).unwrap(),
+            "# GitHub"
+        );
+        assert!(matches!(
+            read_resource(&skill, servers.path(), None),
+            Err(ResourceError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_read_does_not_follow_symlinks() {
+        let servers = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        std::fs::write(outside.path().join("secret.ts"), "secret").unwrap();
+        let github = servers.path().join("github");
+        std::fs::create_dir(&github).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret.ts"), github.join("leak.ts"))
+            .unwrap();
+        std::os::unix::fs::symlink(outside.path(), servers.path().join("linked")).unwrap();
+
+        assert!(list_resources(servers.path(), None).is_empty());
+        for uri in [
+            "mcp-execution://servers/github/leak.ts",
+            "mcp-execution://servers/linked/secret.ts",
+        ] {
+            let uri = ResourceUri::parse(uri).unwrap();
+            assert!(matches!(
+                read_resource(&uri, servers.path(), None),
+                Err(ResourceError::NotFound { .. })
+            ));
+        }
+    }
+}
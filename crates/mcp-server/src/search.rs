@@ -0,0 +1,432 @@
+//! Relevance search over the tools of every generated server, backing `search_tools`.
+//!
+//! A [`ToolIndex`] holds one document per tool recorded in the `_meta.json` of each server
+//! directory directly under the servers directory. [`ToolIndex::refresh`] re-reads only the
+//! sidecars whose size or modification time changed since the last call, and drops servers
+//! whose sidecar is gone, so calling it before every search keeps the index current without a
+//! file watcher.
+//!
+//! Documents are ranked with BM25 (`k1 = 1.2`, `b = 0.75`). A term's frequency is weighted by
+//! the field it came from: a tool's name counts three times, its category and keywords twice,
+//! and its descriptions and parameter names once. Text is split into lowercase alphanumeric
+//! terms at punctuation, underscores, and `camelCase` boundaries, so `createIssue`,
+//! `create_issue`, and "create an issue" all share the terms `create` and `issue`.
+//!
+//! Everything a sidecar records about a tool came from the server that was generated, so each
+//! returned field is run through [`sanitize_untrusted_text`], and `search_tools` wraps its whole
+//! result as untrusted data.
+
+use crate::types::ToolSearchHit;
+use mcp_execution_core::metadata::{
+    METADATA_FILE_NAME, ToolMetadata, read_persisted_tool_metadata,
+};
+use mcp_execution_core::sanitize_path_for_error;
+use mcp_execution_core::untrusted::{MAX_UNTRUSTED_FIELD_LEN, sanitize_untrusted_text};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Longest `query` `search_tools` accepts, in bytes.
+pub const MAX_SEARCH_QUERY_LEN: usize = 500;
+
+/// Largest `limit` `search_tools` accepts.
+pub const MAX_SEARCH_LIMIT: usize = 50;
+
+/// Number of results `search_tools` returns when no `limit` is given.
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+const NAME_WEIGHT: u32 = 3;
+const KEYWORD_WEIGHT: u32 = 2;
+const TEXT_WEIGHT: u32 = 1;
+
+/// Search index over the generated servers' tools.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::search::ToolIndex;
+///
+/// let servers = tempfile::tempdir().unwrap();
+/// let mut index = ToolIndex::default();
+/// index.refresh(servers.path());
+///
+/// assert_eq!(index.tool_count(None), 0);
+/// assert!(index.search("create issue", None, 10).is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct ToolIndex {
+    servers: HashMap<String, IndexedServer>,
+}
+
+#[derive(Debug)]
+struct IndexedServer {
+    stamp: SidecarStamp,
+    tools: Vec<IndexedTool>,
+}
+
+/// What [`ToolIndex::refresh`] compares to decide whether a sidecar changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SidecarStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+#[derive(Debug)]
+struct IndexedTool {
+    /// The hit this tool produces, with a zero score.
+    hit: ToolSearchHit,
+    /// Weighted frequency of each term.
+    terms: HashMap<String, u32>,
+    /// Sum of `terms`' weighted frequencies.
+    length: u32,
+}
+
+impl ToolIndex {
+    /// Brings the index up to date with the server directories directly under `base_dir`.
+    ///
+    /// A sidecar that cannot be read is logged and its server left out until it can be.
+    pub fn refresh(&mut self, base_dir: &Path) {
+        let mut seen = HashSet::new();
+        if let Ok(entries) = std::fs::read_dir(base_dir) {
+            for entry in entries.flatten() {
+                let server_dir = entry.path();
+                if !server_dir.is_dir() {
+                    continue;
+                }
+                let Ok(metadata) = std::fs::metadata(server_dir.join(METADATA_FILE_NAME)) else {
+                    continue;
+                };
+                let server_id = entry.file_name().to_string_lossy().into_owned();
+                let stamp = SidecarStamp {
+                    len: metadata.len(),
+                    modified: metadata.modified().ok(),
+                };
+                seen.insert(server_id.clone());
+
+                if self
+                    .servers
+                    .get(&server_id)
+                    .is_some_and(|server| server.stamp == stamp)
+                {
+                    continue;
+                }
+                match read_persisted_tool_metadata(&server_dir) {
+                    Ok(tools) => {
+                        let tools = tools
+                            .iter()
+                            .map(|tool| index_tool(&server_id, &server_dir, tool))
+                            .collect();
+                        self.servers
+                            .insert(server_id, IndexedServer { stamp, tools });
+                    }
+                    Err(err) => {
+                        tracing::warn!(
+                            server_dir = %sanitize_path_for_error(&server_dir),
+                            "not indexing generated server: {err}"
+                        );
+                        seen.remove(&server_id);
+                    }
+                }
+            }
+        }
+        self.servers.retain(|server_id, _| seen.contains(server_id));
+    }
+
+    /// Returns whether `server_id` has indexed tools.
+    #[must_use]
+    pub fn contains_server(&self, server_id: &str) -> bool {
+        self.servers.contains_key(server_id)
+    }
+
+    /// Returns the number of indexed tools, of `server` only if given.
+    #[must_use]
+    pub fn tool_count(&self, server: Option<&str>) -> usize {
+        self.documents(server).count()
+    }
+
+    /// Returns up to `limit` tools matching `query`, of `server` only if given, most relevant
+    /// first. Ties are broken by server id and then tool name.
+    #[must_use]
+    pub fn search(&self, query: &str, server: Option<&str>, limit: usize) -> Vec<ToolSearchHit> {
+        let query_terms: HashSet<String> = tokenize(query).into_iter().collect();
+        let document_count = self.tool_count(server);
+        if query_terms.is_empty() || document_count == 0 {
+            return Vec::new();
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let (n, average_length) = {
+            let total_length: u64 = self.documents(server).map(|d| u64::from(d.length)).sum();
+            let n = document_count as f64;
+            (n, (total_length as f64 / n).max(1.0))
+        };
+        let idf: Vec<(&str, f64)> = query_terms
+            .iter()
+            .map(|term| {
+                let containing = self
+                    .documents(server)
+                    .filter(|d| d.terms.contains_key(term))
+                    .count();
+                #[allow(clippy::cast_precision_loss)]
+                let containing = containing as f64;
+                let idf = ((n - containing + 0.5) / (containing + 0.5)).ln_1p();
+                (term.as_str(), idf)
+            })
+            .collect();
+
+        let mut hits: Vec<ToolSearchHit> = self
+            .documents(server)
+            .filter_map(|document| {
+                let score = bm25(document, &idf, average_length);
+                (score > 0.0).then(|| ToolSearchHit {
+                    score: (score * 1000.0).round() / 1000.0,
+                    ..document.hit.clone()
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.server_id.cmp(&b.server_id))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    fn documents<'a>(&'a self, server: Option<&'a str>) -> impl Iterator<Item = &'a IndexedTool> {
+        self.servers
+            .iter()
+            .filter(move |(server_id, _)| server.is_none_or(|s| s == server_id.as_str()))
+            .flat_map(|(_, indexed)| &indexed.tools)
+    }
+}
+
+/// Scores `document` against the query terms and their inverse document frequencies.
+fn bm25(document: &IndexedTool, idf: &[(&str, f64)], average_length: f64) -> f64 {
+    let length_norm = K1 * (1.0 - B + B * f64::from(document.length) / average_length);
+    idf.iter()
+        .filter_map(|(term, idf)| {
+            let frequency = f64::from(*document.terms.get(*term)?);
+            Some(idf * frequency * (K1 + 1.0) / (frequency + length_norm))
+        })
+        .sum()
+}
+
+/// Builds the document for one tool of the server in `server_dir`.
+fn index_tool(server_id: &str, server_dir: &Path, tool: &ToolMetadata) -> IndexedTool {
+    let mut terms: HashMap<String, u32> = HashMap::new();
+    let mut add = |text: &str, weight: u32| {
+        for term in tokenize(text) {
+            *terms.entry(term).or_default() += weight;
+        }
+    };
+    add(tool.name.as_str(), NAME_WEIGHT);
+    add(&tool.typescript_name, NAME_WEIGHT);
+    if let Some(category) = &tool.category {
+        add(category, KEYWORD_WEIGHT);
+    }
+    for keyword in &tool.keywords {
+        add(keyword, KEYWORD_WEIGHT);
+    }
+    for text in [&tool.short_description, &tool.description]
+        .into_iter()
+        .flatten()
+    {
+        add(text, TEXT_WEIGHT);
+    }
+    for parameter in &tool.parameters {
+        add(&parameter.name, TEXT_WEIGHT);
+    }
+    let length = terms.values().sum();
+
+    let parameters: Vec<String> = tool
+        .parameters
+        .iter()
+        .map(|p| {
+            let optional = if p.required { "" } else { "?" };
+            format!("{}{optional}: {}", p.name, p.typescript_type)
+        })
+        .collect();
+    let signature = if parameters.is_empty() {
+        format!("{}(params: {{}})", tool.typescript_name)
+    } else {
+        format!(
+            "{}(params: {{ {} }})",
+            tool.typescript_name,
+            parameters.join("; ")
+        )
+    };
+    let short_description = tool
+        .short_description
+        .as_deref()
+        .or(tool.description.as_deref())
+        .unwrap_or_default();
+    let untrusted = |text: &str| sanitize_untrusted_text(text, MAX_UNTRUSTED_FIELD_LEN);
+
+    IndexedTool {
+        hit: ToolSearchHit {
+            server_id: untrusted(server_id),
+            name: untrusted(tool.name.as_str()),
+            path: untrusted(
+                &server_dir
+                    .join(format!("{}.ts", tool.typescript_name))
+                    .display()
+                    .to_string(),
+            ),
+            signature: untrusted(&signature),
+            short_description: untrusted(short_description),
+            category: tool.category.as_deref().map(untrusted),
+            score: 0.0,
+        },
+        terms,
+        length,
+    }
+}
+
+/// Splits `text` into lowercase alphanumeric terms at every other character and at each
+/// lowercase-or-digit to uppercase transition.
+fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in text.chars() {
+        if (!c.is_alphanumeric() || (c.is_uppercase() && previous_lower)) && !current.is_empty() {
+            terms.push(std::mem::take(&mut current));
+        }
+        if c.is_alphanumeric() {
+            current.extend(c.to_lowercase());
+        }
+        previous_lower = c.is_lowercase() || c.is_numeric();
+    }
+    if !current.is_empty() {
+        terms.push(current);
+    }
+    terms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_execution_core::ToolName;
+    use mcp_execution_core::metadata::ParameterMetadata;
+
+    fn tool(name: &str, typescript_name: &str, description: &str) -> ToolMetadata {
+        ToolMetadata {
+            name: ToolName::new(name).unwrap(),
+            typescript_name: typescript_name.to_string(),
+            category: None,
+            keywords: Vec::new(),
+            description: Some(description.to_string()),
+            short_description: None,
+            source_digest: None,
+            parameters: Vec::new(),
+        }
+    }
+
+    fn write_server(base_dir: &Path, server_id: &str, tools: &[ToolMetadata]) {
+        let server_dir = base_dir.join(server_id);
+        std::fs::create_dir_all(&server_dir).unwrap();
+        std::fs::write(
+            server_dir.join(METADATA_FILE_NAME),
+            serde_json::to_string(&serde_json::json!({ "tools": tools })).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_tokenize_splits_case_and_punctuation() {
+        assert_eq!(
+            tokenize("createPullRequest create_issue, v2Beta"),
+            ["create", "pull", "request", "create", "issue", "v2", "beta"]
+        );
+        assert!(tokenize(" -_ ").is_empty());
+    }
+
+    #[test]
+    fn test_search_ranks_name_matches_first() {
+        let servers = tempfile::tempdir().unwrap();
+        let mut create_issue = tool("create_issue", "createIssue", "Creates an issue");
+        create_issue.category = Some("issues".to_string());
+        create_issue.parameters = vec![
+            ParameterMetadata {
+                name: "title".to_string(),
+                typescript_type: "string".to_string(),
+                required: true,
+                description: None,
+            },
+            ParameterMetadata {
+                name: "body".to_string(),
+                typescript_type: "string".to_string(),
+                required: false,
+                description: None,
+            },
+        ];
+        write_server(
+            servers.path(),
+            "github",
+            &[
+                create_issue,
+                tool(
+                    "list_repos",
+                    "listRepos",
+                    "Lists repositories, not an issue",
+                ),
+            ],
+        );
+        write_server(
+            servers.path(),
+            "slack",
+            &[tool("post_message", "postMessage", "Posts a message")],
+        );
+
+        let mut index = ToolIndex::default();
+        index.refresh(servers.path());
+        let hits = index.search("Create issue", None, 10);
+
+        assert_eq!(index.tool_count(None), 3);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].name, "create_issue");
+        assert_eq!(hits[0].category.as_deref(), Some("issues"));
+        assert_eq!(
+            hits[0].signature,
+            "createIssue(params: { title: string; body?: string })"
+        );
+        assert!(hits[0].path.ends_with("createIssue.ts"));
+        assert!(hits[0].score > hits[1].score);
+        assert!(index.search("create issue", Some("slack"), 10).is_empty());
+        assert_eq!(index.search("create issue", None, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_refresh_picks_up_changed_and_removed_sidecars() {
+        let servers = tempfile::tempdir().unwrap();
+        write_server(
+            servers.path(),
+            "github",
+            &[tool("create_issue", "createIssue", "Creates an issue")],
+        );
+        let mut index = ToolIndex::default();
+        index.refresh(servers.path());
+        assert!(index.search("deploy", None, 10).is_empty());
+
+        write_server(
+            servers.path(),
+            "github",
+            &[
+                tool("create_issue", "createIssue", "Creates an issue"),
+                tool("deploy_site", "deploySite", "Deploys the site"),
+            ],
+        );
+        index.refresh(servers.path());
+        assert_eq!(index.search("deploy", None, 10)[0].name, "deploy_site");
+
+        std::fs::remove_dir_all(servers.path().join("github")).unwrap();
+        index.refresh(servers.path());
+        assert!(!index.contains_server("github"));
+        assert_eq!(index.tool_count(None), 0);
+    }
+}
@@ -0,0 +1,379 @@
+//! On-disk copies of pending generation sessions, so they survive a server restart.
+//!
+//! MCP hosts restart their servers freely, and a restart between `introspect_server` and
+//! `save_categorized_tools` used to lose the session. With a [`SessionStore`] attached (see
+//! [`crate::StateManager::with_session_store`]), every stored session is also written to
+//! `<dir>/<session_id>.json` and read back when the next server process starts.
+//!
+//! - **Secrets**: the file holds [`ServerConfig::redacted`], never the config used to connect.
+//!   The redacted copy fingerprints identically, which is all `save_categorized_tools` needs it
+//!   for.
+//! - **Atomicity**: a file is written to a dot-prefixed temporary name and renamed into place,
+//!   so a crash never leaves a half-written session behind.
+//! - **Single use**: consuming a session deletes its file before the session is handed out.
+//!   Only one process can delete a given file, so two servers sharing a directory cannot both
+//!   consume the same session.
+//! - **Confinement**: only regular files named `<uuid>.json` directly inside the directory are
+//!   read. Files are opened without following symlinks and are owner-only on Unix.
+//! - **Expiry**: expired, unreadable, or invalid files are deleted when the store is loaded.
+//!   Sessions that expire while the server runs are deleted on the next load.
+
+use crate::clock::Clock;
+use crate::output_dir::relative_subpath;
+use crate::types::PendingGeneration;
+use chrono::{DateTime, Utc};
+use mcp_execution_codegen::progressive::ToolCategorization;
+use mcp_execution_core::{ServerConfig, ServerId, sanitize_path_for_error};
+use mcp_execution_introspector::ServerInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Schema version written to every session file. A file with any other version is discarded.
+const SESSION_FILE_VERSION: u32 = 1;
+
+/// Upper bound on the size of a session file [`SessionStore::load`] reads.
+///
+/// Matches the largest session [`crate::StateManager`] would accept, with room for the JSON
+/// framing around `server_info`.
+const MAX_SESSION_FILE_SIZE: u64 = crate::state::MAX_TOTAL_PENDING_BYTES as u64;
+
+/// A session as written to disk.
+#[derive(Serialize, Deserialize)]
+struct SessionFile {
+    version: u32,
+    server_id: ServerId,
+    server_info: ServerInfo,
+    /// Always [`ServerConfig::redacted`].
+    #[serde(deserialize_with = "ServerConfig::deserialize_redacted")]
+    config: ServerConfig,
+    output_dir_override: Option<PathBuf>,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    /// Absent from files written before categorizations were carried over.
+    #[serde(default)]
+    previous_categorizations: HashMap<String, ToolCategorization>,
+}
+
+/// A directory of persisted pending generation sessions.
+///
+/// See the [module documentation](self) for the guarantees it provides.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::session_store::SessionStore;
+/// use mcp_execution_server::state::StateManager;
+///
+/// let dir = tempfile::TempDir::new().unwrap();
+/// let store = SessionStore::open(dir.path().join("sessions")).unwrap();
+/// let state = StateManager::new().with_session_store(store);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SessionStore {
+    dir: PathBuf,
+}
+
+impl SessionStore {
+    /// Opens the store at `dir`, creating it (owner-only on Unix) if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if `dir` cannot be created or is not a directory.
+    pub fn open(dir: PathBuf) -> io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(&dir)?;
+        }
+        #[cfg(not(unix))]
+        std::fs::create_dir_all(&dir)?;
+
+        if !std::fs::symlink_metadata(&dir)?.is_dir() {
+            return Err(io::Error::other(format!(
+                "session directory {} is not a directory",
+                sanitize_path_for_error(&dir)
+            )));
+        }
+        Ok(Self { dir })
+    }
+
+    /// Returns the directory sessions are stored in.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn session_path(&self, session_id: Uuid) -> PathBuf {
+        self.dir.join(format!("{session_id}.json"))
+    }
+
+    /// Serializes `generation` with its config redacted.
+    pub(crate) fn encode(generation: &PendingGeneration) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(&SessionFile {
+            version: SESSION_FILE_VERSION,
+            server_id: generation.server_id.clone(),
+            server_info: generation.server_info.clone(),
+            config: generation.config.redacted(),
+            output_dir_override: generation.output_dir_override.clone(),
+            created_at: generation.created_at,
+            expires_at: generation.expires_at,
+            previous_categorizations: generation.previous_categorizations.clone(),
+        })
+    }
+
+    /// Atomically writes an already-[`encode`](Self::encode)d session under `session_id`.
+    pub(crate) async fn write(&self, session_id: Uuid, contents: &[u8]) -> io::Result<()> {
+        let temp = self.dir.join(format!(".{session_id}.json.tmp"));
+        mcp_execution_core::write_confined_file(&temp, contents)
+            .await
+            .map_err(|err| match err {
+                mcp_execution_core::ConfinementError::Io(err) => err,
+                other => io::Error::other(other),
+            })?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio::fs::set_permissions(&temp, std::fs::Permissions::from_mode(0o600)).await?;
+        }
+        tokio::fs::rename(&temp, self.session_path(session_id)).await
+    }
+
+    /// Deletes the file for `session_id`, returning `false` if there was none.
+    ///
+    /// This is the claim step of single use: of several callers removing the same file, only
+    /// one sees `true`.
+    pub(crate) async fn remove(&self, session_id: Uuid) -> io::Result<bool> {
+        match tokio::fs::remove_file(self.session_path(session_id)).await {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Reads every live session, deleting expired, invalid, and leftover temporary files.
+    ///
+    /// A file that cannot be deleted is logged and skipped. Failing to list the directory at
+    /// all is logged and yields no sessions.
+    pub(crate) fn load(&self, clock: &dyn Clock) -> Vec<(Uuid, PendingGeneration)> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                tracing::warn!(
+                    dir = %sanitize_path_for_error(&self.dir),
+                    "failed to list persisted sessions: {err}"
+                );
+                return Vec::new();
+            }
+        };
+
+        let mut sessions = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+            if name.starts_with('.') && name.ends_with(".json.tmp") {
+                discard(&path, "leftover temporary file");
+                continue;
+            }
+            let Some(session_id) = name
+                .strip_suffix(".json")
+                .and_then(|stem| Uuid::parse_str(stem).ok())
+            else {
+                continue;
+            };
+
+            match read_session(&path) {
+                Ok(generation) if generation.is_expired(clock) => discard(&path, "expired"),
+                Ok(generation) => sessions.push((session_id, generation)),
+                Err(reason) => discard(&path, &reason),
+            }
+        }
+        sessions
+    }
+}
+
+/// Reads and validates one session file, or says why it is unusable.
+fn read_session(path: &Path) -> Result<PendingGeneration, String> {
+    let metadata = std::fs::symlink_metadata(path).map_err(|err| err.to_string())?;
+    if !metadata.is_file() {
+        return Err("not a regular file".to_string());
+    }
+    if metadata.len() > MAX_SESSION_FILE_SIZE {
+        return Err(format!(
+            "{} bytes exceeds the {MAX_SESSION_FILE_SIZE} byte limit",
+            metadata.len()
+        ));
+    }
+    let contents = std::fs::read(path).map_err(|err| err.to_string())?;
+    let file: SessionFile = serde_json::from_slice(&contents).map_err(|err| err.to_string())?;
+    if file.version != SESSION_FILE_VERSION {
+        return Err(format!("unsupported version {}", file.version));
+    }
+    relative_subpath(file.output_dir_override.as_deref())
+        .map_err(|err| format!("invalid output_dir: {err}"))?;
+
+    Ok(PendingGeneration {
+        server_id: file.server_id,
+        server_info: file.server_info,
+        config: file.config,
+        output_dir_override: file.output_dir_override,
+        created_at: file.created_at,
+        expires_at: file.expires_at,
+        previous_categorizations: file.previous_categorizations,
+    })
+}
+
+/// Deletes a session file [`SessionStore::load`] will not use.
+fn discard(path: &Path, reason: &str) {
+    let path_display = sanitize_path_for_error(path);
+    match std::fs::remove_file(path) {
+        Ok(()) => tracing::debug!(path = %path_display, "discarded persisted session: {reason}"),
+        Err(err) => tracing::warn!(
+            path = %path_display,
+            "failed to discard persisted session ({reason}): {err}"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::TestClock;
+    use mcp_execution_introspector::ServerCapabilities;
+    use tempfile::TempDir;
+
+    fn pending(clock: &dyn Clock) -> PendingGeneration {
+        let server_id = ServerId::new("test").unwrap();
+        let server_info = ServerInfo {
+            id: server_id.clone(),
+            name: "Test Server".to_string(),
+            version: "1.0.0".to_string(),
+            capabilities: ServerCapabilities {
+                supports_tools: true,
+                supports_resources: false,
+                supports_prompts: false,
+            },
+            tools: vec![],
+        };
+        let config = ServerConfig::builder()
+            .command("echo".to_string())
+            .arg("--token=sk-secret".to_string())
+            .env("TOKEN".to_string(), "sk-secret".to_string())
+            .build()
+            .unwrap();
+        PendingGeneration::new(server_id, server_info, config, None, clock)
+    }
+
+    #[tokio::test]
+    async fn write_then_load_round_trips_without_secrets() {
+        let dir = TempDir::new().unwrap();
+        let store = SessionStore::open(dir.path().join("sessions")).unwrap();
+        let clock = TestClock::new(Utc::now());
+        let session_id = Uuid::new_v4();
+        let generation = pending(&clock).with_previous_categorizations(HashMap::from([(
+            "tool".to_string(),
+            ToolCategorization {
+                category: "kept".to_string(),
+                keywords: Vec::new(),
+                short_description: "Kept".to_string(),
+            },
+        )]));
+
+        let contents = SessionStore::encode(&generation).unwrap();
+        store.write(session_id, &contents).await.unwrap();
+
+        let on_disk = std::fs::read_to_string(store.session_path(session_id)).unwrap();
+        assert!(!on_disk.contains("sk-secret"));
+
+        let loaded = store.load(&clock);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].0, session_id);
+        assert_eq!(loaded[0].1.expires_at, generation.expires_at);
+        assert_eq!(loaded[0].1.config.args().len(), 1);
+        assert_eq!(
+            loaded[0].1.previous_categorizations["tool"].category,
+            "kept"
+        );
+    }
+
+    #[tokio::test]
+    async fn load_keeps_a_config_with_interpolated_command() {
+        use mcp_execution_core::provenance::ConfigFingerprint;
+
+        let dir = TempDir::new().unwrap();
+        let store = SessionStore::open(dir.path().to_path_buf()).unwrap();
+        let clock = TestClock::new(Utc::now());
+        let home = TempDir::new().unwrap();
+        let bin = home.path().join(".local/bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join("srv"), "").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(bin.join("srv"), std::fs::Permissions::from_mode(0o755))
+                .unwrap();
+        }
+        let mut generation = pending(&clock);
+        generation.config = ServerConfig::builder()
+            .command("${HOME}/.local/bin/srv".to_string())
+            .build_interpolated(|_| Some(home.path().display().to_string()))
+            .unwrap();
+        let session_id = Uuid::new_v4();
+
+        let contents = SessionStore::encode(&generation).unwrap();
+        store.write(session_id, &contents).await.unwrap();
+
+        let loaded = store.load(&clock);
+        assert_eq!(loaded.len(), 1);
+        assert!(store.session_path(session_id).exists());
+        assert_eq!(
+            ConfigFingerprint::compute(&loaded[0].1.config),
+            ConfigFingerprint::compute(&generation.config)
+        );
+    }
+
+    #[tokio::test]
+    async fn load_sweeps_expired_invalid_and_temporary_files() {
+        let dir = TempDir::new().unwrap();
+        let store = SessionStore::open(dir.path().to_path_buf()).unwrap();
+        let clock = TestClock::new(Utc::now());
+
+        let expired = Uuid::new_v4();
+        let contents = SessionStore::encode(&pending(&clock)).unwrap();
+        store.write(expired, &contents).await.unwrap();
+        let garbage = store.session_path(Uuid::new_v4());
+        std::fs::write(&garbage, "{").unwrap();
+        let temp = dir.path().join(format!(".{}.json.tmp", Uuid::new_v4()));
+        std::fs::write(&temp, "{").unwrap();
+        let unrelated = dir.path().join("notes.txt");
+        std::fs::write(&unrelated, "keep").unwrap();
+
+        clock.advance(chrono::Duration::minutes(
+            PendingGeneration::DEFAULT_TIMEOUT_MINUTES + 1,
+        ));
+        assert!(store.load(&clock).is_empty());
+        assert!(!store.session_path(expired).exists());
+        assert!(!garbage.exists());
+        assert!(!temp.exists());
+        assert!(unrelated.exists());
+    }
+
+    #[tokio::test]
+    async fn remove_claims_exactly_once() {
+        let dir = TempDir::new().unwrap();
+        let store = SessionStore::open(dir.path().to_path_buf()).unwrap();
+        let session_id = Uuid::new_v4();
+        store.write(session_id, b"{}").await.unwrap();
+
+        assert!(store.remove(session_id).await.unwrap());
+        assert!(!store.remove(session_id).await.unwrap());
+    }
+}
@@ -2,10 +2,13 @@
 //!
 //! The `StateManager` stores temporary session data between `introspect_server`
 //! and `save_categorized_tools` calls. Sessions expire after 30 minutes and
-//! are cleaned up lazily on each operation.
+//! are cleaned up lazily on each operation. With a [`SessionStore`] attached they
+//! are also kept on disk, so they survive a server restart.
 
 use crate::clock::{Clock, SystemClock};
+use crate::session_store::SessionStore;
 use crate::types::PendingGeneration;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
@@ -69,8 +72,8 @@ pub enum StateError {
     },
 }
 
-/// Estimates a [`PendingGeneration`]'s in-memory footprint from its serialized
-/// [`ServerInfo`](mcp_execution_introspector::ServerInfo) size.
+/// Estimates a [`PendingGeneration`]'s in-memory footprint from the serialized size of its
+/// [`ServerInfo`](mcp_execution_introspector::ServerInfo) and carried-over categorizations.
 ///
 /// This is an approximation (it ignores `config`/`output_dir_override`, both small relative to
 /// `server_info`'s tool list), used only to enforce [`MAX_TOTAL_PENDING_BYTES`]. A serialization
@@ -86,7 +89,10 @@ pub enum StateError {
 /// recursion limit while parsing a `tools/list` response, so nothing reaching this function was
 /// ever deep enough to threaten a recursive serialize.
 fn estimate_size_bytes(generation: &PendingGeneration) -> usize {
-    serde_json::to_vec(&generation.server_info).map_or(usize::MAX, |bytes| bytes.len())
+    let size_of = |value: serde_json::Result<Vec<u8>>| value.map_or(usize::MAX, |b| b.len());
+    size_of(serde_json::to_vec(&generation.server_info)).saturating_add(size_of(
+        serde_json::to_vec(&generation.previous_categorizations),
+    ))
 }
 
 /// The pending-session table itself, tracking a running approximate byte total alongside the
@@ -104,6 +110,8 @@ struct PendingTable {
 struct PendingEntry {
     generation: PendingGeneration,
     size_bytes: usize,
+    /// Whether the session has a file in the [`SessionStore`] that consuming it must claim.
+    persisted: bool,
 }
 
 impl PendingTable {
@@ -193,6 +201,7 @@ impl PendingTable {
 pub struct StateManager {
     pending: Arc<RwLock<PendingTable>>,
     clock: Arc<dyn Clock>,
+    store: Option<SessionStore>,
 }
 
 impl Default for StateManager {
@@ -217,6 +226,105 @@ impl StateManager {
         Self {
             pending: Arc::new(RwLock::new(PendingTable::default())),
             clock,
+            store: None,
+        }
+    }
+
+    /// Persists sessions to `store`, loading the sessions a previous process left there.
+    ///
+    /// Expired and invalid files are deleted while loading (see [`SessionStore`]). Loaded
+    /// sessions count against [`MAX_PENDING_SESSIONS`] and [`MAX_TOTAL_PENDING_BYTES`] like
+    /// fresh ones; any that do not fit are deleted too.
+    ///
+    /// A loaded session's `config` is the redacted copy [`SessionStore`] wrote, not the config
+    /// originally introspected.
+    ///
+    /// # Panics
+    ///
+    /// Never in practice: `StateManager` is not `Clone`, so a manager being built by value holds
+    /// the only handle to its table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_server::session_store::SessionStore;
+    /// use mcp_execution_server::state::StateManager;
+    ///
+    /// # async fn example() {
+    /// let dir = tempfile::TempDir::new().unwrap();
+    /// let store = SessionStore::open(dir.path().to_path_buf()).unwrap();
+    /// let state = StateManager::new().with_session_store(store);
+    /// assert_eq!(state.pending_count().await, 0);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_session_store(mut self, store: SessionStore) -> Self {
+        {
+            let table = Arc::get_mut(&mut self.pending)
+                .expect("a StateManager being built has no other handles to its table")
+                .get_mut();
+            for (session_id, generation) in store.load(self.clock.as_ref()) {
+                let size_bytes = estimate_size_bytes(&generation);
+                if let Err(err) = table.check_capacity(size_bytes) {
+                    tracing::warn!(%session_id, "dropping persisted session: {err}");
+                    let path = store.dir().join(format!("{session_id}.json"));
+                    let _ = std::fs::remove_file(path);
+                    continue;
+                }
+                table.entries.insert(
+                    session_id,
+                    PendingEntry {
+                        generation,
+                        size_bytes,
+                        persisted: true,
+                    },
+                );
+                table.total_bytes += size_bytes;
+            }
+        }
+        self.store = Some(store);
+        self
+    }
+
+    /// Writes `generation` to the session store, if there is one.
+    ///
+    /// Returns whether the session is now persisted. A failure is logged rather than returned:
+    /// the session still works for as long as this process runs.
+    async fn persist(&self, session_id: Uuid, generation: &PendingGeneration) -> bool {
+        let Some(store) = &self.store else {
+            return false;
+        };
+        let written = match SessionStore::encode(generation) {
+            Ok(contents) => store
+                .write(session_id, &contents)
+                .await
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+        match written {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::warn!(%session_id, "failed to persist session, keeping it in memory only: {err}");
+                false
+            }
+        }
+    }
+
+    /// Claims a persisted session's file before the session is handed out.
+    ///
+    /// Returns `false` if the session must not be handed out: another process sharing the
+    /// store already consumed it, or the file could not be deleted (so a restart could hand it
+    /// out again). Either way the caller drops the session from memory.
+    async fn claim(&self, session_id: Uuid, entry: &PendingEntry) -> bool {
+        let Some(store) = self.store.as_ref().filter(|_| entry.persisted) else {
+            return true;
+        };
+        match store.remove(session_id).await {
+            Ok(claimed) => claimed,
+            Err(err) => {
+                tracing::warn!(%session_id, "failed to claim persisted session: {err}");
+                false
+            }
         }
     }
 
@@ -249,27 +357,43 @@ impl StateManager {
         let session_id = Uuid::new_v4();
         let size_bytes = estimate_size_bytes(&generation);
 
-        {
-            let mut table = self.pending.write().await;
-            table.sweep_expired(self.clock.as_ref());
-            table.check_capacity(size_bytes)?;
+        // Written before the session is visible, so a `take` can never race ahead of its file.
+        let persisted = self.persist(session_id, &generation).await;
 
-            table.entries.insert(
-                session_id,
-                PendingEntry {
-                    generation,
-                    size_bytes,
-                },
-            );
-            table.total_bytes += size_bytes;
+        let mut table = self.pending.write().await;
+        table.sweep_expired(self.clock.as_ref());
+        if let Err(err) = table.check_capacity(size_bytes) {
+            drop(table);
+            self.discard_file(session_id, persisted).await;
+            return Err(err);
         }
+        table.entries.insert(
+            session_id,
+            PendingEntry {
+                generation,
+                size_bytes,
+                persisted,
+            },
+        );
+        table.total_bytes += size_bytes;
+        drop(table);
 
         Ok(session_id)
     }
 
+    /// Deletes the file of a session that never made it into the table.
+    async fn discard_file(&self, session_id: Uuid, persisted: bool) {
+        if let Some(store) = self.store.as_ref().filter(|_| persisted)
+            && let Err(err) = store.remove(session_id).await
+        {
+            tracing::warn!(%session_id, "failed to delete persisted session: {err}");
+        }
+    }
+
     /// Retrieves and removes a pending generation.
     ///
-    /// Returns `None` if the session is not found or has expired.
+    /// Returns `None` if the session is not found or has expired, or if it is persisted and
+    /// another process sharing the [`SessionStore`] already consumed it.
     /// This operation also performs lazy cleanup of expired sessions.
     ///
     /// # Examples
@@ -297,13 +421,16 @@ impl StateManager {
         let mut table = self.pending.write().await;
         table.sweep_expired(self.clock.as_ref());
 
+        let claimed = self
+            .claim(session_id, table.entries.get(&session_id)?)
+            .await;
         let entry = table.entries.remove(&session_id)?;
         table.total_bytes -= entry.size_bytes;
         drop(table);
         let generation = entry.generation;
 
         // Verify not expired (lock already released)
-        if generation.is_expired(self.clock.as_ref()) {
+        if !claimed || generation.is_expired(self.clock.as_ref()) {
             return None;
         }
 
@@ -419,6 +546,11 @@ impl StateManager {
 
         let outcome = match validate(&entry.generation) {
             Ok(value) => {
+                if !self.claim(session_id, entry).await {
+                    let entry = table.entries.remove(&session_id)?;
+                    table.total_bytes -= entry.size_bytes;
+                    return None;
+                }
                 let entry = table
                     .entries
                     .remove(&session_id)
@@ -491,15 +623,21 @@ impl StateManager {
         generation: PendingGeneration,
         size_bytes: usize,
     ) -> Result<(), StateError> {
+        let persisted = self.persist(session_id, &generation).await;
+
         let mut table = self.pending.write().await;
         table.sweep_expired(self.clock.as_ref());
-        table.check_capacity(size_bytes)?;
-
+        if let Err(err) = table.check_capacity(size_bytes) {
+            drop(table);
+            self.discard_file(session_id, persisted).await;
+            return Err(err);
+        }
         if let Some(previous) = table.entries.insert(
             session_id,
             PendingEntry {
                 generation,
                 size_bytes,
+                persisted,
             },
         ) {
             table.total_bytes -= previous.size_bytes;
@@ -510,6 +648,82 @@ impl StateManager {
         Ok(())
     }
 
+    /// Maps every unexpired session through `summarize`, oldest first.
+    ///
+    /// `summarize` runs while the table's read lock is held, so like [`Self::take_if`]'s
+    /// `validate` it should only pick fields out of its arguments. A session checked out by an
+    /// in-flight [`Self::take_if`] caller is not listed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_server::state::StateManager;
+    /// # use mcp_execution_server::types::PendingGeneration;
+    ///
+    /// # async fn example(pending: PendingGeneration) {
+    /// let state = StateManager::new();
+    /// let session_id = state.store(pending).await.unwrap();
+    ///
+    /// let ids = state.list(|session_id, _generation| session_id).await;
+    /// assert_eq!(ids, vec![session_id]);
+    /// # }
+    /// ```
+    pub async fn list<T>(&self, summarize: impl Fn(Uuid, &PendingGeneration) -> T) -> Vec<T> {
+        let table = self.pending.read().await;
+        let clock = self.clock.as_ref();
+        let mut sessions: Vec<_> = table
+            .entries
+            .iter()
+            .filter(|(_, entry)| !entry.generation.is_expired(clock))
+            .collect();
+        sessions.sort_by_key(|(session_id, entry)| (entry.generation.created_at, **session_id));
+        let summaries = sessions
+            .into_iter()
+            .map(|(session_id, entry)| summarize(*session_id, &entry.generation))
+            .collect();
+        drop(table);
+        summaries
+    }
+
+    /// Pushes an unexpired session's expiry out to
+    /// [`PendingGeneration::refreshed_expiry`], returning the new expiry.
+    ///
+    /// Returns `None` if the session is not found or has expired. A persisted session's file is
+    /// rewritten with the new expiry; if that fails, the old file (and expiry) is what a restart
+    /// sees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_server::state::StateManager;
+    /// # use mcp_execution_server::types::PendingGeneration;
+    ///
+    /// # async fn example(pending: PendingGeneration) {
+    /// let state = StateManager::new();
+    /// let session_id = state.store(pending).await.unwrap();
+    ///
+    /// let expires_at = state.extend(session_id).await.unwrap();
+    /// assert_eq!(state.get(session_id).await.unwrap().expires_at, expires_at);
+    /// # }
+    /// ```
+    pub async fn extend(&self, session_id: Uuid) -> Option<DateTime<Utc>> {
+        let mut table = self.pending.write().await;
+        let clock = self.clock.as_ref();
+        let entry = table
+            .entries
+            .get_mut(&session_id)
+            .filter(|entry| !entry.generation.is_expired(clock))?;
+        entry.generation.expires_at = entry.generation.refreshed_expiry(clock);
+        let expires_at = entry.generation.expires_at;
+        // Rewritten under the lock so a concurrent `take` cannot claim the old file and then
+        // have this write bring the session back.
+        if entry.persisted {
+            self.persist(session_id, &entry.generation).await;
+        }
+        drop(table);
+        Some(expires_at)
+    }
+
     /// Returns the current pending session count (excluding expired).
     ///
     /// # Examples
@@ -775,6 +989,7 @@ mod tests {
                 PendingEntry {
                     generation,
                     size_bytes,
+                    persisted: false,
                 },
             );
             table.total_bytes += size_bytes;
@@ -1139,4 +1354,137 @@ mod tests {
             assert!(state.get(session_id).await.is_none());
         }
     }
+
+    fn persistent_state(dir: &std::path::Path) -> StateManager {
+        StateManager::new().with_session_store(SessionStore::open(dir.to_path_buf()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_session_store_survives_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let session_id = persistent_state(dir.path())
+            .store(create_test_pending())
+            .await
+            .unwrap();
+
+        let restarted = persistent_state(dir.path());
+        assert_eq!(restarted.pending_count().await, 1);
+        let generation = restarted.take(session_id).await.unwrap();
+        assert_eq!(generation.server_id.as_str(), "test");
+        assert_eq!(generation.server_info.tools.len(), 1);
+
+        // Consuming the session deleted its file, so a further restart does not bring it back.
+        assert_eq!(persistent_state(dir.path()).pending_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_session_store_take_if_is_single_use_across_processes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let first = persistent_state(dir.path());
+        let session_id = first.store(create_test_pending()).await.unwrap();
+        let second = persistent_state(dir.path());
+
+        let taken = first
+            .take_if(session_id, |_generation| Ok::<_, &str>(()))
+            .await;
+        assert!(matches!(taken, Some(Ok(_))));
+        let raced = second
+            .take_if(session_id, |_generation| Ok::<_, &str>(()))
+            .await;
+        assert!(
+            raced.is_none(),
+            "a consumed session must not be handed out twice"
+        );
+        assert!(second.get(session_id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_store_restore_persists_again() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let state = persistent_state(dir.path());
+        let session_id = state.store(create_test_pending()).await.unwrap();
+
+        let (generation, size_bytes, ()) = state
+            .take_if(session_id, |_generation| Ok::<_, &str>(()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(persistent_state(dir.path()).pending_count().await, 0);
+
+        state
+            .restore(session_id, generation, size_bytes)
+            .await
+            .unwrap();
+        assert!(
+            persistent_state(dir.path())
+                .take(session_id)
+                .await
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_store_drops_expired_sessions_on_load() {
+        let dir = tempfile::TempDir::new().unwrap();
+        persistent_state(dir.path())
+            .store(create_expired_pending())
+            .await
+            .unwrap();
+
+        assert_eq!(persistent_state(dir.path()).pending_count().await, 0);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_skips_expired_and_orders_oldest_first() {
+        let start = chrono::Utc::now();
+        let clock = Arc::new(TestClock::new(start));
+        let state = StateManager::with_clock(Arc::clone(&clock) as Arc<dyn Clock>);
+        let first = state
+            .store(create_test_pending_with_clock(clock.as_ref()))
+            .await
+            .unwrap();
+        clock.advance(chrono::Duration::minutes(1));
+        let second = state
+            .store(create_test_pending_with_clock(clock.as_ref()))
+            .await
+            .unwrap();
+        state.store(create_expired_pending()).await.unwrap();
+
+        let ids = state.list(|session_id, _generation| session_id).await;
+        assert_eq!(ids, vec![first, second]);
+    }
+
+    #[tokio::test]
+    async fn test_extend_refreshes_expiry_and_rewrites_persisted_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let start = chrono::Utc::now();
+        let clock = Arc::new(TestClock::new(start));
+        let state = StateManager::with_clock(Arc::clone(&clock) as Arc<dyn Clock>)
+            .with_session_store(SessionStore::open(dir.path().to_path_buf()).unwrap());
+        let session_id = state
+            .store(create_test_pending_with_clock(clock.as_ref()))
+            .await
+            .unwrap();
+
+        clock.advance(chrono::Duration::minutes(10));
+        let expires_at = state.extend(session_id).await.unwrap();
+        assert_eq!(
+            expires_at,
+            start + chrono::Duration::minutes(10 + PendingGeneration::DEFAULT_TIMEOUT_MINUTES)
+        );
+        let reloaded = StateManager::with_clock(Arc::clone(&clock) as Arc<dyn Clock>)
+            .with_session_store(SessionStore::open(dir.path().to_path_buf()).unwrap());
+        assert_eq!(
+            reloaded.get(session_id).await.unwrap().expires_at,
+            expires_at
+        );
+
+        assert!(state.extend(Uuid::new_v4()).await.is_none());
+        clock.advance(chrono::Duration::hours(1));
+        assert!(
+            state.extend(session_id).await.is_none(),
+            "an expired session cannot be revived"
+        );
+    }
 }
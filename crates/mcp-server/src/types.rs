@@ -4,9 +4,13 @@
 //! - `introspect_server`: Connect to and introspect an MCP server
 //! - `save_categorized_tools`: Generate TypeScript files with categorization
 //! - `list_generated_servers`: List all servers with generated files
+//!
+//! and for the session tools `get_session_tools`, `list_sessions`, `describe_session`, and
+//! `discard_session`.
 
 use crate::clock::Clock;
 use chrono::{DateTime, Utc};
+use mcp_execution_codegen::progressive::ToolCategorization;
 use mcp_execution_core::{ServerConfig, ServerId};
 use mcp_execution_introspector::ServerInfo;
 use schemars::JsonSchema;
@@ -21,16 +25,18 @@ use uuid::Uuid;
 
 /// Parameters for introspecting an MCP server.
 ///
-/// This type only ever builds a stdio [`ServerConfig`] (see
-/// `GeneratorService::introspect_server`, which calls `ServerConfig::builder().command(...)`
-/// and never sets a transport of `Http` or `Sse`). It must never gain a field capable of
-/// setting `ServerConfig`'s transport to `Http`/`Sse` (e.g. `url`, `http`, `sse`, `headers`)
-/// without SSRF allowlisting logic added alongside it: `ServerConfig::url`
-/// (`crates/mcp-core/src/server_config.rs`) documents that this crate does not apply SSRF
-/// allowlisting itself and expects a server-context embedder - which is exactly what
-/// `mcp-execution-server` is - to add its own before connecting. `tests::introspect_server_params_shape_is_pinned`
-/// pins the current field set so a silent addition fails to compile instead of merely
-/// widening the attack surface unnoticed.
+/// The server is named in exactly one of two ways: `command` (with `args` and `env`) spells out
+/// a stdio server, or `from_config` names an entry of the user's MCP config, which the server
+/// resolves itself so secrets in its `env` or `headers` never pass through the tool call.
+///
+/// Only the `from_config` path can reach an `Http`/`Sse` [`ServerConfig`], and only for a URL
+/// the user wrote into their own config. The params themselves must never gain a field capable
+/// of setting a URL (e.g. `url`, `http`, `sse`, `headers`) without SSRF allowlisting logic
+/// added alongside it: `ServerConfig::url` (`crates/mcp-core/src/server_config.rs`) documents
+/// that this crate does not apply SSRF allowlisting itself and expects a server-context
+/// embedder - which is exactly what `mcp-execution-server` is - to add its own before
+/// connecting. `tests::introspect_server_params_shape_is_pinned` pins the current field set so
+/// a silent addition fails to compile instead of merely widening the attack surface unnoticed.
 ///
 /// # Examples
 ///
@@ -39,13 +45,30 @@ use uuid::Uuid;
 /// use std::collections::HashMap;
 ///
 /// let params = IntrospectServerParams {
-///     server_id: "github".to_string(),
-///     command: "npx".to_string(),
+///     server_id: Some("github".to_string()),
+///     command: Some("npx".to_string()),
 ///     args: vec!["-y".to_string(), "@anthropic/mcp-server-github".to_string()],
 ///     env: HashMap::new(),
+///     from_config: None,
+///     output_dir: None,
+///     connect_timeout_secs: None,
+///     discover_timeout_secs: None,
+///     page_size: None,
+///     max_description_len: None,
+/// };
+///
+/// // The same server, taken from the user's MCP config; `server_id` defaults to "github".
+/// let params = IntrospectServerParams {
+///     server_id: None,
+///     command: None,
+///     args: Vec::new(),
+///     env: HashMap::new(),
+///     from_config: Some("github".to_string()),
 ///     output_dir: None,
 ///     connect_timeout_secs: None,
 ///     discover_timeout_secs: None,
+///     page_size: None,
+///     max_description_len: None,
 /// };
 /// ```
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
@@ -54,16 +77,20 @@ pub struct IntrospectServerParams {
     ///
     /// Must be 1-64 lowercase letters, digits, or hyphens (see `validate_server_id_slug`'s
     /// `MAX_SERVER_ID_LENGTH`, mirrored here as a literal since schemars attributes cannot
-    /// reference a `const`).
+    /// reference a `const`). Required with `command`; defaults to the entry name with
+    /// `from_config`.
+    #[serde(default)]
     #[schemars(length(max = 64), regex(pattern = r"^[a-z0-9-]+$"))]
-    pub server_id: String,
+    pub server_id: Option<String>,
 
-    /// Command to start the server (e.g., "npx", "docker").
+    /// Command to start the server (e.g., "npx", "docker"). Mutually exclusive with
+    /// `from_config`.
     ///
     /// Capped at `mcp_execution_core::MAX_ARG_LEN` (4096 bytes) at runtime; mirrored here as a
     /// literal since schemars attributes cannot reference a `const`.
+    #[serde(default)]
     #[schemars(length(max = 4096))]
-    pub command: String,
+    pub command: Option<String>,
 
     /// Arguments to pass to the command.
     ///
@@ -84,6 +111,12 @@ pub struct IntrospectServerParams {
     #[serde(default)]
     pub env: HashMap<String, String>,
 
+    /// Name of an entry in the user's MCP config (`mcpServers`) to introspect instead of
+    /// `command`. The entry supplies the transport, command or URL, environment, headers, and
+    /// timeouts, so `args`, `env`, and the timeout overrides must not be set alongside it.
+    #[serde(default)]
+    pub from_config: Option<String>,
+
     /// Custom output subdirectory, relative to `~/.claude/servers/{server_id}/`
     /// (default: `~/.claude/servers/{server_id}` itself). Confined to that
     /// directory: an absolute path, a `..` component, or a path that escapes it
@@ -99,6 +132,20 @@ pub struct IntrospectServerParams {
     /// when set.
     #[serde(default)]
     pub discover_timeout_secs: Option<u64>,
+
+    /// Return at most this many tools, plus a `next_cursor` for `get_session_tools` to fetch
+    /// the rest (default: every tool at once).
+    ///
+    /// Capped at `MAX_TOOL_PAGE_SIZE` (500) at runtime; mirrored here as a literal since
+    /// schemars attributes cannot reference a `const`.
+    #[serde(default)]
+    #[schemars(range(min = 1, max = 500))]
+    pub page_size: Option<usize>,
+
+    /// Truncate each tool description to at most this many characters, marking a cut one with
+    /// a trailing `…` (default: the 500-character cap every description already has).
+    #[serde(default)]
+    pub max_description_len: Option<usize>,
 }
 
 /// Result from introspecting an MCP server.
@@ -121,7 +168,10 @@ pub struct IntrospectServerParams {
 ///         name: "create_issue".to_string(),
 ///         description: "Create a new issue".to_string(),
 ///         parameters: vec!["title".to_string(), "body".to_string()],
+///         carried_over_category: None,
 ///     }],
+///     tools_carried_over: 0,
+///     next_cursor: None,
 ///     session_id: Uuid::new_v4(),
 ///     expires_at: Utc::now(),
 /// };
@@ -139,9 +189,16 @@ pub struct IntrospectServerResult {
     /// Number of tools discovered
     pub tools_found: usize,
 
-    /// List of tools for categorization
+    /// List of tools for categorization: the first `page_size` of them when that was set
     pub tools: Vec<IntrospectedToolSummary>,
 
+    /// Number of tools whose categorization from the previous generation still applies
+    pub tools_carried_over: usize,
+
+    /// Cursor for `get_session_tools` to fetch the next page, absent on the last one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+
     /// Session ID for `save_categorized_tools` call
     pub session_id: Uuid,
 
@@ -163,6 +220,7 @@ pub struct IntrospectServerResult {
 ///     name: "create_issue".to_string(),
 ///     description: "Create a new issue".to_string(),
 ///     parameters: vec!["title".to_string(), "body".to_string()],
+///     carried_over_category: None,
 /// };
 ///
 /// assert_eq!(summary.parameters.len(), 2);
@@ -177,6 +235,11 @@ pub struct IntrospectedToolSummary {
 
     /// Parameter names for context
     pub parameters: Vec<String>,
+
+    /// Category the previous generation gave this unchanged tool. `save_categorized_tools`
+    /// keeps its categorization unless an entry in `categorized_tools` overrides it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carried_over_category: Option<String>,
 }
 
 // ============================================================================
@@ -185,10 +248,17 @@ pub struct IntrospectedToolSummary {
 
 /// Parameters for saving categorized tools.
 ///
+/// Tools are categorized by explicit `categorized_tools` entries, then by the categorization
+/// carried over from the previous generation (see
+/// [`PendingGeneration::previous_categorizations`]), then by the first of `rules` whose pattern
+/// matches, so a few rules can stand in for hundreds of entries. A tool none of these
+/// covers is generated uncategorized and reported in
+/// [`SaveCategorizedToolsResult::unmatched_tools`].
+///
 /// # Examples
 ///
 /// ```
-/// use mcp_execution_server::types::{SaveCategorizedToolsParams, CategorizedTool};
+/// use mcp_execution_server::types::{CategoryRule, SaveCategorizedToolsParams, CategorizedTool};
 /// use uuid::Uuid;
 ///
 /// let params = SaveCategorizedToolsParams {
@@ -201,6 +271,13 @@ pub struct IntrospectedToolSummary {
 ///             short_description: "Create a new issue in a repository".to_string(),
 ///         },
 ///     ],
+///     rules: vec![CategoryRule {
+///         pattern: "*_issue*".to_string(),
+///         category: "issues".to_string(),
+///         keywords: vec!["issue".to_string()],
+///         short_description: None,
+///     }],
+///     exclude: Some(vec!["*_admin_*".to_string()]),
 /// };
 /// ```
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
@@ -214,8 +291,30 @@ pub struct SaveCategorizedToolsParams {
     /// MAX_TOOL_FILES)`, tighter than the flat limit below in nearly all cases; `MAX_TOOL_FILES`
     /// (`mcp_execution_skill`, 500) is still the absolute ceiling regardless of session, so it
     /// is what's mirrored here as a literal (schemars attributes cannot reference a `const`).
+    #[serde(default)]
     #[schemars(length(max = 500))]
     pub categorized_tools: Vec<CategorizedTool>,
+
+    /// Rules categorizing every tool no `categorized_tools` entry names, tried in order; the
+    /// first whose pattern matches a tool's name applies.
+    ///
+    /// Capped at `MAX_CATEGORY_RULES` (100) at runtime; mirrored here as a literal since
+    /// schemars attributes cannot reference a `const`.
+    #[serde(default)]
+    #[schemars(length(max = 100))]
+    pub rules: Vec<CategoryRule>,
+
+    /// Glob patterns (`*`, `?`) naming tools to leave out of the generated files.
+    ///
+    /// Excluded tools get no TypeScript file and are omitted from `index.ts`, `_meta.json`,
+    /// and the skill built from it; categorizations supplied for them are ignored. The filter
+    /// is recorded in `_meta.json`. When `exclude` is omitted, the filter recorded by a
+    /// previous generation for the same server is reused; an empty list clears it. Capped at
+    /// `MAX_TOOL_FILTER_PATTERNS` (`mcp_execution_core::tool_filter`, 500), mirrored here as a
+    /// literal.
+    #[serde(default)]
+    #[schemars(length(max = 500))]
+    pub exclude: Option<Vec<String>>,
 }
 
 /// A tool with categorization metadata from Claude.
@@ -263,6 +362,55 @@ pub struct CategorizedTool {
     pub short_description: String,
 }
 
+/// A rule categorizing every tool whose name matches `pattern`.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::CategoryRule;
+///
+/// let rule: CategoryRule = serde_json::from_str(
+///     r#"{ "match": "issue_*", "category": "issues", "keywords": ["issue", "bug"] }"#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(rule.pattern, "issue_*");
+/// assert!(rule.short_description.is_none());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CategoryRule {
+    /// Glob pattern (`*`, `?`) matched against the whole tool name.
+    ///
+    /// Capped at `MAX_TOOL_FILTER_PATTERN_LEN` (128 bytes) at runtime, like an `exclude`
+    /// pattern; mirrored here as a literal.
+    #[serde(rename = "match")]
+    #[schemars(length(max = 128))]
+    pub pattern: String,
+
+    /// Category for every matching tool.
+    ///
+    /// Capped at `MAX_CATEGORY_LEN` (100 bytes) at runtime, like
+    /// [`CategorizedTool::category`].
+    #[schemars(length(max = 100))]
+    pub category: String,
+
+    /// Keywords for every matching tool.
+    ///
+    /// Capped at `MAX_KEYWORDS_LEN` (500 bytes) at runtime, counted as the comma-separated
+    /// list [`CategorizedTool::keywords`] would hold.
+    #[serde(default)]
+    pub keywords: Vec<String>,
+
+    /// Description for every matching tool's header comment (default: the tool's own
+    /// description).
+    ///
+    /// Capped at `MAX_SHORT_DESCRIPTION_LEN` (320 bytes) at runtime, like
+    /// [`CategorizedTool::short_description`].
+    #[serde(default)]
+    #[schemars(length(max = 320))]
+    pub short_description: Option<String>,
+}
+
 /// Result from saving categorized tools.
 ///
 /// Reports success status, number of files generated, and any errors
@@ -279,6 +427,8 @@ pub struct CategorizedTool {
 ///     files_generated: 3,
 ///     output_dir: "~/.claude/servers/github".to_string(),
 ///     categories: HashMap::from([("issues".to_string(), 3)]),
+///     tools_excluded: 0,
+///     unmatched_tools: vec![],
 ///     errors: vec![],
 /// };
 ///
@@ -298,6 +448,13 @@ pub struct SaveCategorizedToolsResult {
     /// Count of tools per category
     pub categories: HashMap<String, usize>,
 
+    /// Number of introspected tools left out by the tool filter
+    pub tools_excluded: usize,
+
+    /// Generated tools that neither a `categorized_tools` entry nor a rule categorized
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unmatched_tools: Vec<String>,
+
     /// Any tools that failed to generate
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub errors: Vec<ToolGenerationError>,
@@ -407,6 +564,395 @@ pub struct GeneratedServerInfo {
     pub output_dir: String,
 }
 
+// ============================================================================
+// get_session_tools types
+// ============================================================================
+
+/// Parameters for fetching a page of a pending session's tools.
+///
+/// `cursor` carries the `page_size` and `max_description_len` of the call that returned it, so
+/// passing only `session_id` and `cursor` continues with the same settings; setting either
+/// field here overrides it from this page on.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::GetSessionToolsParams;
+/// use uuid::Uuid;
+///
+/// let params = GetSessionToolsParams {
+///     session_id: Uuid::new_v4(),
+///     cursor: Some("100:100:".to_string()),
+///     page_size: None,
+///     max_description_len: None,
+/// };
+/// ```
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetSessionToolsParams {
+    /// Session ID from `introspect_server` or `list_sessions`
+    pub session_id: Uuid,
+
+    /// `next_cursor` from `introspect_server` or a previous `get_session_tools` call
+    /// (default: start from the first tool)
+    #[serde(default)]
+    pub cursor: Option<String>,
+
+    /// Return at most this many tools (default: the cursor's page size, or every remaining
+    /// tool without a cursor).
+    ///
+    /// Capped at `MAX_TOOL_PAGE_SIZE` (500) at runtime; mirrored here as a literal since
+    /// schemars attributes cannot reference a `const`.
+    #[serde(default)]
+    #[schemars(range(min = 1, max = 500))]
+    pub page_size: Option<usize>,
+
+    /// Truncate each tool description to at most this many characters (default: the
+    /// cursor's limit, if any)
+    #[serde(default)]
+    pub max_description_len: Option<usize>,
+}
+
+/// One page of a pending session's tools.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::{GetSessionToolsResult, IntrospectedToolSummary};
+/// use chrono::Utc;
+/// use uuid::Uuid;
+///
+/// let result = GetSessionToolsResult {
+///     session_id: Uuid::new_v4(),
+///     server_id: "github".to_string(),
+///     tools_found: 2,
+///     tools: vec![IntrospectedToolSummary {
+///         name: "create_issue".to_string(),
+///         description: "Create a new issue".to_string(),
+///         parameters: vec!["title".to_string()],
+///         carried_over_category: None,
+///     }],
+///     next_cursor: Some("1:1:".to_string()),
+///     expires_at: Utc::now(),
+/// };
+///
+/// assert_eq!(result.tools.len(), 1);
+/// ```
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GetSessionToolsResult {
+    /// Session the tools belong to
+    pub session_id: Uuid,
+
+    /// Server identifier
+    pub server_id: String,
+
+    /// Number of tools discovered, across all pages
+    pub tools_found: usize,
+
+    /// This page's tools
+    pub tools: Vec<IntrospectedToolSummary>,
+
+    /// Cursor for the next page, absent on the last one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+
+    /// Session expiration time (ISO 8601)
+    pub expires_at: DateTime<Utc>,
+}
+
+// ============================================================================
+// Session tool types
+// ============================================================================
+
+/// Result from listing pending sessions.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::{ListSessionsResult, PendingSessionInfo};
+/// use chrono::Utc;
+/// use uuid::Uuid;
+///
+/// let result = ListSessionsResult {
+///     sessions: vec![PendingSessionInfo {
+///         session_id: Uuid::new_v4(),
+///         server_id: "github".to_string(),
+///         tool_count: 12,
+///         output_dir: "~/.claude/servers/github".to_string(),
+///         created_at: Utc::now(),
+///         expires_at: Utc::now(),
+///     }],
+///     total_sessions: 1,
+/// };
+///
+/// assert_eq!(result.total_sessions, 1);
+/// ```
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ListSessionsResult {
+    /// Pending sessions, oldest first
+    pub sessions: Vec<PendingSessionInfo>,
+
+    /// Total number of pending sessions
+    pub total_sessions: usize,
+}
+
+/// A pending `introspect_server` session, as reported by `list_sessions`.
+///
+/// Holds only server-controlled fields, so unlike [`IntrospectServerResult`] it carries nothing
+/// the introspected server reported about itself.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::PendingSessionInfo;
+/// use chrono::Utc;
+/// use uuid::Uuid;
+///
+/// let info = PendingSessionInfo {
+///     session_id: Uuid::new_v4(),
+///     server_id: "github".to_string(),
+///     tool_count: 12,
+///     output_dir: "~/.claude/servers/github".to_string(),
+///     created_at: Utc::now(),
+///     expires_at: Utc::now(),
+/// };
+///
+/// assert_eq!(info.server_id, "github");
+/// ```
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PendingSessionInfo {
+    /// Session ID for `save_categorized_tools`, `describe_session`, or `discard_session`
+    pub session_id: Uuid,
+
+    /// Server identifier
+    pub server_id: String,
+
+    /// Number of tools introspected
+    pub tool_count: usize,
+
+    /// Directory `save_categorized_tools` will write to
+    pub output_dir: String,
+
+    /// Session creation time (ISO 8601)
+    pub created_at: DateTime<Utc>,
+
+    /// Session expiration time (ISO 8601)
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Parameters for describing a pending session.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::DescribeSessionParams;
+/// use uuid::Uuid;
+///
+/// let params = DescribeSessionParams { session_id: Uuid::new_v4() };
+/// ```
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct DescribeSessionParams {
+    /// Session ID from `introspect_server` or `list_sessions`
+    pub session_id: Uuid,
+}
+
+/// Parameters for discarding a pending session.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::DiscardSessionParams;
+/// use uuid::Uuid;
+///
+/// let params = DiscardSessionParams { session_id: Uuid::new_v4() };
+/// ```
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct DiscardSessionParams {
+    /// Session ID from `introspect_server` or `list_sessions`
+    pub session_id: Uuid,
+}
+
+/// Result from discarding a pending session.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::DiscardSessionResult;
+/// use uuid::Uuid;
+///
+/// let result = DiscardSessionResult {
+///     session_id: Uuid::new_v4(),
+///     server_id: "github".to_string(),
+/// };
+///
+/// assert_eq!(result.server_id, "github");
+/// ```
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DiscardSessionResult {
+    /// The discarded session's ID
+    pub session_id: Uuid,
+
+    /// Server the discarded session introspected
+    pub server_id: String,
+}
+
+// ============================================================================
+// search_tools types
+// ============================================================================
+
+/// Parameters for searching the tools of every generated server.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::SearchToolsParams;
+///
+/// let params = SearchToolsParams {
+///     query: "create pull request".to_string(),
+///     limit: Some(5),
+///     server: None,
+/// };
+/// ```
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct SearchToolsParams {
+    /// Words to look for in tool names, keywords, categories, descriptions, and parameter names
+    #[schemars(length(max = 500))]
+    pub query: String,
+
+    /// Maximum number of results (default: 10)
+    #[schemars(range(min = 1, max = 50))]
+    pub limit: Option<usize>,
+
+    /// Only search this generated server's tools
+    pub server: Option<String>,
+}
+
+/// Result from searching generated tools.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::{SearchToolsResult, ToolSearchHit};
+///
+/// let result = SearchToolsResult {
+///     hits: vec![ToolSearchHit {
+///         server_id: "github".to_string(),
+///         name: "create_issue".to_string(),
+///         path: "~/.claude/servers/github/createIssue.ts".to_string(),
+///         signature: "createIssue(params: { title: string; body?: string })".to_string(),
+///         short_description: "Create a new issue".to_string(),
+///         category: Some("issues".to_string()),
+///         score: 4.2,
+///     }],
+///     tools_searched: 120,
+/// };
+///
+/// assert_eq!(result.hits.len(), 1);
+/// ```
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SearchToolsResult {
+    /// Matching tools, most relevant first
+    pub hits: Vec<ToolSearchHit>,
+
+    /// Number of tools the query was matched against
+    pub tools_searched: usize,
+}
+
+/// A tool matched by `search_tools`.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::ToolSearchHit;
+///
+/// let hit = ToolSearchHit {
+///     server_id: "github".to_string(),
+///     name: "create_issue".to_string(),
+///     path: "~/.claude/servers/github/createIssue.ts".to_string(),
+///     signature: "createIssue(params: { title: string })".to_string(),
+///     short_description: "Create a new issue".to_string(),
+///     category: None,
+///     score: 1.5,
+/// };
+///
+/// assert_eq!(hit.server_id, "github");
+/// ```
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ToolSearchHit {
+    /// Generated server the tool belongs to
+    pub server_id: String,
+
+    /// Original MCP tool name
+    pub name: String,
+
+    /// Path of the tool's generated TypeScript file
+    pub path: String,
+
+    /// Function signature of the generated binding
+    pub signature: String,
+
+    /// Short description, or the tool's description when it was never categorized
+    pub short_description: String,
+
+    /// Category, if the tool was categorized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+
+    /// BM25 relevance score
+    pub score: f64,
+}
+
+// ============================================================================
+// Prompt argument types
+// ============================================================================
+
+/// Arguments of the `onboard_server` prompt.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::OnboardServerArgs;
+///
+/// let args: OnboardServerArgs = serde_json::from_str(r#"{"name": "github"}"#).unwrap();
+/// assert_eq!(args.name, "github");
+/// ```
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct OnboardServerArgs {
+    /// Name of the server in your MCP config, used as its server ID
+    pub name: String,
+}
+
+/// Arguments of the `refresh_server` prompt.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::RefreshServerArgs;
+///
+/// let args: RefreshServerArgs = serde_json::from_str(r#"{"name": "github"}"#).unwrap();
+/// assert_eq!(args.name, "github");
+/// ```
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct RefreshServerArgs {
+    /// ID of the generated server to regenerate
+    pub name: String,
+}
+
+/// Arguments of the `write_skill` prompt.
+///
+/// # Examples
+///
+/// ```
+/// use mcp_execution_server::types::WriteSkillArgs;
+///
+/// let args: WriteSkillArgs = serde_json::from_str(r#"{"server": "github"}"#).unwrap();
+/// assert_eq!(args.server, "github");
+/// ```
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct WriteSkillArgs {
+    /// ID of the generated server to write a skill for
+    pub server: String,
+}
+
 // ============================================================================
 // State management types
 // ============================================================================
@@ -445,6 +991,9 @@ pub struct PendingGeneration {
     pub server_info: ServerInfo,
 
     /// Server configuration for regeneration if needed
+    ///
+    /// For a session loaded from a [`SessionStore`](crate::session_store::SessionStore) this is
+    /// the [`ServerConfig::redacted`] copy: enough to fingerprint, not to connect.
     pub config: ServerConfig,
 
     /// Caller-supplied `output_dir` override from `introspect_server`, exactly as received
@@ -462,12 +1011,20 @@ pub struct PendingGeneration {
 
     /// Session expiration time (30 minutes default)
     pub expires_at: DateTime<Utc>,
+
+    /// Categorizations from the output directory's existing `_meta.json` for tools that are
+    /// unchanged since it was generated, keyed by raw tool name.
+    pub previous_categorizations: HashMap<String, ToolCategorization>,
 }
 
 impl PendingGeneration {
     /// Default session timeout: 30 minutes.
     pub const DEFAULT_TIMEOUT_MINUTES: i64 = 30;
 
+    /// Longest a session can live, counted from `created_at`, however often it is refreshed:
+    /// 2 hours.
+    pub const MAX_LIFETIME_MINUTES: i64 = 120;
+
     /// Creates a new pending generation session.
     ///
     /// The session's `created_at`/`expires_at` are derived from `clock.now()`,
@@ -516,9 +1073,52 @@ impl PendingGeneration {
             output_dir_override,
             created_at: now,
             expires_at: now + chrono::Duration::minutes(Self::DEFAULT_TIMEOUT_MINUTES),
+            previous_categorizations: HashMap::new(),
         }
     }
 
+    /// Sets the categorizations carried over from the previous generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_server::types::PendingGeneration;
+    /// use mcp_execution_server::clock::SystemClock;
+    /// use mcp_execution_codegen::progressive::ToolCategorization;
+    /// # use mcp_execution_core::{ServerId, ServerConfig};
+    /// # use mcp_execution_introspector::ServerInfo;
+    /// use std::collections::HashMap;
+    ///
+    /// # fn example(server_info: ServerInfo) {
+    /// let previous = HashMap::from([(
+    ///     "create_issue".to_string(),
+    ///     ToolCategorization {
+    ///         category: "issues".to_string(),
+    ///         keywords: vec!["issue".to_string()],
+    ///         short_description: "Create an issue".to_string(),
+    ///     },
+    /// )]);
+    /// let pending = PendingGeneration::new(
+    ///     ServerId::new("github").unwrap(),
+    ///     server_info,
+    ///     ServerConfig::builder().command("npx".to_string()).build().unwrap(),
+    ///     None,
+    ///     &SystemClock,
+    /// )
+    /// .with_previous_categorizations(previous);
+    ///
+    /// assert_eq!(pending.previous_categorizations.len(), 1);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_previous_categorizations(
+        mut self,
+        previous_categorizations: HashMap<String, ToolCategorization>,
+    ) -> Self {
+        self.previous_categorizations = previous_categorizations;
+        self
+    }
+
     /// Checks if this session has expired, using `clock.now()` as the current time.
     ///
     /// # Examples
@@ -545,6 +1145,38 @@ impl PendingGeneration {
     pub fn is_expired(&self, clock: &dyn Clock) -> bool {
         clock.now() > self.expires_at
     }
+
+    /// Returns the expiry this session gets when refreshed at `clock.now()`.
+    ///
+    /// A refresh grants a full [`Self::DEFAULT_TIMEOUT_MINUTES`] from now, capped at
+    /// [`Self::MAX_LIFETIME_MINUTES`] after `created_at`, and never moves the expiry earlier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcp_execution_server::types::PendingGeneration;
+    /// use mcp_execution_server::clock::SystemClock;
+    /// # use mcp_execution_core::{ServerId, ServerConfig};
+    /// # use mcp_execution_introspector::ServerInfo;
+    ///
+    /// # fn example(server_info: ServerInfo) {
+    /// let pending = PendingGeneration::new(
+    ///     ServerId::new("test").unwrap(),
+    ///     server_info,
+    ///     ServerConfig::builder().command("echo".to_string()).build().unwrap(),
+    ///     None,
+    ///     &SystemClock,
+    /// );
+    ///
+    /// assert!(pending.refreshed_expiry(&SystemClock) >= pending.expires_at);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refreshed_expiry(&self, clock: &dyn Clock) -> DateTime<Utc> {
+        let refreshed = clock.now() + chrono::Duration::minutes(Self::DEFAULT_TIMEOUT_MINUTES);
+        let cap = self.created_at + chrono::Duration::minutes(Self::MAX_LIFETIME_MINUTES);
+        refreshed.min(cap).max(self.expires_at)
+    }
 }
 
 #[cfg(test)]
@@ -582,6 +1214,25 @@ mod tests {
         assert!(!pending.is_expired(&clock));
     }
 
+    #[test]
+    fn test_refreshed_expiry_is_capped_at_max_lifetime() {
+        let start = Utc::now();
+        let clock = TestClock::new(start);
+        let pending = create_test_pending_with_clock(&clock);
+
+        clock.advance(chrono::Duration::minutes(20));
+        assert_eq!(
+            pending.refreshed_expiry(&clock),
+            start + chrono::Duration::minutes(20 + PendingGeneration::DEFAULT_TIMEOUT_MINUTES)
+        );
+
+        clock.advance(chrono::Duration::hours(3));
+        assert_eq!(
+            pending.refreshed_expiry(&clock),
+            start + chrono::Duration::minutes(PendingGeneration::MAX_LIFETIME_MINUTES)
+        );
+    }
+
     #[test]
     fn test_pending_generation_expired_one_second_after_boundary() {
         let clock = TestClock::new(Utc::now());
@@ -616,13 +1267,16 @@ mod tests {
     #[test]
     fn introspect_server_params_shape_is_pinned() {
         let params = IntrospectServerParams {
-            server_id: "test".to_string(),
-            command: "echo".to_string(),
+            server_id: Some("test".to_string()),
+            command: Some("echo".to_string()),
             args: vec![],
             env: HashMap::new(),
+            from_config: None,
             output_dir: None,
             connect_timeout_secs: None,
             discover_timeout_secs: None,
+            page_size: None,
+            max_description_len: None,
         };
 
         // Fields that must never appear on this type without SSRF allowlisting logic: `url`,
@@ -632,9 +1286,12 @@ mod tests {
             command: _,
             args: _,
             env: _,
+            from_config: _,
             output_dir: _,
             connect_timeout_secs: _,
             discover_timeout_secs: _,
+            page_size: _,
+            max_description_len: _,
         } = params;
     }
 
@@ -671,6 +1328,49 @@ mod tests {
             props["categorized_tools"]["maxItems"],
             mcp_execution_skill::MAX_TOOL_FILES
         );
+        assert_eq!(
+            props["exclude"]["maxItems"],
+            mcp_execution_core::tool_filter::MAX_TOOL_FILTER_PATTERNS
+        );
+        assert_eq!(
+            props["rules"]["maxItems"],
+            crate::service::MAX_CATEGORY_RULES
+        );
+    }
+
+    #[test]
+    fn test_category_rule_schema_declares_length_bounds() {
+        use crate::service::{MAX_CATEGORY_LEN, MAX_SHORT_DESCRIPTION_LEN};
+
+        let schema = schemars::schema_for!(CategoryRule);
+        let props = schema.get("properties").unwrap().as_object().unwrap();
+
+        assert_eq!(
+            props["match"]["maxLength"],
+            mcp_execution_core::tool_filter::MAX_TOOL_FILTER_PATTERN_LEN
+        );
+        assert_eq!(props["category"]["maxLength"], MAX_CATEGORY_LEN);
+        assert_eq!(
+            props["short_description"]["maxLength"],
+            MAX_SHORT_DESCRIPTION_LEN
+        );
+    }
+
+    #[test]
+    fn test_save_categorized_tools_params_exclude_distinguishes_omitted_from_empty() {
+        let params: SaveCategorizedToolsParams = serde_json::from_value(serde_json::json!({
+            "session_id": Uuid::new_v4(),
+            "categorized_tools": [],
+        }))
+        .unwrap();
+        assert_eq!(params.exclude, None);
+
+        let params: SaveCategorizedToolsParams = serde_json::from_value(serde_json::json!({
+            "session_id": Uuid::new_v4(),
+            "exclude": [],
+        }))
+        .unwrap();
+        assert_eq!(params.exclude, Some(Vec::new()));
     }
 
     #[test]
@@ -690,6 +1390,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_page_size_schemas_declare_max_tool_page_size() {
+        use crate::service::MAX_TOOL_PAGE_SIZE;
+
+        for schema in [
+            schemars::schema_for!(IntrospectServerParams),
+            schemars::schema_for!(GetSessionToolsParams),
+        ] {
+            let page_size = &schema.get("properties").unwrap()["page_size"];
+            assert_eq!(page_size["maximum"], MAX_TOOL_PAGE_SIZE);
+            assert_eq!(page_size["minimum"], 1);
+        }
+    }
+
+    #[test]
+    fn test_search_tools_params_schema_declares_bounds() {
+        use crate::search::{MAX_SEARCH_LIMIT, MAX_SEARCH_QUERY_LEN};
+
+        let schema = schemars::schema_for!(SearchToolsParams);
+        let props = schema.get("properties").unwrap().as_object().unwrap();
+
+        assert_eq!(props["query"]["maxLength"], MAX_SEARCH_QUERY_LEN);
+        assert_eq!(props["limit"]["maximum"], MAX_SEARCH_LIMIT);
+        assert_eq!(props["limit"]["minimum"], 1);
+    }
+
     // Test helpers
     fn create_test_pending() -> PendingGeneration {
         create_test_pending_with_clock(&SystemClock)
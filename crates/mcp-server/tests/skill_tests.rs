@@ -38,6 +38,8 @@ async fn write_metadata_sidecar(dir: &std::path::Path, tool_names: &[&str]) {
                 category: Some("test-category".to_string()),
                 keywords: vec!["test".to_string(), (*name).to_string()],
                 description: Some(format!("Test tool: {name}")),
+                short_description: None,
+                source_digest: None,
                 parameters: vec![
                     ParameterMetadata {
                         name: "required_param".to_string(),
@@ -433,6 +435,8 @@ async fn test_scan_directory_file_too_large() {
             category: None,
             keywords: vec!["large".to_string()],
             description: Some(String::new()),
+            short_description: None,
+            source_digest: None,
             parameters: vec![],
         }],
         provenance: test_provenance(),
@@ -0,0 +1,801 @@
+//! Agent host profiles.
+//!
+//! Generated bindings are plain TypeScript files any agent can run, but every agent host looks
+//! for its instructions and its server config somewhere else. A [`HostProfile`] names those
+//! places for one host:
+//!
+//! | Host | Bindings | Instructions | Server config |
+//! |------|----------|--------------|---------------|
+//! | `claude` | `~/.claude/servers` | `~/.claude/skills/<id>/SKILL.md` | layered discovery |
+//! | `codex` | `~/.codex/servers` | section of `~/.codex/AGENTS.md` | `~/.codex/mcp.json` |
+//! | `cursor` | `~/.cursor/servers` | `.cursor/rules/<id>.mdc` | `~/.cursor/mcp.json` |
+//! | `generic` | `~/.mcp-execution/servers` | section of `AGENTS.md` | `~/.mcp-execution/mcp.json` |
+//!
+//! `claude` follows [`Paths`] (so `CLAUDE_CONFIG_DIR` still applies) and reads every layer
+//! [`McpConfigLocations`] knows about. `codex` honours [`CODEX_HOME_ENV`]. Project-relative
+//! paths resolve against the current project directory.
+//!
+//! Bindings can also be committed with a project: [`HostProfile::project_bindings_dir`] and
+//! [`HostProfile::project_instructions_path`] mirror the table inside the project root (e.g.
+//! `.claude/servers` and `.claude/skills/<id>/SKILL.md`), and [`SkillLayout::for_project`]
+//! refers to them relative to that root.
+//!
+//! Skills are always generated as `SKILL.md` — frontmatter plus a Markdown body — and
+//! [`InstructionsFormat::render`] adapts that document to the host's own artifact, so the
+//! templates and the `generate_skill` prompt are shared by every host.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::path::Path;
+//! use mcp_execution_core::paths::Paths;
+//! use mcp_execution_skill::host::{InstructionsFormat, host_profile};
+//!
+//! let cursor = host_profile("cursor").unwrap();
+//! let paths = Paths::new("/home/u");
+//! assert_eq!(cursor.bindings_dir(&paths), Path::new("/home/u/.cursor/servers"));
+//! assert_eq!(cursor.instructions_format(), InstructionsFormat::CursorRule);
+//! assert_eq!(
+//!     cursor.instructions_path(&paths, Path::new("/work/app"), "github"),
+//!     Path::new("/work/app/.cursor/rules/github.mdc"),
+//! );
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use mcp_execution_core::mcp_config::McpConfigLocations;
+use mcp_execution_core::paths::Paths;
+use mcp_execution_core::{sanitize_path_for_error, write_confined_file};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::parser::strip_frontmatter;
+use crate::types::SkillMetadata;
+
+/// Environment variable Codex uses to relocate `~/.codex`.
+pub const CODEX_HOME_ENV: &str = "CODEX_HOME";
+
+/// The host used when none is selected.
+pub const DEFAULT_HOST: &str = "claude";
+
+/// Names accepted by [`host_profile`], in display order.
+pub const HOST_NAMES: &[&str] = &["claude", "codex", "cursor", "generic"];
+
+/// Where one agent host expects generated bindings, instructions, and server config.
+///
+/// Implemented by [`ClaudeCode`], [`Codex`], [`Cursor`], and [`Generic`]; look one up by name
+/// with [`host_profile`].
+pub trait HostProfile: fmt::Debug + Send + Sync {
+    /// The name selecting this profile (`--host <name>`).
+    fn name(&self) -> &'static str;
+
+    /// Directory generated TypeScript bindings are written to, one subdirectory per server.
+    fn bindings_dir(&self, paths: &Paths) -> PathBuf;
+
+    /// The only config file servers are read from, or `None` to use every layer
+    /// [`McpConfigLocations`] discovers.
+    fn config_file(&self, paths: &Paths) -> Option<PathBuf>;
+
+    /// The kind of instruction artifact this host reads.
+    fn instructions_format(&self) -> InstructionsFormat;
+
+    /// Where the instructions for `server_id` are written by default.
+    fn instructions_path(&self, paths: &Paths, project_dir: &Path, server_id: &str) -> PathBuf;
+
+    /// Bindings directory inside `project_root`, for bindings committed with the project.
+    fn project_bindings_dir(&self, project_root: &Path) -> PathBuf;
+
+    /// Where the instructions for `server_id` are written when they are committed with the
+    /// project at `project_root`.
+    fn project_instructions_path(&self, project_root: &Path, server_id: &str) -> PathBuf;
+
+    /// Points `locations` at this host's bindings directory and config file.
+    ///
+    /// An explicit config file already in `locations` (`--config` or `MCP_EXECUTION_CONFIG`)
+    /// is kept. Locations without a home directory are returned unchanged.
+    #[must_use]
+    fn configure(&self, locations: McpConfigLocations) -> McpConfigLocations {
+        let Some(paths) = locations.paths().cloned() else {
+            return locations;
+        };
+        let config_file = self.config_file(&paths);
+        let bindings_dir = self.bindings_dir(&paths);
+        let locations = locations.with_paths(paths.with_servers_dir(bindings_dir));
+        match config_file {
+            Some(file) if locations.explicit().is_none() => locations.with_explicit(file),
+            _ => locations,
+        }
+    }
+}
+
+/// Looks up a built-in profile by name (see [`HOST_NAMES`]).
+#[must_use]
+pub fn host_profile(name: &str) -> Option<&'static dyn HostProfile> {
+    match name {
+        "claude" => Some(&ClaudeCode),
+        "codex" => Some(&Codex),
+        "cursor" => Some(&Cursor),
+        "generic" => Some(&Generic),
+        _ => None,
+    }
+}
+
+/// Claude Code: everything under `~/.claude`, skills as `SKILL.md`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClaudeCode;
+
+impl HostProfile for ClaudeCode {
+    fn name(&self) -> &'static str {
+        "claude"
+    }
+
+    fn bindings_dir(&self, paths: &Paths) -> PathBuf {
+        paths.claude_dir().join("servers")
+    }
+
+    fn config_file(&self, _paths: &Paths) -> Option<PathBuf> {
+        None
+    }
+
+    fn instructions_format(&self) -> InstructionsFormat {
+        InstructionsFormat::SkillMd
+    }
+
+    fn instructions_path(&self, paths: &Paths, _project_dir: &Path, server_id: &str) -> PathBuf {
+        paths.skills_dir().join(server_id).join("SKILL.md")
+    }
+
+    fn project_bindings_dir(&self, project_root: &Path) -> PathBuf {
+        project_root.join(".claude").join("servers")
+    }
+
+    fn project_instructions_path(&self, project_root: &Path, server_id: &str) -> PathBuf {
+        project_root
+            .join(".claude")
+            .join("skills")
+            .join(server_id)
+            .join("SKILL.md")
+    }
+}
+
+/// `OpenAI` Codex CLI: bindings under `~/.codex`, instructions in its global `AGENTS.md`.
+///
+/// Codex keeps its own servers in `config.toml`, which the bridge cannot read, so this profile
+/// reads `mcp.json` (standard `mcpServers` format) next to it instead.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Codex;
+
+impl Codex {
+    fn home(paths: &Paths) -> PathBuf {
+        codex_home(paths, std::env::var_os(CODEX_HOME_ENV).map(PathBuf::from))
+    }
+}
+
+fn codex_home(paths: &Paths, env: Option<PathBuf>) -> PathBuf {
+    env.filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| paths.home().join(".codex"))
+}
+
+impl HostProfile for Codex {
+    fn name(&self) -> &'static str {
+        "codex"
+    }
+
+    fn bindings_dir(&self, paths: &Paths) -> PathBuf {
+        Self::home(paths).join("servers")
+    }
+
+    fn config_file(&self, paths: &Paths) -> Option<PathBuf> {
+        Some(Self::home(paths).join("mcp.json"))
+    }
+
+    fn instructions_format(&self) -> InstructionsFormat {
+        InstructionsFormat::AgentsSection
+    }
+
+    fn instructions_path(&self, paths: &Paths, _project_dir: &Path, _server_id: &str) -> PathBuf {
+        Self::home(paths).join("AGENTS.md")
+    }
+
+    fn project_bindings_dir(&self, project_root: &Path) -> PathBuf {
+        project_root.join(".codex").join("servers")
+    }
+
+    fn project_instructions_path(&self, project_root: &Path, _server_id: &str) -> PathBuf {
+        project_root.join("AGENTS.md")
+    }
+}
+
+/// Cursor: bindings under `~/.cursor`, one project rule per server.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cursor;
+
+impl HostProfile for Cursor {
+    fn name(&self) -> &'static str {
+        "cursor"
+    }
+
+    fn bindings_dir(&self, paths: &Paths) -> PathBuf {
+        paths.home().join(".cursor").join("servers")
+    }
+
+    fn config_file(&self, paths: &Paths) -> Option<PathBuf> {
+        Some(paths.home().join(".cursor").join("mcp.json"))
+    }
+
+    fn instructions_format(&self) -> InstructionsFormat {
+        InstructionsFormat::CursorRule
+    }
+
+    fn instructions_path(&self, _paths: &Paths, project_dir: &Path, server_id: &str) -> PathBuf {
+        self.project_instructions_path(project_dir, server_id)
+    }
+
+    fn project_bindings_dir(&self, project_root: &Path) -> PathBuf {
+        project_root.join(".cursor").join("servers")
+    }
+
+    fn project_instructions_path(&self, project_root: &Path, server_id: &str) -> PathBuf {
+        project_root
+            .join(".cursor")
+            .join("rules")
+            .join(format!("{server_id}.mdc"))
+    }
+}
+
+/// Any agent that reads `AGENTS.md`: bindings and config under `~/.mcp-execution`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Generic;
+
+impl HostProfile for Generic {
+    fn name(&self) -> &'static str {
+        "generic"
+    }
+
+    fn bindings_dir(&self, paths: &Paths) -> PathBuf {
+        paths.home().join(".mcp-execution").join("servers")
+    }
+
+    fn config_file(&self, paths: &Paths) -> Option<PathBuf> {
+        Some(paths.home().join(".mcp-execution").join("mcp.json"))
+    }
+
+    fn instructions_format(&self) -> InstructionsFormat {
+        InstructionsFormat::AgentsSection
+    }
+
+    fn instructions_path(&self, _paths: &Paths, project_dir: &Path, server_id: &str) -> PathBuf {
+        self.project_instructions_path(project_dir, server_id)
+    }
+
+    fn project_bindings_dir(&self, project_root: &Path) -> PathBuf {
+        project_root.join(".mcp-execution").join("servers")
+    }
+
+    fn project_instructions_path(&self, project_root: &Path, _server_id: &str) -> PathBuf {
+        project_root.join("AGENTS.md")
+    }
+}
+
+/// The instruction artifact a host reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionsFormat {
+    /// A standalone `SKILL.md`, written as generated.
+    SkillMd,
+
+    /// A Cursor project rule (`.mdc`): `description`/`alwaysApply` frontmatter, then the body.
+    CursorRule,
+
+    /// A marked section of a shared `AGENTS.md`, replaced in place on regeneration.
+    AgentsSection,
+}
+
+impl InstructionsFormat {
+    /// Adapts generated `SKILL.md` content to this format.
+    ///
+    /// `metadata` is what [`extract_skill_metadata`](crate::extract_skill_metadata) returned for
+    /// `skill_md`. For [`AgentsSection`](Self::AgentsSection) every heading is demoted one level
+    /// so the skill nests under the file's own title.
+    ///
+    /// # Panics
+    ///
+    /// Does not panic in practice: the Cursor frontmatter is one string and one boolean, which
+    /// always serialize.
+    #[must_use]
+    pub fn render(self, skill_md: &str, metadata: &SkillMetadata, server_id: &str) -> String {
+        match self {
+            Self::SkillMd => skill_md.to_string(),
+            Self::CursorRule => {
+                let frontmatter = CursorRuleFrontmatter {
+                    description: &metadata.description,
+                    always_apply: false,
+                };
+                // PANIC: a `&str` and a `bool` always serialize; see `Frontmatter::to_yaml_block`.
+                let yaml = serde_saphyr::to_string(&frontmatter)
+                    .expect("YAML serialization of CursorRuleFrontmatter is infallible");
+                format!("---\n{yaml}---\n\n{}", strip_frontmatter(skill_md))
+            }
+            Self::AgentsSection => format!(
+                "{}\n{}\n{}\n",
+                section_begin(server_id),
+                demote_headings(strip_frontmatter(skill_md)).trim_end(),
+                section_end(server_id),
+            ),
+        }
+    }
+
+    /// Whether `existing` file content already holds instructions for `server_id`.
+    ///
+    /// For whole-file formats any existing file counts; for
+    /// [`AgentsSection`](Self::AgentsSection) only a section marked for `server_id` does.
+    #[must_use]
+    pub fn contains(self, existing: &str, server_id: &str) -> bool {
+        match self {
+            Self::SkillMd | Self::CursorRule => true,
+            Self::AgentsSection => section_range(existing, server_id).is_some(),
+        }
+    }
+
+    /// Combines `rendered` (from [`render`](Self::render)) with a file's `existing` content.
+    ///
+    /// Whole-file formats replace the file. [`AgentsSection`](Self::AgentsSection) replaces the
+    /// section marked for `server_id`, or appends one, leaving the rest of the file untouched.
+    #[must_use]
+    pub fn merge(self, existing: Option<&str>, rendered: &str, server_id: &str) -> String {
+        let existing = match (self, existing) {
+            (Self::AgentsSection, Some(existing)) if !existing.trim().is_empty() => existing,
+            _ => return rendered.to_string(),
+        };
+        if let Some(range) = section_range(existing, server_id) {
+            let mut merged = existing.to_string();
+            merged.replace_range(range, rendered);
+            return merged;
+        }
+        let separator = if existing.ends_with("\n\n") {
+            ""
+        } else if existing.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        format!("{existing}{separator}{rendered}")
+    }
+}
+
+/// Shape of a Cursor rule's frontmatter. No `globs`: the rule is attached when the agent
+/// decides the description is relevant.
+#[derive(Serialize)]
+struct CursorRuleFrontmatter<'a> {
+    description: &'a str,
+    #[serde(rename = "alwaysApply")]
+    always_apply: bool,
+}
+
+fn section_begin(server_id: &str) -> String {
+    format!("<!-- mcp-execution:begin {server_id} -->")
+}
+
+fn section_end(server_id: &str) -> String {
+    format!("<!-- mcp-execution:end {server_id} -->")
+}
+
+/// Byte range of the section marked for `server_id`, including a newline after its end marker.
+fn section_range(content: &str, server_id: &str) -> Option<std::ops::Range<usize>> {
+    let start = content.find(&section_begin(server_id))?;
+    let end_marker = section_end(server_id);
+    let end = start + content[start..].find(&end_marker)? + end_marker.len();
+    let end = if content[end..].starts_with('\n') {
+        end + 1
+    } else {
+        end
+    };
+    Some(start..end)
+}
+
+/// Adds one `#` to every ATX heading outside fenced code blocks.
+fn demote_headings(markdown: &str) -> String {
+    let mut in_fence = false;
+    let mut out = String::with_capacity(markdown.len() + 16);
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if !in_fence && line.starts_with('#') {
+            out.push('#');
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Where a skill's bindings and instructions live, as its generated text refers to them.
+///
+/// Both fields are display strings — see [`display_path`] — not paths to open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillLayout {
+    /// The bindings directory the skill's commands use (without the server segment).
+    pub bindings_dir: String,
+
+    /// Where the instructions are written by default; becomes
+    /// [`GenerateSkillResult::default_output_path_hint`](crate::GenerateSkillResult::default_output_path_hint).
+    pub output_path_hint: String,
+}
+
+impl SkillLayout {
+    /// Claude Code's default layout under `~/.claude`.
+    #[must_use]
+    pub fn claude(server_id: &str) -> Self {
+        Self {
+            bindings_dir: crate::context::DEFAULT_BINDINGS_DIR.to_string(),
+            output_path_hint: format!("~/.claude/skills/{server_id}/SKILL.md"),
+        }
+    }
+
+    /// The layout of `host`, with bindings read from `bindings_dir`.
+    ///
+    /// `bindings_dir` is passed separately because callers can move it (`--servers-dir`).
+    #[must_use]
+    pub fn for_host(
+        host: &dyn HostProfile,
+        paths: &Paths,
+        bindings_dir: &Path,
+        project_dir: &Path,
+        server_id: &str,
+    ) -> Self {
+        Self {
+            bindings_dir: display_path(bindings_dir, paths.home()),
+            output_path_hint: display_path(
+                &host.instructions_path(paths, project_dir, server_id),
+                paths.home(),
+            ),
+        }
+    }
+
+    /// The layout of `host` for a skill committed with the project at `project_root`.
+    ///
+    /// Both paths are relative to the project root, which is where agents run the skill's
+    /// commands from, so the skill names no directory on the generating machine.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use mcp_execution_skill::host::{ClaudeCode, SkillLayout};
+    ///
+    /// let layout = SkillLayout::for_project(&ClaudeCode, Path::new("/work/app"), "github");
+    /// assert_eq!(layout.bindings_dir, ".claude/servers");
+    /// assert_eq!(layout.output_path_hint, ".claude/skills/github/SKILL.md");
+    /// ```
+    #[must_use]
+    pub fn for_project(host: &dyn HostProfile, project_root: &Path, server_id: &str) -> Self {
+        Self {
+            bindings_dir: project_relative(&host.project_bindings_dir(project_root), project_root),
+            output_path_hint: project_relative(
+                &host.project_instructions_path(project_root, server_id),
+                project_root,
+            ),
+        }
+    }
+}
+
+/// Shows `path` relative to `root`, with `/` separators, as instructions committed with a
+/// project refer to it. Paths outside `root` are shown as given.
+fn project_relative(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root).map_or_else(
+        |_| path.display().to_string(),
+        |rest| {
+            rest.components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        },
+    )
+}
+
+/// Shows `path` with a leading `~/` when it is under `home`, as the generated instructions
+/// refer to it.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use mcp_execution_skill::host::display_path;
+///
+/// let home = Path::new("/home/u");
+/// assert_eq!(display_path(Path::new("/home/u/.codex/servers"), home), "~/.codex/servers");
+/// assert_eq!(display_path(Path::new("/srv/servers"), home), "/srv/servers");
+/// ```
+#[must_use]
+pub fn display_path(path: &Path, home: &Path) -> String {
+    match path.strip_prefix(home) {
+        Ok(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Ok(rest) => format!("~/{}", rest.display()),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+/// Errors from [`write_instructions`].
+#[derive(Debug, Error)]
+pub enum InstructionsError {
+    /// Instructions for the server are already there and `overwrite` was not set.
+    #[error("instructions for '{server_id}' already exist in {path}")]
+    AlreadyExists {
+        /// The server whose instructions exist.
+        server_id: String,
+        /// Sanitized target path.
+        path: String,
+    },
+
+    /// Reading the existing file, creating its directory, or writing it failed.
+    #[error("failed to write {path}: {source}")]
+    Io {
+        /// Sanitized target path.
+        path: String,
+        /// Underlying error.
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Writes `rendered` instructions for `server_id` to `path`, merging with what is there.
+///
+/// Creates the parent directory. Returns whether earlier instructions for the server were
+/// replaced. The final write goes through
+/// [`write_confined_file`](mcp_execution_core::write_confined_file), so a symlink at `path` is
+/// refused rather than followed.
+///
+/// # Errors
+///
+/// Returns [`InstructionsError::AlreadyExists`] if the file already holds instructions for the
+/// server and `overwrite` is false, or [`InstructionsError::Io`] if any filesystem step fails.
+pub async fn write_instructions(
+    format: InstructionsFormat,
+    path: &Path,
+    rendered: &str,
+    server_id: &str,
+    overwrite: bool,
+) -> Result<bool, InstructionsError> {
+    let io_error = |source| InstructionsError::Io {
+        path: sanitize_path_for_error(path),
+        source,
+    };
+
+    let existing = match tokio::fs::read_to_string(path).await {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(io_error(e)),
+    };
+    let replaced = existing
+        .as_deref()
+        .is_some_and(|existing| format.contains(existing, server_id));
+    if replaced && !overwrite {
+        return Err(InstructionsError::AlreadyExists {
+            server_id: server_id.to_string(),
+            path: sanitize_path_for_error(path),
+        });
+    }
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(io_error)?;
+    }
+    let merged = format.merge(existing.as_deref(), rendered, server_id);
+    write_confined_file(path, merged.as_bytes())
+        .await
+        .map_err(|e| io_error(std::io::Error::other(e.to_string())))?;
+    Ok(replaced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract_skill_metadata;
+
+    const SKILL_MD: &str = "---\nname: github-progressive\ndescription: GitHub tools\n---\n\n\
+                            # github-progressive\n\nIntro.\n\n## Usage\n\n```bash\n# list\nls\n```\n";
+
+    fn metadata() -> SkillMetadata {
+        extract_skill_metadata(SKILL_MD).unwrap()
+    }
+
+    #[test]
+    fn every_name_resolves() {
+        for name in HOST_NAMES {
+            assert_eq!(host_profile(name).unwrap().name(), *name);
+        }
+        assert!(host_profile("emacs").is_none());
+    }
+
+    #[test]
+    fn claude_follows_relocated_config_dir() {
+        let paths = Paths::new("/home/u").with_claude_dir("/cfg");
+        assert_eq!(ClaudeCode.bindings_dir(&paths), Path::new("/cfg/servers"));
+        assert_eq!(
+            ClaudeCode.instructions_path(&paths, Path::new("/work"), "github"),
+            Path::new("/cfg/skills/github/SKILL.md")
+        );
+        assert!(ClaudeCode.config_file(&paths).is_none());
+    }
+
+    #[test]
+    fn codex_home_env_relocates_everything() {
+        let paths = Paths::new("/home/u");
+        assert_eq!(codex_home(&paths, None), Path::new("/home/u/.codex"));
+        assert_eq!(
+            codex_home(&paths, Some(PathBuf::new())),
+            Path::new("/home/u/.codex")
+        );
+        assert_eq!(
+            codex_home(&paths, Some(PathBuf::from("/opt/codex"))),
+            Path::new("/opt/codex")
+        );
+    }
+
+    #[test]
+    fn configure_sets_bindings_and_config_file() {
+        let locations = Cursor.configure(McpConfigLocations::new().with_home_dir("/home/u"));
+        let paths = locations.paths().unwrap();
+        assert_eq!(paths.servers_dir(), Path::new("/home/u/.cursor/servers"));
+        assert_eq!(
+            locations.explicit(),
+            Some(Path::new("/home/u/.cursor/mcp.json"))
+        );
+    }
+
+    #[test]
+    fn configure_keeps_explicit_config() {
+        let locations = Generic.configure(
+            McpConfigLocations::new()
+                .with_home_dir("/home/u")
+                .with_explicit("/etc/mcp.json"),
+        );
+        assert_eq!(locations.explicit(), Some(Path::new("/etc/mcp.json")));
+    }
+
+    #[test]
+    fn configure_claude_keeps_layered_discovery() {
+        let locations = ClaudeCode.configure(McpConfigLocations::new().with_home_dir("/home/u"));
+        assert!(locations.explicit().is_none());
+        assert_eq!(
+            locations.paths().unwrap().servers_dir(),
+            Path::new("/home/u/.claude/servers")
+        );
+    }
+
+    #[test]
+    fn cursor_rule_replaces_frontmatter() {
+        let rule = InstructionsFormat::CursorRule.render(SKILL_MD, &metadata(), "github");
+        assert!(rule.starts_with("---\ndescription: GitHub tools\nalwaysApply: false\n---\n\n"));
+        assert!(rule.contains("# github-progressive\n"));
+        assert!(!rule.contains("name: github-progressive"));
+    }
+
+    #[test]
+    fn agents_section_demotes_headings_outside_fences() {
+        let section = InstructionsFormat::AgentsSection.render(SKILL_MD, &metadata(), "github");
+        assert!(section.starts_with("<!-- mcp-execution:begin github -->\n## github-progressive"));
+        assert!(section.contains("\n### Usage\n"));
+        assert!(section.contains("\n# list\n"));
+        assert!(section.ends_with("<!-- mcp-execution:end github -->\n"));
+    }
+
+    #[test]
+    fn agents_section_merge_replaces_only_its_own_section() {
+        let format = InstructionsFormat::AgentsSection;
+        let old = format.render(SKILL_MD, &metadata(), "github");
+        let other = format.render(SKILL_MD, &metadata(), "slack");
+        let existing = format!("# Project\n\nRules.\n\n{old}{other}");
+        assert!(format.contains(&existing, "github"));
+        assert!(!format.contains(&existing, "linear"));
+
+        let new = format!(
+            "{}\nnew\n{}\n",
+            section_begin("github"),
+            section_end("github")
+        );
+        let merged = format.merge(Some(&existing), &new, "github");
+        assert_eq!(merged, format!("# Project\n\nRules.\n\n{new}{other}"));
+    }
+
+    #[test]
+    fn agents_section_merge_appends_after_blank_line() {
+        let format = InstructionsFormat::AgentsSection;
+        let section = format.render(SKILL_MD, &metadata(), "github");
+        assert_eq!(
+            format.merge(Some("# Project"), &section, "github"),
+            format!("# Project\n\n{section}")
+        );
+        assert_eq!(format.merge(None, &section, "github"), section);
+    }
+
+    #[test]
+    fn layout_for_host_abbreviates_home() {
+        let paths = Paths::new("/home/u");
+        let layout = SkillLayout::for_host(
+            &Codex,
+            &paths,
+            Path::new("/home/u/.codex/servers"),
+            Path::new("/work"),
+            "github",
+        );
+        if std::env::var_os(CODEX_HOME_ENV).is_none() {
+            assert_eq!(layout.output_path_hint, "~/.codex/AGENTS.md");
+        }
+        assert_eq!(layout.bindings_dir, "~/.codex/servers");
+
+        let claude = SkillLayout::for_host(
+            &ClaudeCode,
+            &paths,
+            &ClaudeCode.bindings_dir(&paths),
+            Path::new("/work"),
+            "github",
+        );
+        assert_eq!(claude, SkillLayout::claude("github"));
+    }
+
+    #[test]
+    fn layout_for_project_is_relative_for_every_host() {
+        let root = Path::new("/work/app");
+        for name in HOST_NAMES {
+            let layout = SkillLayout::for_project(host_profile(name).unwrap(), root, "github");
+            assert!(!layout.bindings_dir.starts_with(['/', '~']), "{layout:?}");
+            assert!(
+                !layout.output_path_hint.starts_with(['/', '~']),
+                "{layout:?}"
+            );
+        }
+        assert_eq!(
+            SkillLayout::for_project(&Codex, root, "github").output_path_hint,
+            "AGENTS.md"
+        );
+    }
+
+    #[test]
+    fn display_path_abbreviates_home() {
+        let home = Path::new("/home/u");
+        assert_eq!(display_path(home, home), "~");
+        assert_eq!(
+            display_path(Path::new("/home/u/.claude/servers"), home),
+            "~/.claude/servers"
+        );
+    }
+
+    #[tokio::test]
+    async fn write_instructions_refuses_existing_section_without_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("AGENTS.md");
+        std::fs::write(&path, "# Project\n").unwrap();
+        let format = InstructionsFormat::AgentsSection;
+        let section = format.render(SKILL_MD, &metadata(), "github");
+
+        assert!(
+            !write_instructions(format, &path, &section, "github", false)
+                .await
+                .unwrap()
+        );
+        let err = write_instructions(format, &path, &section, "github", false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, InstructionsError::AlreadyExists { .. }));
+        assert!(
+            write_instructions(format, &path, &section, "github", true)
+                .await
+                .unwrap()
+        );
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, format!("# Project\n\n{section}"));
+    }
+
+    #[tokio::test]
+    async fn write_instructions_creates_rule_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Cursor.instructions_path(&Paths::new(dir.path()), dir.path(), "github");
+        let format = InstructionsFormat::CursorRule;
+        let rule = format.render(SKILL_MD, &metadata(), "github");
+
+        write_instructions(format, &path, &rule, "github", false)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), rule);
+    }
+}
@@ -9,6 +9,7 @@
 //! 1. `parser` - Reads the `_meta.json` sidecar emitted by codegen
 //! 2. `context` - Builds structured context from parsed tools
 //! 3. `template` - Renders Handlebars template with context
+//! 4. `host` - Adapts the rendered skill to the agent host's own instruction artifact
 //!
 //! # Examples
 //!
@@ -25,12 +26,14 @@
 #![warn(missing_docs, missing_debug_implementations)]
 
 mod context;
+pub mod host;
 mod output_path;
 mod parser;
 mod template;
 pub mod types;
 
-pub use context::build_skill_context;
+pub use context::{build_skill_context, build_skill_context_for};
+pub use host::{HostProfile, InstructionsFormat, SkillLayout, host_profile};
 pub use output_path::{OutputPathError, resolve_skill_output_path};
 pub use parser::{
     MAX_FILE_SIZE, MAX_FRONTMATTER_SIZE, MAX_TOOL_FILES, ParsedParameter, ParsedToolFile,
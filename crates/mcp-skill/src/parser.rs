@@ -822,6 +822,14 @@ pub fn extract_skill_metadata(
     })
 }
 
+/// The Markdown body of `content`, without its `---`-delimited frontmatter and the blank
+/// lines after it. Content without frontmatter is returned unchanged.
+pub fn strip_frontmatter(content: &str) -> &str {
+    FRONTMATTER_REGEX.find(content).map_or(content, |m| {
+        content[m.end()..].trim_start_matches(['\r', '\n'])
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -851,6 +859,8 @@ mod tests {
                     category: Some("test".to_string()),
                     keywords: vec!["test".to_string()],
                     description: Some(format!("Tool {i}")),
+                    short_description: None,
+                    source_digest: None,
                     parameters: vec![ParameterMetadata {
                         name: "param".to_string(),
                         typescript_type: "string".to_string(),
@@ -915,6 +925,8 @@ mod tests {
                 category: None,
                 keywords: vec![],
                 description: None,
+                short_description: None,
+                source_digest: None,
                 parameters: vec![],
             },
             ToolMetadata {
@@ -923,6 +935,8 @@ mod tests {
                 category: None,
                 keywords: vec![],
                 description: None,
+                short_description: None,
+                source_digest: None,
                 parameters: vec![],
             },
         ];
@@ -1318,6 +1332,15 @@ More content.
         ));
     }
 
+    #[test]
+    fn test_strip_frontmatter() {
+        assert_eq!(
+            strip_frontmatter("---\nname: a\n---\n\n# Body\n"),
+            "# Body\n"
+        );
+        assert_eq!(strip_frontmatter("# Body\n"), "# Body\n");
+    }
+
     #[test]
     fn test_extract_skill_metadata_missing_name() {
         let content = "---\ndescription: test\n---\n# Test";
@@ -281,6 +281,7 @@ mod tests {
             }],
             generation_prompt: "Pre-built prompt".to_string(),
             default_output_path_hint: "~/.claude/skills/test/SKILL.md".to_string(),
+            bindings_dir: "~/.claude/servers".to_string(),
             warnings: vec![],
             use_case_hints: vec![],
         }
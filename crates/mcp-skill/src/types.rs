@@ -136,6 +136,7 @@ pub struct GenerateSkillParams {
 ///     example_tools: vec![],
 ///     generation_prompt: "Generate a SKILL.md for github...".to_string(),
 ///     default_output_path_hint: "~/.claude/skills/github/SKILL.md".to_string(),
+///     bindings_dir: "~/.claude/servers".to_string(),
 ///     warnings: vec![],
 ///     use_case_hints: vec![],
 /// };
@@ -181,6 +182,11 @@ pub struct GenerateSkillResult {
     /// own default.
     pub default_output_path_hint: String,
 
+    /// Directory holding the server's generated bindings, as the skill's commands refer to it
+    /// (`~/.claude/servers` for Claude Code; see [`crate::host`]).
+    #[serde(default = "default_bindings_dir")]
+    pub bindings_dir: String,
+
     /// Non-fatal drift/degradation warnings. Two independent sources feed this field, both
     /// additive rather than either overwriting the other: [`crate::build_skill_context`] seeds
     /// it with any `use_case_hints` sanitization warnings (a hint dropped past
@@ -201,6 +207,10 @@ pub struct GenerateSkillResult {
     pub use_case_hints: Vec<String>,
 }
 
+fn default_bindings_dir() -> String {
+    crate::context::DEFAULT_BINDINGS_DIR.to_string()
+}
+
 /// A category of tools for the skill.
 ///
 /// # Examples